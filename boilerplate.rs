@@ -1,22 +1,28 @@
-use std::fmt::Display;
+use crate::{answer::Answer, vis::Visualizer};
 
 // Handy references:
 // - https://doc.rust-lang.org/std/iter/trait.Iterator.html
 // - https://docs.rs/itertools/0.8.2/itertools/trait.Itertools.html
 // - https://docs.rs/regex/latest/regex/struct.Regex.html
+//
+// Take `unsolved` off a part once it has a real solver, so the calendar
+// counts it.
 
-pub fn part1(_input: String, _vis: bool) -> Box<dyn Display> {
-    Box::new("todo")
+#[aoc(year = YEAR, day = DAY, part = 1, unsolved)]
+pub fn part1(_input: String, _vis: &Visualizer) -> anyhow::Result<Answer> {
+    Ok(Answer::Unsolved)
 }
 
-pub fn part2(_input: String, _vis: bool) -> Box<dyn Display> {
-    Box::new("todo")
+#[aoc(year = YEAR, day = DAY, part = 2, unsolved)]
+pub fn part2(_input: String, _vis: &Visualizer) -> anyhow::Result<Answer> {
+    Ok(Answer::Unsolved)
 }
 
 #[cfg(test)]
 mod test {
-    const TEST_INPUT: &'static str = r"";
+    const TEST_INPUT: &str = r"";
 
     crate::test::aoc_test!(part1, TEST_INPUT, "todo");
     crate::test::aoc_test!(part2, TEST_INPUT, "todo");
+    crate::test::aoc_examples!(YEAR, DAY, 1 => part1, 2 => part2);
 }
@@ -1,64 +1,224 @@
-//use proc_macro::{Ident, Punct, TokenTree};
-use proc_macro::TokenStream;
+use proc_macro::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
 
 // Attribute examples:
 //   #[aoc(year = 2022, day = 1, part = 1)]
 //   #[aoc(year = 2022, day = 1, part = 1, label = "blah")]
+//   #[aoc(year = 2023, day = 5, part = 2, label = "ranges", slow)]
+//   #[aoc(year = 2023, day = 6, part = 1, unsolved)]
+//   #[aoc(year = 2023, day = 6, part = 2, label = r"a\b", slow, unsolved)]
 // Solution function examples:
-//   no option to visualize:
-//     fn solve(input: String) -> Box<dyn Display> {}
-//   can have visualizations turned on:
-//     fn solve(input: String, vis: bool) -> Box<dyn Display> {}
+//   fn solve(input: String, vis: &Visualizer) -> anyhow::Result<Answer> {}
+//   fn solve(input: String, vis: &Visualizer) -> Box<dyn Display> {}
+//
+// The function is left as-is, and a `crate::registry::Registration` is
+// submitted to `inventory` next to it so that `crate::registry::register`
+// can find it at startup.
 #[proc_macro_attribute]
-pub fn aoc(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    // let mut year = None;
-    // let mut day = None;
-    // let mut part = None;
-    // let mut label = None;
-    // //let mut fnname = None;
-    // #[derive(Debug)]
-    // enum State {
-    //     Idle,
-    //     EqAfterYear,
-    //     YearValue,
-    //     EqAfterDay,
-    //     DayValue,
-    //     EqAfterPart,
-    //     PartValue,
-    //     EqAfterLabel,
-    //     LabelValue,
-    // }
-    // let mut st = State::Idle;
-    // for t in attr {
-    //     st = match (st, t) {
-    //         (State::Idle, TokenTree::Ident(i)) if i.to_string() == "year" => State::EqAfterYear,
-    //         (State::Idle, TokenTree::Ident(i)) if i.to_string() == "day" => State::EqAfterDay,
-    //         (State::Idle, TokenTree::Ident(i)) if i.to_string() == "part" => State::EqAfterPart,
-    //         (State::Idle, TokenTree::Ident(i)) if i.to_string() == "label" => State::EqAfterLabel,
-    //         (State::Idle, TokenTree::Punct(p)) if p.as_char() == ',' => State::Idle,
-    //         (State::EqAfterYear, TokenTree::Punct(p)) if p.as_char() == '=' => State::YearValue,
-    //         (State::EqAfterDay, TokenTree::Punct(p)) if p.as_char() == '=' => State::DayValue,
-    //         (State::EqAfterPart, TokenTree::Punct(p)) if p.as_char() == '=' => State::PartValue,
-    //         (State::EqAfterLabel, TokenTree::Punct(p)) if p.as_char() == '=' => State::LabelValue,
-    //         (State::YearValue, TokenTree::Literal(l)) => {
-    //             year = Some(l);
-    //             State::Idle
-    //         }
-    //         (State::DayValue, TokenTree::Literal(l)) => {
-    //             day = Some(l);
-    //             State::Idle
-    //         }
-    //         (State::PartValue, TokenTree::Literal(l)) => {
-    //             part = Some(l);
-    //             State::Idle
-    //         }
-    //         (State::LabelValue, TokenTree::Literal(l)) => {
-    //             label = Some(l);
-    //             State::Idle
-    //         }
-    //         (st, t) => panic!("Bad syntax: st={:?} token={:?}", st, t),
-    //     };
-    // }
-    // // Make a 'register' call.
-    item
+pub fn aoc(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let registration = parse_attr(attr).and_then(|args| {
+        let name = fn_name(&item)?;
+        Ok(args.submit(&name))
+    });
+    let mut out = match registration {
+        Ok(ts) => ts,
+        Err(e) => e.into_compile_error(),
+    };
+    out.extend(item);
+    out
+}
+
+struct Args {
+    year: u32,
+    day: u32,
+    part: u32,
+    label: Option<String>,
+    slow: bool,
+    // A placeholder that doesn't solve anything yet.
+    unsolved: bool,
+}
+
+impl Args {
+    fn submit(&self, name: &Ident) -> TokenStream {
+        let label = match &self.label {
+            Some(l) => format!("Some({l:?})"),
+            None => "None".to_string(),
+        };
+        format!(
+            "::inventory::submit! {{ crate::registry::Registration {{ year: {}, day: {}, part: {}, label: {}, slow: {}, unsolved: {}, f: |input, vis| crate::answer::IntoAnswer::into_answer({}(input, vis)) }} }}",
+            self.year, self.day, self.part, label, self.slow, self.unsolved, name
+        )
+        .parse()
+        .unwrap()
+    }
+}
+
+struct Error {
+    span: Span,
+    msg: String,
+}
+
+impl Error {
+    fn new(span: Span, msg: impl Into<String>) -> Self {
+        Self {
+            span,
+            msg: msg.into(),
+        }
+    }
+
+    // compile_error! { "msg" }, with every token pointing at the offending span.
+    fn into_compile_error(self) -> TokenStream {
+        let mut bang = Punct::new('!', Spacing::Alone);
+        bang.set_span(self.span);
+        let mut msg = Literal::string(&self.msg);
+        msg.set_span(self.span);
+        let mut body = Group::new(Delimiter::Brace, TokenTree::Literal(msg).into());
+        body.set_span(self.span);
+        [
+            TokenTree::Ident(Ident::new("compile_error", self.span)),
+            TokenTree::Punct(bang),
+            TokenTree::Group(body),
+        ]
+        .into_iter()
+        .collect()
+    }
+}
+
+type Result<T> = std::result::Result<T, Error>;
+
+fn parse_attr(attr: TokenStream) -> Result<Args> {
+    let mut year = None;
+    let mut day = None;
+    let mut part = None;
+    let mut label = None;
+    let mut slow = false;
+    let mut unsolved = false;
+
+    let mut tokens = attr.into_iter().peekable();
+    while let Some(t) = tokens.next() {
+        let key = match t {
+            TokenTree::Ident(i) => i,
+            t => {
+                return Err(Error::new(
+                    t.span(),
+                    "expected `year`, `day`, `part`, `label`, `slow`, or `unsolved`",
+                ))
+            }
+        };
+        let name = key.to_string();
+        let value = match tokens.peek() {
+            Some(TokenTree::Punct(p)) if p.as_char() == '=' => {
+                let eq = tokens.next().unwrap();
+                match tokens.next() {
+                    Some(TokenTree::Literal(l)) => Some(l),
+                    Some(t) => {
+                        return Err(Error::new(
+                            t.span(),
+                            format!("expected a literal value for `{name}`"),
+                        ))
+                    }
+                    None => {
+                        return Err(Error::new(
+                            eq.span(),
+                            format!("expected a value for `{name}` after `=`"),
+                        ))
+                    }
+                }
+            }
+            _ => None,
+        };
+        match (name.as_str(), value) {
+            ("year", Some(v)) => set(&mut year, &key, int(&v, 2015..=9999)?)?,
+            ("day", Some(v)) => set(&mut day, &key, int(&v, 1..=25)?)?,
+            ("part", Some(v)) => set(&mut part, &key, int(&v, 1..=2)?)?,
+            ("label", Some(v)) => set(&mut label, &key, string(&v)?)?,
+            ("slow", None) => flag(&mut slow, &key)?,
+            ("unsolved", None) => flag(&mut unsolved, &key)?,
+            ("slow" | "unsolved", Some(v)) => {
+                return Err(Error::new(
+                    v.span(),
+                    format!("`{name}` does not take a value"),
+                ))
+            }
+            ("year" | "day" | "part" | "label", None) => {
+                return Err(Error::new(key.span(), format!("expected `{name} = ...`")))
+            }
+            _ => return Err(Error::new(key.span(), format!("unknown argument `{name}`"))),
+        }
+        match tokens.next() {
+            None => break,
+            Some(TokenTree::Punct(p)) if p.as_char() == ',' => (),
+            Some(t) => return Err(Error::new(t.span(), "expected `,`")),
+        }
+    }
+
+    let missing = |what| Error::new(Span::call_site(), format!("missing `{what} = ...`"));
+    Ok(Args {
+        year: year.ok_or_else(|| missing("year"))?,
+        day: day.ok_or_else(|| missing("day"))?,
+        part: part.ok_or_else(|| missing("part"))?,
+        label,
+        slow,
+        unsolved,
+    })
+}
+
+fn set<T>(slot: &mut Option<T>, key: &Ident, value: T) -> Result<()> {
+    if slot.is_some() {
+        return Err(Error::new(
+            key.span(),
+            format!("`{key}` given more than once"),
+        ));
+    }
+    *slot = Some(value);
+    Ok(())
+}
+
+fn flag(slot: &mut bool, key: &Ident) -> Result<()> {
+    if *slot {
+        return Err(Error::new(
+            key.span(),
+            format!("`{key}` given more than once"),
+        ));
+    }
+    *slot = true;
+    Ok(())
+}
+
+fn int(l: &Literal, range: std::ops::RangeInclusive<u32>) -> Result<u32> {
+    let n: u32 = l
+        .to_string()
+        .parse()
+        .map_err(|_| Error::new(l.span(), "expected an integer"))?;
+    if !range.contains(&n) {
+        return Err(Error::new(
+            l.span(),
+            format!("expected a value from {} to {}", range.start(), range.end()),
+        ));
+    }
+    Ok(n)
+}
+
+// Any kind of string literal, raw or not, unescaped.
+fn string(l: &Literal) -> Result<String> {
+    let s: syn::LitStr = syn::parse(TokenTree::Literal(l.clone()).into())
+        .map_err(|_| Error::new(l.span(), "expected a string"))?;
+    Ok(s.value())
+}
+
+fn fn_name(item: &TokenStream) -> Result<Ident> {
+    let mut tokens = item.clone().into_iter();
+    while let Some(t) = tokens.next() {
+        if let TokenTree::Ident(i) = &t {
+            if i.to_string() == "fn" {
+                if let Some(TokenTree::Ident(name)) = tokens.next() {
+                    return Ok(name);
+                }
+                break;
+            }
+        }
+    }
+    Err(Error::new(
+        Span::call_site(),
+        "#[aoc] can only be used on a fn",
+    ))
 }
@@ -0,0 +1,178 @@
+use std::fmt::Display;
+
+use num::BigInt;
+
+// What a solver found.
+#[derive(Debug, Clone, Eq)]
+pub enum Answer {
+    Int(i64),
+    BigInt(BigInt),
+    Str(String),
+    // Letters drawn with characters, like 2022 day 10's CRT.
+    Art(String),
+    // The solver isn't written yet.
+    Unsolved,
+}
+
+impl Answer {
+    // Figures out the best variant for an answer that's already been formatted.
+    pub fn parse(s: &str) -> Self {
+        if s == "todo" {
+            Answer::Unsolved
+        } else if let Ok(n) = s.parse() {
+            Answer::Int(n)
+        } else if let Ok(n) = s.parse() {
+            Answer::BigInt(n)
+        } else if s.contains('\n') {
+            Answer::Art(s.to_string())
+        } else {
+            Answer::Str(s.to_string())
+        }
+    }
+
+    pub fn is_unsolved(&self) -> bool {
+        matches!(self, Answer::Unsolved)
+    }
+}
+
+// Numbers are compared by value, so a BigInt that would fit in an i64 still
+// matches the Int.
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Answer::Int(a), Answer::Int(b)) => a == b,
+            (Answer::BigInt(a), Answer::BigInt(b)) => a == b,
+            (Answer::Int(a), Answer::BigInt(b)) | (Answer::BigInt(b), Answer::Int(a)) => {
+                BigInt::from(*a) == *b
+            }
+            (Answer::Str(a), Answer::Str(b)) | (Answer::Art(a), Answer::Art(b)) => a == b,
+            (Answer::Unsolved, Answer::Unsolved) => true,
+            _ => false,
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{n}"),
+            Answer::BigInt(n) => write!(f, "{n}"),
+            Answer::Str(s) | Answer::Art(s) => write!(f, "{s}"),
+            Answer::Unsolved => write!(f, "todo"),
+        }
+    }
+}
+
+macro_rules! from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    match i64::try_from(n) {
+                        Ok(n) => Answer::Int(n),
+                        Err(_) => Answer::BigInt(BigInt::from(n)),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+from_int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+impl From<BigInt> for Answer {
+    fn from(n: BigInt) -> Self {
+        match i64::try_from(&n) {
+            Ok(n) => Answer::Int(n),
+            Err(_) => Answer::BigInt(n),
+        }
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::parse(s)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::parse(&s)
+    }
+}
+
+// Anything a solver can return. Older solvers return `Box<dyn Display>`, which
+// is converted by formatting it and then guessing the kind of answer.
+pub trait IntoAnswer {
+    fn into_answer(self) -> anyhow::Result<Answer>;
+}
+
+impl IntoAnswer for anyhow::Result<Answer> {
+    fn into_answer(self) -> anyhow::Result<Answer> {
+        self
+    }
+}
+
+impl IntoAnswer for Answer {
+    fn into_answer(self) -> anyhow::Result<Answer> {
+        Ok(self)
+    }
+}
+
+impl IntoAnswer for Box<dyn Display> {
+    fn into_answer(self) -> anyhow::Result<Answer> {
+        Ok(Answer::parse(&self.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fmt::Display;
+
+    use num::BigInt;
+
+    use super::{Answer, IntoAnswer};
+
+    #[test]
+    fn parse() {
+        assert_eq!(Answer::Unsolved, Answer::parse("todo"));
+        assert_eq!(Answer::Int(-12), Answer::parse("-12"));
+        assert_eq!(
+            Answer::BigInt("123456789012345678901234567890".parse().unwrap()),
+            Answer::parse("123456789012345678901234567890")
+        );
+        assert_eq!(Answer::Str("CMZ".to_string()), Answer::parse("CMZ"));
+        assert_eq!(
+            Answer::Art("\n#.\n.#\n".to_string()),
+            Answer::parse("\n#.\n.#\n")
+        );
+    }
+
+    #[test]
+    fn from_ints() {
+        assert_eq!(Answer::Int(7), Answer::from(7u8));
+        assert_eq!(Answer::Int(7), Answer::from(7usize));
+        assert_eq!(
+            Answer::BigInt(BigInt::from(u64::MAX)),
+            Answer::from(u64::MAX)
+        );
+    }
+
+    #[test]
+    fn bigints_match_ints() {
+        assert!(matches!(Answer::from(BigInt::from(5)), Answer::Int(5)));
+        assert_eq!(Answer::Int(5), Answer::BigInt(BigInt::from(5)));
+        assert_eq!(Answer::BigInt(BigInt::from(-5)), Answer::from(-5));
+        assert_ne!(Answer::Int(5), Answer::BigInt(BigInt::from(6)));
+        assert_ne!(Answer::Int(5), Answer::Str("5".to_string()));
+        crate::test::dotest(5, "", |_, _| Answer::BigInt(BigInt::from(5)));
+    }
+
+    #[test]
+    fn adapt_display() {
+        let old: Box<dyn Display> = Box::new(42);
+        assert_eq!(Answer::Int(42), old.into_answer().unwrap());
+        let old: Box<dyn Display> = Box::new("todo");
+        assert!(old.into_answer().unwrap().is_unsolved());
+    }
+}
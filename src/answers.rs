@@ -0,0 +1,146 @@
+use std::{
+    fmt::Display,
+    fs,
+    io::{ErrorKind, Write},
+};
+
+use crate::{profile::Profile, submit::Outcome};
+
+pub enum Check {
+    Match,
+    Mismatch(String),
+    New,
+}
+
+impl Check {
+    pub fn new(expected: Option<String>, actual: &str) -> Self {
+        // Trailing whitespace is ignored so the files can be edited by hand.
+        match expected {
+            None => Check::New,
+            Some(e) if e.trim_end() == actual.trim_end() => Check::Match,
+            Some(e) => Check::Mismatch(e.trim_end().to_string()),
+        }
+    }
+
+    pub fn is_mismatch(&self) -> bool {
+        matches!(self, Check::Mismatch(_))
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Check::Match => "match",
+            Check::Mismatch(_) => "mismatch",
+            Check::New => "new",
+        }
+    }
+
+    pub fn hide_expected(self) -> Self {
+        match self {
+            Check::Mismatch(_) => Check::Mismatch("(hidden)".to_string()),
+            c => c,
+        }
+    }
+}
+
+impl Display for Check {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Check::Match => write!(f, "✓"),
+            Check::Mismatch(expected) => write!(f, "✗ (expected {expected})"),
+            Check::New => write!(f, "new"),
+        }
+    }
+}
+
+pub fn get_answer(
+    profile: &Profile,
+    year: i32,
+    day: u32,
+    part: u8,
+) -> anyhow::Result<Option<String>> {
+    match fs::read_to_string(file_for(profile, year, day, part)) {
+        Ok(s) => Ok(Some(s)),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+pub fn save_answer(
+    profile: &Profile,
+    year: i32,
+    day: u32,
+    part: u8,
+    answer: &str,
+) -> anyhow::Result<()> {
+    fs::create_dir_all(day_dir(profile, year, day))?;
+    fs::write(file_for(profile, year, day, part), answer)?;
+    Ok(())
+}
+
+// Past submissions, as (answer, outcome) pairs, oldest first. Lines that
+// can't be understood are skipped.
+pub fn get_submissions(
+    profile: &Profile,
+    year: i32,
+    day: u32,
+    part: u8,
+) -> anyhow::Result<Vec<(String, Outcome)>> {
+    let s = match fs::read_to_string(history_file_for(profile, year, day, part)) {
+        Ok(s) => s,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    Ok(s.lines()
+        .filter_map(|line| line.split_once('\t'))
+        .filter_map(|(outcome, answer)| Some((answer.to_string(), Outcome::from_key(outcome)?)))
+        .collect())
+}
+
+pub fn record_submission(
+    profile: &Profile,
+    year: i32,
+    day: u32,
+    part: u8,
+    answer: &str,
+    outcome: &Outcome,
+) -> anyhow::Result<()> {
+    fs::create_dir_all(day_dir(profile, year, day))?;
+    let mut f = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(history_file_for(profile, year, day, part))?;
+    writeln!(f, "{}\t{answer}", outcome.key())?;
+    Ok(())
+}
+
+fn day_dir(profile: &Profile, year: i32, day: u32) -> String {
+    format!("{}/{}/{}", profile.dir("answers"), year, day)
+}
+
+fn file_for(profile: &Profile, year: i32, day: u32, part: u8) -> String {
+    format!("{}/{}", day_dir(profile, year, day), part)
+}
+
+fn history_file_for(profile: &Profile, year: i32, day: u32, part: u8) -> String {
+    format!("{}.history", file_for(profile, year, day, part))
+}
+
+#[cfg(test)]
+mod test {
+    use super::Check;
+
+    #[test]
+    fn check_against_stored_answer() {
+        assert!(matches!(Check::new(None, "42"), Check::New));
+        assert!(matches!(Check::new(Some("42".into()), "42"), Check::Match));
+        assert!(matches!(
+            Check::new(Some("42\n".into()), "42"),
+            Check::Match
+        ));
+        assert!(Check::new(Some("41".into()), "42").is_mismatch());
+        assert_eq!(
+            "✗ (expected 41)",
+            Check::new(Some("41".into()), "42").to_string()
+        );
+    }
+}
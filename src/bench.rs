@@ -0,0 +1,165 @@
+use std::{collections::BTreeMap, fmt::Display, fs, io::ErrorKind, path::Path, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, Copy)]
+pub struct Stats {
+    pub runs: usize,
+    #[serde(with = "nanos")]
+    pub min: Duration,
+    #[serde(with = "nanos")]
+    pub median: Duration,
+    #[serde(with = "nanos")]
+    pub mean: Duration,
+    #[serde(with = "nanos")]
+    pub stddev: Duration,
+}
+
+impl Stats {
+    pub fn new(mut samples: Vec<Duration>) -> Self {
+        assert!(!samples.is_empty(), "need at least one sample");
+        samples.sort();
+        let runs = samples.len();
+        let mid = runs / 2;
+        let median = if runs.is_multiple_of(2) {
+            (samples[mid - 1] + samples[mid]) / 2
+        } else {
+            samples[mid]
+        };
+        let mean = samples.iter().sum::<Duration>() / runs as u32;
+        let variance = samples
+            .iter()
+            .map(|d| (d.as_secs_f64() - mean.as_secs_f64()).powi(2))
+            .sum::<f64>()
+            / runs as f64;
+        Self {
+            runs,
+            min: samples[0],
+            median,
+            mean,
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+
+    // Percent change of the median, relative to `baseline`.
+    pub fn change_from(&self, baseline: &Stats) -> f64 {
+        let base = baseline.median.as_secs_f64();
+        (self.median.as_secs_f64() - base) / base * 100.0
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} runs: min {:.2?} / median {:.2?} / mean {:.2?} / stddev {:.2?}",
+            self.runs, self.min, self.median, self.mean, self.stddev
+        )
+    }
+}
+
+#[derive(Default, Serialize, Deserialize)]
+pub struct Baseline {
+    solvers: BTreeMap<String, Stats>,
+}
+
+impl Baseline {
+    pub fn load(path: &Path) -> anyhow::Result<Option<Self>> {
+        match fs::read_to_string(path) {
+            Ok(s) => Ok(Some(serde_json::from_str(&s).map_err(|e| {
+                anyhow::anyhow!("error reading baseline from {path:?}: {e}")
+            })?)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn get(&self, year: i32, day: u32, part: u8, label: Option<&str>) -> Option<&Stats> {
+        self.solvers.get(&key(year, day, part, label))
+    }
+
+    pub fn insert(&mut self, year: i32, day: u32, part: u8, label: Option<&str>, stats: Stats) {
+        self.solvers.insert(key(year, day, part, label), stats);
+    }
+
+    // Replaces the solvers that are in `newer`, and keeps the rest.
+    pub fn merge(&mut self, newer: Baseline) {
+        self.solvers.extend(newer.solvers);
+    }
+}
+
+fn key(year: i32, day: u32, part: u8, label: Option<&str>) -> String {
+    format!("{}/{:02}/{}/{}", year, day, part, label.unwrap_or(""))
+}
+
+mod nanos {
+    use std::time::Duration;
+
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(d: &Duration, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_u64(d.as_nanos() as u64)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Duration, D::Error> {
+        Ok(Duration::from_nanos(u64::deserialize(d)?))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::{Baseline, Stats};
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    #[test]
+    fn stats() {
+        let stats = Stats::new(vec![ms(4), ms(2), ms(8), ms(6)]);
+        assert_eq!(4, stats.runs);
+        assert_eq!(ms(2), stats.min);
+        assert_eq!(ms(5), stats.median);
+        assert_eq!(ms(5), stats.mean);
+        assert_eq!(2236, stats.stddev.as_micros());
+    }
+
+    #[test]
+    fn baseline_round_trip() {
+        let mut baseline = Baseline::default();
+        let stats = Stats::new(vec![ms(10), ms(12), ms(11)]);
+        baseline.insert(2022, 3, 2, Some("with bytes"), stats);
+        let json = serde_json::to_string(&baseline).unwrap();
+        let baseline: Baseline = serde_json::from_str(&json).unwrap();
+        assert_eq!(Some(&stats), baseline.get(2022, 3, 2, Some("with bytes")));
+        assert_eq!(None, baseline.get(2022, 3, 2, None));
+
+        let faster = Stats::new(vec![ms(9), ms(9), ms(9)]);
+        assert!((faster.change_from(&stats) + 18.18).abs() < 0.01);
+    }
+
+    #[test]
+    fn merge() {
+        let (old, new) = (Stats::new(vec![ms(10)]), Stats::new(vec![ms(5)]));
+        let mut baseline = Baseline::default();
+        baseline.insert(2022, 3, 1, None, old);
+        baseline.insert(2022, 3, 2, None, old);
+        let mut newer = Baseline::default();
+        newer.insert(2022, 3, 2, None, new);
+        newer.insert(2023, 5, 2, None, new);
+        baseline.merge(newer);
+        assert_eq!(Some(&old), baseline.get(2022, 3, 1, None));
+        assert_eq!(Some(&new), baseline.get(2022, 3, 2, None));
+        assert_eq!(Some(&new), baseline.get(2023, 5, 2, None));
+    }
+}
@@ -0,0 +1,132 @@
+use std::collections::BTreeMap;
+
+// What there is for one part of a puzzle.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Part {
+    // There's a known-good answer, or a correct submission.
+    pub solved: bool,
+    pub solver: Progress,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum Progress {
+    #[default]
+    Missing,
+    // Every solver is still a placeholder.
+    Todo,
+    Done,
+}
+
+// Draws one year as five weeks of five days, with ★ for each solved part and
+// ☆ for the rest. Below that are the parts that still need a solver. Parts
+// that aren't in `parts` don't have anything.
+pub fn render(year: i32, parts: &BTreeMap<(u32, u8), Part>) -> String {
+    let get = |day, part| parts.get(&(day, part)).copied().unwrap_or_default();
+    // The last star comes free with the other 49.
+    let all_but_last = (1..=25)
+        .flat_map(|d| [(d, 1), (d, 2)])
+        .filter(|&dp| dp != (25, 2))
+        .all(|(d, p)| get(d, p).solved);
+    let solved = |day, part| get(day, part).solved || (day, part) == (25, 2) && all_but_last;
+
+    let stars = (1..=25)
+        .flat_map(|d| [(d, 1), (d, 2)])
+        .filter(|&(d, p)| solved(d, p))
+        .count();
+    let mut out = format!("{year}: {stars}★\n");
+    for week in 0..5 {
+        let days: Vec<String> = (1..=5)
+            .map(|d| week * 5 + d)
+            .map(|d| {
+                let star = |p| if solved(d, p) { '★' } else { '☆' };
+                format!("{d:>3} {}{}", star(1), star(2))
+            })
+            .collect();
+        out.push_str(&days.join("  "));
+        out.push('\n');
+    }
+
+    let mut todo = Vec::new();
+    let mut missing = Vec::new();
+    for day in 1..=25 {
+        let progress = [get(day, 1).solver, get(day, 2).solver];
+        if day == 25 {
+            match progress[0] {
+                Progress::Missing => missing.push("25".to_string()),
+                Progress::Todo => todo.push("25".to_string()),
+                Progress::Done => (),
+            }
+            continue;
+        }
+        match progress {
+            [Progress::Missing, Progress::Missing] => missing.push(day.to_string()),
+            [Progress::Todo, Progress::Todo] => todo.push(day.to_string()),
+            _ => {
+                for (i, p) in progress.iter().enumerate() {
+                    match p {
+                        Progress::Missing => missing.push(format!("{day}/{}", i + 1)),
+                        Progress::Todo => todo.push(format!("{day}/{}", i + 1)),
+                        Progress::Done => (),
+                    }
+                }
+            }
+        }
+    }
+    if !todo.is_empty() {
+        out.push_str(&format!("  todo: {}\n", todo.join(", ")));
+    }
+    if !missing.is_empty() {
+        out.push_str(&format!("  no solver: {}\n", missing.join(", ")));
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeMap;
+
+    use super::{render, Part, Progress};
+
+    fn part(solved: bool, solver: Progress) -> Part {
+        Part { solved, solver }
+    }
+
+    #[test]
+    fn partial_year() {
+        let mut parts = BTreeMap::new();
+        for day in 1..=21 {
+            parts.insert((day, 1), part(true, Progress::Done));
+            parts.insert((day, 2), part(true, Progress::Done));
+        }
+        parts.insert((5, 2), part(true, Progress::Todo));
+        parts.insert((23, 1), part(true, Progress::Done));
+        parts.insert((23, 2), part(false, Progress::Todo));
+        parts.insert((24, 1), part(false, Progress::Todo));
+        parts.insert((24, 2), part(false, Progress::Todo));
+        assert_eq!(
+            "2023: 43★\n  \
+               1 ★★    2 ★★    3 ★★    4 ★★    5 ★★\n  \
+               6 ★★    7 ★★    8 ★★    9 ★★   10 ★★\n \
+              11 ★★   12 ★★   13 ★★   14 ★★   15 ★★\n \
+              16 ★★   17 ★★   18 ★★   19 ★★   20 ★★\n \
+              21 ★★   22 ☆☆   23 ★☆   24 ☆☆   25 ☆☆\n  \
+              todo: 5/2, 23/2, 24\n  \
+              no solver: 22, 25\n",
+            render(2023, &parts)
+        );
+    }
+
+    #[test]
+    fn free_last_star() {
+        let mut parts = BTreeMap::new();
+        for day in 1..=25 {
+            parts.insert((day, 1), part(true, Progress::Done));
+            parts.insert((day, 2), part(true, Progress::Done));
+        }
+        parts.remove(&(25, 2));
+        let out = render(2022, &parts);
+        assert!(out.starts_with("2022: 50★\n"), "{out}");
+        assert!(out.contains("25 ★★\n"), "{out}");
+        assert!(!out.contains("no solver"), "{out}");
+    }
+}
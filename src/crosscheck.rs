@@ -0,0 +1,181 @@
+use std::{collections::BTreeMap, time::Duration};
+
+// Collects the results of every implementation of a part, to see if they
+// agree and which one is fastest.
+#[derive(Default)]
+pub struct CrossCheck {
+    groups: BTreeMap<(i32, u32, u8, Option<String>), Vec<Entry>>,
+}
+
+struct Entry {
+    label: Option<&'static str>,
+    // The answer, or why there isn't one.
+    result: Result<String, String>,
+    elapsed: Duration,
+}
+
+pub struct Report {
+    pub lines: Vec<String>,
+    pub disagreements: usize,
+}
+
+impl CrossCheck {
+    // `profile` is only set when there's more than one, so each profile's
+    // answers are compared separately.
+    pub fn add(
+        &mut self,
+        (year, day, part): (i32, u32, u8),
+        profile: Option<String>,
+        label: Option<&'static str>,
+        result: Result<String, String>,
+        elapsed: Duration,
+    ) {
+        self.groups
+            .entry((year, day, part, profile))
+            .or_default()
+            .push(Entry {
+                label,
+                result,
+                elapsed,
+            });
+    }
+
+    // Only parts with more than one implementation are reported. The
+    // implementations are listed fastest first, and failures go last.
+    pub fn report(&self) -> Report {
+        let mut lines = Vec::new();
+        let mut disagreements = 0;
+        for ((year, day, part, profile), entries) in &self.groups {
+            if entries.len() < 2 {
+                continue;
+            }
+            let mut entries: Vec<&Entry> = entries.iter().collect();
+            entries.sort_by_key(|e| (e.result.is_err(), e.elapsed));
+            let first = &entries[0].result;
+            let agree = entries
+                .iter()
+                .all(|e| e.result.is_ok() && &e.result == first);
+            let who = match profile {
+                Some(p) => format!(" [{p}]"),
+                None => "".to_string(),
+            };
+            let ranked: Vec<String> = entries
+                .iter()
+                .map(|e| {
+                    let label = e.label.unwrap_or("(default)");
+                    match (&e.result, agree) {
+                        (Ok(_), true) => format!("{label} {:.2?}", e.elapsed),
+                        (Ok(answer), false) => format!("{label} {:.2?} = {answer}", e.elapsed),
+                        (Err(failure), _) => format!("{label} {failure}"),
+                    }
+                })
+                .collect();
+            let verdict = match agree {
+                true => format!("{} agree", entries.len()),
+                false => {
+                    disagreements += 1;
+                    "DISAGREE".to_string()
+                }
+            };
+            lines.push(format!(
+                "{year}: Dec {day:02}: part {part}{who}: {verdict}: {}",
+                ranked.join(", ")
+            ));
+        }
+        Report {
+            lines,
+            disagreements,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::CrossCheck;
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    #[test]
+    fn ranks_agreeing_implementations() {
+        let mut cc = CrossCheck::default();
+        cc.add((2023, 2, 1), None, None, Ok("8".to_string()), ms(3));
+        cc.add(
+            (2023, 2, 1),
+            None,
+            Some("regexp"),
+            Ok("8".to_string()),
+            ms(5),
+        );
+        cc.add(
+            (2023, 2, 1),
+            None,
+            Some("itertools"),
+            Ok("8".to_string()),
+            ms(1),
+        );
+        // Only one implementation, so there's nothing to compare.
+        cc.add((2023, 2, 2), None, None, Ok("2286".to_string()), ms(1));
+        let report = cc.report();
+        assert_eq!(0, report.disagreements);
+        assert_eq!(
+            vec![
+                "2023: Dec 02: part 1: 3 agree: itertools 1.00ms, (default) 3.00ms, regexp 5.00ms"
+            ],
+            report.lines
+        );
+    }
+
+    #[test]
+    fn reports_disagreements() {
+        let mut cc = CrossCheck::default();
+        cc.add((2022, 6, 1), None, None, Ok("1896".to_string()), ms(2));
+        cc.add(
+            (2022, 6, 1),
+            None,
+            Some("alt"),
+            Ok("1897".to_string()),
+            ms(1),
+        );
+        cc.add((2022, 13, 2), None, None, Ok("1".to_string()), ms(1));
+        cc.add(
+            (2022, 13, 2),
+            None,
+            Some("no sort"),
+            Err("PANIC: oops".to_string()),
+            ms(0),
+        );
+        let report = cc.report();
+        assert_eq!(2, report.disagreements);
+        assert_eq!(
+            vec![
+                "2022: Dec 06: part 1: DISAGREE: alt 1.00ms = 1897, (default) 2.00ms = 1896",
+                "2022: Dec 13: part 2: DISAGREE: (default) 1.00ms = 1, no sort PANIC: oops",
+            ],
+            report.lines
+        );
+    }
+
+    #[test]
+    fn profiles_are_separate() {
+        let mut cc = CrossCheck::default();
+        cc.add(
+            (2023, 1, 1),
+            Some("a".to_string()),
+            None,
+            Ok("1".to_string()),
+            ms(1),
+        );
+        cc.add(
+            (2023, 1, 1),
+            Some("b".to_string()),
+            None,
+            Ok("2".to_string()),
+            ms(1),
+        );
+        assert!(cc.report().lines.is_empty());
+    }
+}
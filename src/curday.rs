@@ -1,7 +1,56 @@
-use chrono::{DateTime, FixedOffset, Utc};
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, TimeZone, Utc};
 
 pub fn aoc_now() -> DateTime<FixedOffset> {
+    Utc::now().with_timezone(&aoc_tz())
+}
+
+// Puzzles unlock at midnight, UTC-5.
+pub fn unlock_time(year: i32, day: u32) -> anyhow::Result<DateTime<FixedOffset>> {
+    let date = NaiveDate::from_ymd_opt(year, 12, day)
+        .filter(|_| (1..=25).contains(&day))
+        .ok_or_else(|| anyhow::anyhow!("there's no puzzle for {year}/{day}"))?;
+    Ok(aoc_tz()
+        .from_local_datetime(&date.and_hms_opt(0, 0, 0).unwrap())
+        .unwrap())
+}
+
+// The puzzle that unlocks next. Today's still counts for an hour after it unlocks.
+pub fn next_puzzle(now: &DateTime<FixedOffset>) -> (i32, u32) {
+    let next = (*now - chrono::Duration::hours(1) + chrono::Duration::days(1)).date_naive();
+    match (next.month(), next.day()) {
+        (12, d) if d <= 25 => (next.year(), d),
+        (12, _) => (next.year() + 1, 1),
+        _ => (next.year(), 1),
+    }
+}
+
+fn aoc_tz() -> FixedOffset {
     const HOUR: i32 = 3600;
-    let aoc_tz = FixedOffset::west_opt(5 * HOUR).unwrap();
-    Utc::now().with_timezone(&aoc_tz)
+    FixedOffset::west_opt(5 * HOUR).unwrap()
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::{DateTime, FixedOffset};
+
+    use super::{next_puzzle, unlock_time};
+
+    #[test]
+    fn unlocks_at_midnight_est() {
+        let unlock = unlock_time(2023, 5).unwrap();
+        assert_eq!("2023-12-05 00:00:00 -05:00", unlock.to_string());
+        assert_eq!("2023-12-05 05:00:00", unlock.naive_utc().to_string());
+        assert!(unlock_time(2023, 26).is_err());
+    }
+
+    #[test]
+    fn next() {
+        let at = |s: &str| DateTime::<FixedOffset>::parse_from_rfc3339(s).unwrap();
+        assert_eq!((2023, 1), next_puzzle(&at("2023-11-30T23:55:00-05:00")));
+        assert_eq!((2023, 5), next_puzzle(&at("2023-12-04T12:00:00-05:00")));
+        assert_eq!((2023, 5), next_puzzle(&at("2023-12-05T00:30:00-05:00")));
+        assert_eq!((2023, 6), next_puzzle(&at("2023-12-05T01:30:00-05:00")));
+        assert_eq!((2024, 1), next_puzzle(&at("2023-12-25T12:00:00-05:00")));
+        assert_eq!((2026, 1), next_puzzle(&at("2026-10-18T09:00:00-05:00")));
+    }
 }
@@ -0,0 +1,231 @@
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    hash::Hash,
+};
+
+// How to find where a simulation starts repeating.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    // Remembers every fingerprint, so it only goes round the cycle once, but
+    // needs room for all of them.
+    #[allow(dead_code)]
+    Seen,
+    // Brent's algorithm: keeps two states instead, but takes a few times as
+    // many steps.
+    Brent,
+}
+
+// After `start` steps, the states go round and round, coming back to the same
+// one every `period` steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: u64,
+    pub period: u64,
+}
+
+// Steps from `init` until a state comes back. States with the same
+// fingerprint count as the same, so the fingerprint has to be enough to tell
+// what comes next. Never returns if the states don't repeat.
+pub fn find<S, F>(
+    strategy: Strategy,
+    init: &S,
+    mut step: impl FnMut(&mut S),
+    mut fingerprint: impl FnMut(&S) -> F,
+) -> Cycle
+where
+    S: Clone,
+    F: Hash + Eq,
+{
+    match strategy {
+        Strategy::Seen => {
+            let mut seen = HashMap::new();
+            let mut state = init.clone();
+            for i in 0.. {
+                match seen.entry(fingerprint(&state)) {
+                    Entry::Occupied(e) => {
+                        return Cycle {
+                            start: *e.get(),
+                            period: i - e.get(),
+                        }
+                    }
+                    Entry::Vacant(e) => e.insert(i),
+                };
+                step(&mut state);
+            }
+            unreachable!()
+        }
+        Strategy::Brent => {
+            // The hare goes ahead, and the tortoise jumps to where it is at
+            // each power of two, until the hare comes back round to it.
+            let mut tortoise = fingerprint(init);
+            let mut hare = init.clone();
+            step(&mut hare);
+            let (mut power, mut period) = (1, 1);
+            while fingerprint(&hare) != tortoise {
+                if power == period {
+                    tortoise = fingerprint(&hare);
+                    power *= 2;
+                    period = 0;
+                }
+                step(&mut hare);
+                period += 1;
+            }
+            // Then, a period apart, they meet where the cycle starts.
+            let mut tortoise = init.clone();
+            let mut hare = init.clone();
+            for _ in 0..period {
+                step(&mut hare);
+            }
+            let mut start = 0;
+            while fingerprint(&tortoise) != fingerprint(&hare) {
+                step(&mut tortoise);
+                step(&mut hare);
+                start += 1;
+            }
+            Cycle { start, period }
+        }
+    }
+}
+
+impl Cycle {
+    // The first step with the same state as step `n`.
+    pub fn equivalent(&self, n: u64) -> u64 {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.period
+        }
+    }
+
+    // The state after `n` steps, without going round more than once.
+    pub fn state_after<S: Clone>(&self, n: u64, init: &S, mut step: impl FnMut(&mut S)) -> S {
+        let mut state = init.clone();
+        for _ in 0..self.equivalent(n) {
+            step(&mut state);
+        }
+        state
+    }
+
+    // Something about the state after `n` steps that goes up by the same
+    // amount every time round, like a height, or doesn't change at all. None
+    // if it's too big for a u64.
+    pub fn value_after<S: Clone>(
+        &self,
+        n: u64,
+        init: &S,
+        mut step: impl FnMut(&mut S),
+        mut value: impl FnMut(&S) -> u64,
+    ) -> Option<u64> {
+        let mut state = init.clone();
+        if n < self.start + self.period {
+            for _ in 0..n {
+                step(&mut state);
+            }
+            return Some(value(&state));
+        }
+        let (rounds, offset) = (
+            (n - self.start) / self.period,
+            (n - self.start) % self.period,
+        );
+        let (mut first, mut within) = (0, 0);
+        for i in 0..=self.start + self.period {
+            if i == self.start {
+                first = value(&state);
+            }
+            if i == self.start + offset {
+                within = value(&state);
+            }
+            if i == self.start + self.period {
+                let gain = value(&state).checked_sub(first)?;
+                return within.checked_add(rounds.checked_mul(gain)?);
+            }
+            step(&mut state);
+        }
+        unreachable!()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{find, Cycle, Strategy};
+
+    // 100, 70, 40, then 10, 17, 1, 8 and so on, round all the numbers under 23.
+    fn step(n: &mut u64) {
+        *n = if *n >= 23 { *n - 30 } else { (*n + 7) % 23 };
+    }
+
+    #[test]
+    fn strategies() {
+        for strategy in [Strategy::Seen, Strategy::Brent] {
+            assert_eq!(
+                Cycle {
+                    start: 3,
+                    period: 23
+                },
+                find(strategy, &100, step, |&n| n)
+            );
+            assert_eq!(
+                Cycle {
+                    start: 0,
+                    period: 1
+                },
+                find(strategy, &5, |_| {}, |&n| n)
+            );
+            // Only the fingerprint counts.
+            let count = |(n, steps): &mut (u64, u64)| {
+                step(n);
+                *steps += 1;
+            };
+            assert_eq!(
+                Cycle {
+                    start: 3,
+                    period: 23
+                },
+                find(strategy, &(100, 0), count, |&(n, _)| n)
+            );
+        }
+    }
+
+    #[test]
+    fn after() {
+        let cycle = find(Strategy::Brent, &100, step, |&n| n);
+        let mut n = 100;
+        for i in 0..100 {
+            assert_eq!(n, cycle.state_after(i, &100, step));
+            assert_eq!(Some(n), cycle.value_after(i, &100, step, |&n| n));
+            step(&mut n);
+        }
+        assert_eq!(
+            10,
+            cycle.state_after(3 + 23 * 1_000_000_000_000, &100, step)
+        );
+        // (u64::MAX - 3) % 23 is 2.
+        assert_eq!(5, cycle.equivalent(u64::MAX));
+        assert_eq!(1, cycle.state_after(u64::MAX, &100, step));
+    }
+
+    #[test]
+    fn growing() {
+        // Counts the times round 0, 1, 2, 3.
+        let step = |(n, rounds): &mut (u64, u64)| {
+            *n = (*n + 1) % 4;
+            if *n == 0 {
+                *rounds += 1;
+            }
+        };
+        let cycle = find(Strategy::Seen, &(0, 0), step, |&(n, _)| n);
+        assert_eq!(
+            Cycle {
+                start: 0,
+                period: 4
+            },
+            cycle
+        );
+        let rounds = |n| cycle.value_after(n, &(0, 0), step, |&(_, rounds)| rounds);
+        assert_eq!(Some(25), rounds(102));
+        assert_eq!(Some(26), rounds(104));
+        assert_eq!(Some(u64::MAX / 4), rounds(u64::MAX));
+        let init = (0, u64::MAX - 10);
+        assert_eq!(None, cycle.value_after(u64::MAX, &init, step, |&(_, r)| r));
+    }
+}
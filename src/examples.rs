@@ -0,0 +1,195 @@
+use std::{fs, io::ErrorKind};
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::puzzle::{articles, get_page, strip_tags, unescape};
+
+// An example input from a puzzle description, and the answers the
+// description gives for it.
+#[derive(Debug, PartialEq)]
+pub struct Example {
+    pub input: String,
+    pub meta: Meta,
+}
+
+// Saved next to each example, in `{n}.json`.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Meta {
+    pub source: String,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+impl Meta {
+    pub fn expected(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part1.as_deref(),
+            2 => self.part2.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+// Gets the puzzle page and saves its examples, unless they've already been
+// saved along with part 2's answer. Until then, part 2 might have unlocked
+// since, with more examples. Returns the number of examples.
+pub fn fetch_examples(base_url: &str, token: &str, year: i32, day: u32) -> anyhow::Result<usize> {
+    let cached = load_examples(year, day)?;
+    if cached.iter().any(|e| e.meta.part2.is_some()) {
+        return Ok(cached.len());
+    }
+    let url = format!("{}/{}/day/{}", base_url, year, day);
+    let html = get_page(base_url, || Ok(token.to_string()), year, day, false)?;
+    let examples = parse_examples(&html, &url);
+    save_examples(year, day, &examples)?;
+    Ok(examples.len())
+}
+
+// Reads the saved examples, in order. There aren't any if they haven't been fetched.
+pub fn load_examples(year: i32, day: u32) -> anyhow::Result<Vec<Example>> {
+    let mut examples = Vec::new();
+    for n in 1.. {
+        let file = crate::input::example_file_for(year, day, n);
+        let input = match fs::read_to_string(&file) {
+            Ok(s) => s,
+            Err(e) if e.kind() == ErrorKind::NotFound => break,
+            Err(e) => return Err(e.into()),
+        };
+        let meta = match fs::read_to_string(format!("{file}.json")) {
+            Ok(s) => serde_json::from_str(&s)
+                .map_err(|e| anyhow::anyhow!("error reading {file}.json: {e}"))?,
+            Err(e) if e.kind() == ErrorKind::NotFound => Meta::default(),
+            Err(e) => return Err(e.into()),
+        };
+        examples.push(Example { input, meta });
+    }
+    Ok(examples)
+}
+
+fn save_examples(year: i32, day: u32, examples: &[Example]) -> anyhow::Result<()> {
+    for (i, example) in examples.iter().enumerate() {
+        let file = crate::input::example_file_for(year, day, i as u32 + 1);
+        if let Some(dir) = std::path::Path::new(&file).parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&file, &example.input)?;
+        fs::write(
+            format!("{file}.json"),
+            serde_json::to_string_pretty(&example.meta)?,
+        )?;
+    }
+    Ok(())
+}
+
+// Finds every <pre><code> block on a puzzle page. The expected answer for
+// each part is the last highlighted <code><em> in that part's description.
+// It's a guess which example goes with the answer: the part's first block,
+// or, if the part doesn't have its own example, the one from part 1.
+pub fn parse_examples(html: &str, source: &str) -> Vec<Example> {
+    let block = Regex::new(r"(?s)<pre><code>(.*?)</code></pre>").unwrap();
+    let answer =
+        Regex::new(r"(?s)<code><em>(.*?)</em></code>|<em><code>(.*?)</code></em>").unwrap();
+
+    let mut examples: Vec<Example> = Vec::new();
+    let mut last_example = None;
+    for (i, part) in articles(html).into_iter().take(2).enumerate() {
+        let mut first_block = None;
+        for b in block.captures_iter(part) {
+            let input = unescape(&strip_tags(&b[1]));
+            let n = match examples.iter().position(|e| e.input == input) {
+                Some(n) => n,
+                None => {
+                    examples.push(Example {
+                        input,
+                        meta: Meta {
+                            source: source.to_string(),
+                            ..Meta::default()
+                        },
+                    });
+                    examples.len() - 1
+                }
+            };
+            first_block.get_or_insert(n);
+        }
+        // Highlighted code inside the example blocks isn't an answer.
+        let prose = block.replace_all(part, "");
+        let expected = answer
+            .captures_iter(&prose)
+            .last()
+            .and_then(|c| c.get(1).or(c.get(2)))
+            .map(|m| unescape(&strip_tags(m.as_str())));
+        if let Some(n) = first_block.or(last_example) {
+            let meta = &mut examples[n].meta;
+            match i {
+                0 => meta.part1 = expected,
+                _ => meta.part2 = expected,
+            }
+            last_example = Some(n);
+        }
+    }
+    examples
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_examples, Example, Meta};
+
+    fn example(input: &str, part1: Option<&str>, part2: Option<&str>) -> Example {
+        Example {
+            input: input.to_string(),
+            meta: Meta {
+                source: "test".to_string(),
+                part1: part1.map(str::to_string),
+                part2: part2.map(str::to_string),
+            },
+        }
+    }
+
+    #[test]
+    fn parse_shared_example() {
+        let examples = parse_examples(include_str!("../fixtures/2022-day-1.html"), "test");
+        assert_eq!(
+            vec![example(
+                "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n",
+                Some("24000"),
+                Some("45000")
+            )],
+            examples
+        );
+    }
+
+    #[test]
+    fn parse_separate_examples() {
+        let examples = parse_examples(include_str!("../fixtures/2023-day-1.html"), "test");
+        assert_eq!(2, examples.len());
+        assert!(examples[0].input.starts_with("1abc2\n"));
+        assert_eq!(Some("142"), examples[0].meta.expected(1));
+        assert_eq!(None, examples[0].meta.expected(2));
+        assert!(examples[1].input.starts_with("two1nine\n"));
+        assert_eq!(None, examples[1].meta.expected(1));
+        assert_eq!(Some("281"), examples[1].meta.expected(2));
+    }
+
+    #[test]
+    fn parse_unsolved() {
+        let examples = parse_examples(include_str!("../fixtures/2022-day-14-part1.html"), "test");
+        // The drawings count as examples too, but they don't have answers.
+        assert_eq!(3, examples.len());
+        assert_eq!(
+            example(
+                "498,4 -> 498,6 -> 496,6\n503,4 -> 502,4 -> 502,9 -> 494,9\n",
+                Some("24"),
+                None
+            ),
+            examples[0]
+        );
+        assert_eq!(None, examples[1].meta.expected(1));
+        assert!(examples[2].input.contains("~#########."));
+    }
+
+    #[test]
+    fn parse_no_articles() {
+        assert!(parse_examples("<html><main>Please log in.</main></html>", "test").is_empty());
+    }
+}
@@ -0,0 +1,212 @@
+use std::{
+    any::Any,
+    fmt::Display,
+    panic::{catch_unwind, AssertUnwindSafe},
+    sync::mpsc,
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    answer::Answer,
+    bench::Stats,
+    mem::{self, Usage},
+    solver::Solver,
+    vis::Visualizer,
+};
+
+pub struct Run {
+    pub answer: Answer,
+    pub elapsed: Duration,
+    pub stats: Option<Stats>,
+    pub mem: Option<Usage>,
+    // Why the visualization couldn't be saved. The answer is still good.
+    pub vis_error: Option<anyhow::Error>,
+}
+
+pub enum Failure {
+    Error(anyhow::Error),
+    Panic(String),
+    Timeout(Duration),
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Failure::Error(e) => write!(f, "ERROR: {e:#}"),
+            Failure::Panic(msg) => write!(f, "PANIC: {msg}"),
+            Failure::Timeout(limit) => write!(f, "TIMEOUT after {limit:.2?}"),
+        }
+    }
+}
+
+// Runs the solver once, and then `bench` more times if it's set. Only the
+// first run is shown to `vis`, and with `mem`, only its allocations are
+// counted. An error or a panic is returned as a failure. If the solver
+// doesn't finish within `timeout`, it's left running on its own thread and
+// reported as a failure.
+pub fn execute(
+    solver: &Solver,
+    input: &str,
+    vis: Visualizer,
+    bench: Option<u32>,
+    mem: bool,
+    timeout: Option<Duration>,
+) -> Result<Run, Failure> {
+    let f = solver.f.clone();
+    let input = input.to_string();
+    let go = move || {
+        catch_unwind(AssertUnwindSafe(|| measure(&*f, &input, &vis, bench, mem)))
+            .map_err(|e| Failure::Panic(panic_message(e)))?
+            .map_err(Failure::Error)
+    };
+    match timeout {
+        None => go(),
+        Some(limit) => {
+            let (tx, rx) = mpsc::channel();
+            thread::spawn(move || {
+                // Nobody's listening after a timeout.
+                let _ = tx.send(go());
+            });
+            rx.recv_timeout(limit)
+                .unwrap_or(Err(Failure::Timeout(limit)))
+        }
+    }
+}
+
+fn measure(
+    f: &(dyn Fn(String, &Visualizer) -> anyhow::Result<Answer> + Send + Sync),
+    input: &str,
+    vis: &Visualizer,
+    bench: Option<u32>,
+    mem: bool,
+) -> anyhow::Result<Run> {
+    let first = input.to_string();
+    let now = Instant::now();
+    let (answer, usage) = match mem {
+        true => {
+            let (answer, usage) = mem::measure(|| f(first, vis));
+            (answer, Some(usage))
+        }
+        false => (f(first, vis), None),
+    };
+    let mut elapsed = now.elapsed();
+    let answer = answer?;
+    let vis_error = vis.finish().err();
+    let mut stats = None;
+    if let Some(runs) = bench {
+        // The run above was the warm-up.
+        let samples = (0..runs.max(1))
+            .map(|_| {
+                let input = input.to_string();
+                let now = Instant::now();
+                let _ = f(input, &Visualizer::off());
+                now.elapsed()
+            })
+            .collect();
+        let s = Stats::new(samples);
+        elapsed = s.median;
+        stats = Some(s);
+    }
+    Ok(Run {
+        answer,
+        elapsed,
+        stats,
+        mem: usage,
+        vis_error,
+    })
+}
+
+fn panic_message(e: Box<dyn Any + Send>) -> String {
+    if let Some(s) = e.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = e.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "(no message)".to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{sync::Arc, thread, time::Duration};
+
+    use super::{execute, Failure};
+    use crate::{
+        answer::Answer,
+        solver::Solver,
+        vis::{Frame, Frontend, Visualizer},
+    };
+
+    fn solver(f: impl Fn(String) -> anyhow::Result<Answer> + Send + Sync + 'static) -> Solver {
+        Solver {
+            year: 2023,
+            day: 1,
+            part: 1,
+            label: None,
+            f: Arc::new(move |input, _vis| f(input)),
+            slow: false,
+            unsolved: false,
+        }
+    }
+
+    fn run(solver: &Solver, timeout: Option<Duration>) -> Result<Answer, Failure> {
+        execute(solver, "input", Visualizer::off(), None, false, timeout).map(|run| run.answer)
+    }
+
+    #[test]
+    fn answers() {
+        let s = solver(|input| Ok(Answer::from(input.len())));
+        assert!(matches!(run(&s, None), Ok(Answer::Int(5))));
+        assert!(matches!(
+            run(&s, Some(Duration::from_secs(10))),
+            Ok(Answer::Int(5))
+        ));
+        let s = solver(|_| anyhow::bail!("no luck"));
+        let failure = run(&s, None).err().unwrap();
+        assert_eq!("ERROR: no luck", failure.to_string());
+    }
+
+    // Can't save anything.
+    struct Broken;
+
+    impl Frontend for Broken {
+        fn log(&mut self, _line: &str) {}
+        fn frame(&mut self, _frame: &Frame) {}
+        fn finish(&mut self) -> anyhow::Result<()> {
+            anyhow::bail!("disk full")
+        }
+    }
+
+    #[test]
+    fn keeps_answer_without_vis() {
+        let s = solver(|_| Ok(Answer::from(7)));
+        let run = execute(&s, "", Visualizer::new(Broken), None, false, None)
+            .ok()
+            .unwrap();
+        assert!(matches!(run.answer, Answer::Int(7)));
+        assert_eq!("disk full", run.vis_error.unwrap().to_string());
+    }
+
+    #[test]
+    fn panics() {
+        let s = solver(|input| panic!("can't handle {input}"));
+        for timeout in [None, Some(Duration::from_secs(10))] {
+            let failure = run(&s, timeout).err().unwrap();
+            assert!(matches!(failure, Failure::Panic(_)));
+            assert_eq!("PANIC: can't handle input", failure.to_string());
+        }
+    }
+
+    #[test]
+    fn timeouts() {
+        let s = solver(|_| {
+            thread::sleep(Duration::from_secs(2));
+            Ok(Answer::from(1))
+        });
+        let limit = Duration::from_millis(50);
+        let failure = run(&s, Some(limit)).err().unwrap();
+        assert!(matches!(failure, Failure::Timeout(d) if d == limit));
+        assert_eq!("TIMEOUT after 50.00ms", failure.to_string());
+    }
+}
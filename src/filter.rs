@@ -0,0 +1,437 @@
+use std::{ops::RangeInclusive, str::FromStr};
+
+use crate::solver::Solver;
+
+// Which solvers to run. Each term on the command line picks some solvers,
+// and the ones picked by a '!' term are left out:
+//
+//   TERM  = ['!'] ( DATE ['@' LABEL] | '@' LABEL | 'slow' )
+//   DATE  = YEARS | [YEARS '/'] DAYS ['/' PARTS] | '/' PARTS
+//   YEARS, DAYS, PARTS = '*' | N[-N] [',' N[-N]]...
+//
+// Years have 4 digits and days have 1 or 2, so '2023' is a year and '23' is
+// a day. With two fields, a '*' at the start is the years, so '*/5' is day 5
+// of every year; '/PARTS' picks parts of every day. For example,
+// '2022/20-25', '2023/1,5,12/2', '*/5/2', '@orig', and '2023 !slow'.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Filter {
+    pub include: Vec<Term>,
+    pub exclude: Vec<Term>,
+}
+
+// None means any.
+pub type Set<T> = Option<Vec<RangeInclusive<T>>>;
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Term {
+    pub years: Set<i32>,
+    pub days: Set<u32>,
+    pub parts: Set<u8>,
+    pub label: Option<String>,
+    pub slow: bool,
+}
+
+impl Filter {
+    // With only '!' terms, or none at all, the rest is up to the defaults.
+    pub fn parse(terms: &[String]) -> anyhow::Result<Filter> {
+        let mut filter = Filter::default();
+        for term in terms {
+            match term.strip_prefix('!') {
+                Some(t) => filter.exclude.push(Term::parse(t)?),
+                None => filter.include.push(Term::parse(term)?),
+            }
+        }
+        if filter.include.is_empty() {
+            filter.include.push(Term::default());
+        }
+        Ok(filter)
+    }
+
+    pub fn matches(&self, solver: &Solver) -> bool {
+        self.include.iter().any(|t| t.matches(solver))
+            && !self.exclude.iter().any(|t| t.matches(solver))
+    }
+
+    // Whether a 'slow' term asks for the slow solvers, so they should run.
+    pub fn wants_slow(&self) -> bool {
+        self.include.iter().any(|t| t.slow)
+    }
+
+    // The day, if exactly one is selected.
+    pub fn single_day(&self) -> Option<(i32, u32)> {
+        match (self.include.as_slice(), self.exclude.is_empty()) {
+            ([term], true) => Some((single(&term.years)?, single(&term.days)?)),
+            _ => None,
+        }
+    }
+}
+
+impl Term {
+    pub fn parse(term: &str) -> anyhow::Result<Term> {
+        let err = |msg: String| anyhow::anyhow!("invalid filter {term:?}: {msg}");
+        if term.starts_with('!') {
+            return Err(err("'!' only goes at the start".to_string()));
+        }
+        if term == "slow" {
+            return Ok(Term {
+                slow: true,
+                ..Term::default()
+            });
+        }
+        let (date, label) = match term.split_once('@') {
+            Some((_, "")) => return Err(err("needs a label after the @".to_string())),
+            Some((date, label)) => (date, Some(label.to_string())),
+            None => (term, None),
+        };
+        let mut t = Term {
+            label,
+            ..Term::default()
+        };
+        if date.is_empty() {
+            if t.label.is_none() {
+                return Err(err("it's empty".to_string()));
+            }
+            return Ok(t);
+        }
+        let fields: Vec<&str> = date.split('/').collect();
+        match fields.as_slice() {
+            [f] if is_year(f) => t.years = parse_years(f).map_err(err)?,
+            [f] => t.days = parse_set(f, 1..=25, "day").map_err(err)?,
+            ["", p] => {
+                if p.is_empty() {
+                    return Err(err("needs a part number after the /".to_string()));
+                }
+                t.parts = parse_set(p, 1..=2, "part").map_err(err)?;
+            }
+            [y, d] if is_year(y) || *y == "*" => {
+                t.years = parse_years(y).map_err(err)?;
+                t.days = parse_set(d, 1..=25, "day").map_err(err)?;
+            }
+            [d, p] => {
+                t.days = parse_set(d, 1..=25, "day").map_err(err)?;
+                t.parts = parse_set(p, 1..=2, "part").map_err(err)?;
+            }
+            [y, d, p] => {
+                if !is_year(y) && *y != "*" {
+                    return Err(err(format!("expected YYYY but got {y:?}")));
+                }
+                t.years = parse_years(y).map_err(err)?;
+                t.days = parse_set(d, 1..=25, "day").map_err(err)?;
+                t.parts = parse_set(p, 1..=2, "part").map_err(err)?;
+            }
+            _ => return Err(err("too many components".to_string())),
+        }
+        Ok(t)
+    }
+
+    // Whether there's nothing about which puzzles to pick.
+    pub fn is_undated(&self) -> bool {
+        self.years.is_none() && self.days.is_none()
+    }
+
+    // Whether this only picks puzzles, so it's the same as the old
+    // [YYYY/]DD[/PART]. Returns those, with None for any.
+    pub fn single(&self) -> Option<(Option<i32>, Option<u32>, Option<u8>)> {
+        fn one<T: Copy + PartialEq>(set: &Set<T>) -> Option<Option<T>> {
+            match set {
+                None => Some(None),
+                Some(_) => single(set).map(Some),
+            }
+        }
+        if self.label.is_some() || self.slow {
+            return None;
+        }
+        Some((one(&self.years)?, one(&self.days)?, one(&self.parts)?))
+    }
+
+    fn matches(&self, solver: &Solver) -> bool {
+        contains(&self.years, solver.year)
+            && contains(&self.days, solver.day)
+            && contains(&self.parts, solver.part)
+            && self
+                .label
+                .as_deref()
+                .is_none_or(|l| solver.label == Some(l))
+            && (!self.slow || solver.slow)
+    }
+}
+
+// Just the one value.
+pub fn only<T: Copy>(n: T) -> Set<T> {
+    Some(vec![n..=n])
+}
+
+fn is_year(field: &str) -> bool {
+    field.split([',', '-']).next().is_some_and(|n| n.len() == 4)
+}
+
+// Unlike days and parts, '*' can't leave the years unset, or they'd default
+// to this year's.
+fn parse_years(field: &str) -> Result<Set<i32>, String> {
+    const YEARS: RangeInclusive<i32> = 2015..=9999;
+    match field {
+        "*" => Ok(Some(vec![YEARS])),
+        _ => parse_set(field, YEARS, "year"),
+    }
+}
+
+fn contains<T: PartialOrd>(set: &Set<T>, value: T) -> bool {
+    match set {
+        None => true,
+        Some(ranges) => ranges.iter().any(|r| r.contains(&value)),
+    }
+}
+
+fn single<T: Copy + PartialEq>(set: &Set<T>) -> Option<T> {
+    match set.as_deref() {
+        Some([r]) if r.start() == r.end() => Some(*r.start()),
+        _ => None,
+    }
+}
+
+fn parse_set<T>(field: &str, valid: RangeInclusive<T>, what: &str) -> Result<Set<T>, String>
+where
+    T: FromStr + PartialOrd + Copy + std::fmt::Display,
+{
+    if field == "*" {
+        return Ok(None);
+    }
+    let num = |s: &str| -> Result<T, String> {
+        let n: T = s
+            .parse()
+            .map_err(|_| format!("expected a {what} but got {s:?}"))?;
+        if !valid.contains(&n) {
+            return Err(format!(
+                "{what} {n} isn't between {} and {}",
+                valid.start(),
+                valid.end()
+            ));
+        }
+        Ok(n)
+    };
+    let mut ranges = Vec::new();
+    for item in field.split(',') {
+        let range = match item.split_once('-') {
+            Some((a, b)) => {
+                let (a, b) = (num(a)?, num(b)?);
+                if a > b {
+                    return Err(format!("{what}s {item} are backwards"));
+                }
+                a..=b
+            }
+            None => {
+                let n = num(item)?;
+                n..=n
+            }
+        };
+        ranges.push(range);
+    }
+    Ok(Some(ranges))
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use super::{Filter, Term};
+    use crate::{answer::Answer, solver::Solver};
+
+    fn solver(year: i32, day: u32, part: u8, label: Option<&'static str>, slow: bool) -> Solver {
+        Solver {
+            year,
+            day,
+            part,
+            label,
+            f: Arc::new(|_, _| Ok(Answer::Unsolved)),
+            slow,
+            unsolved: false,
+        }
+    }
+
+    fn filter(terms: &[&str]) -> Filter {
+        let terms: Vec<String> = terms.iter().map(|t| t.to_string()).collect();
+        Filter::parse(&terms).unwrap()
+    }
+
+    fn err(term: &str) -> String {
+        Term::parse(term).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn parse_day_range() {
+        let t = Term::parse("2022/20-25").unwrap();
+        assert_eq!(Some(vec![2022..=2022]), t.years);
+        assert_eq!(Some(vec![20..=25]), t.days);
+        assert_eq!(None, t.parts);
+    }
+
+    #[test]
+    fn parse_day_list() {
+        let t = Term::parse("2023/1,5,12/2").unwrap();
+        assert_eq!(Some(vec![1..=1, 5..=5, 12..=12]), t.days);
+        assert_eq!(Some(vec![2..=2]), t.parts);
+    }
+
+    #[test]
+    fn parse_year_list_and_range() {
+        let t = Term::parse("2015-2017,2023").unwrap();
+        assert_eq!(Some(vec![2015..=2017, 2023..=2023]), t.years);
+        assert_eq!(None, t.days);
+    }
+
+    #[test]
+    fn parse_days_and_part() {
+        let t = Term::parse("1-3/1").unwrap();
+        assert_eq!(None, t.years);
+        assert_eq!(Some(vec![1..=3]), t.days);
+        assert_eq!(Some(vec![1..=1]), t.parts);
+    }
+
+    #[test]
+    fn parse_wildcard() {
+        let t = Term::parse("2023/*/2").unwrap();
+        assert_eq!(Some(vec![2023..=2023]), t.years);
+        assert_eq!(None, t.days);
+        assert_eq!(Some(vec![2..=2]), t.parts);
+
+        let t = Term::parse("*/5/2").unwrap();
+        assert_eq!(Some(vec![2015..=9999]), t.years);
+        assert_eq!(Some(vec![5..=5]), t.days);
+        assert_eq!(Some(vec![2..=2]), t.parts);
+        // With two fields, the '*' is the years.
+        let t = Term::parse("*/5").unwrap();
+        assert_eq!(Some(vec![2015..=9999]), t.years);
+        assert_eq!(Some(vec![5..=5]), t.days);
+        assert_eq!(None, t.parts);
+        assert_eq!(None, Term::parse("*/5").unwrap().single());
+    }
+
+    #[test]
+    fn parse_labels() {
+        let t = Term::parse("@orig").unwrap();
+        assert_eq!(Some("orig".to_string()), t.label);
+        assert!(t.is_undated());
+        let t = Term::parse("2023/5@from oliver").unwrap();
+        assert_eq!(Some("from oliver".to_string()), t.label);
+        assert_eq!(Some(vec![5..=5]), t.days);
+    }
+
+    #[test]
+    fn parse_exclusions() {
+        let f = filter(&["2023", "!slow", "!5/2"]);
+        assert_eq!(1, f.include.len());
+        assert_eq!(2, f.exclude.len());
+        assert!(f.exclude[0].slow);
+        assert_eq!(Some(vec![5..=5]), f.exclude[1].days);
+
+        // Only exclusions leaves the default.
+        let f = filter(&["!slow"]);
+        assert_eq!(vec![Term::default()], f.include);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            r#"invalid filter "2023/26": day 26 isn't between 1 and 25"#,
+            err("2023/26")
+        );
+        assert_eq!(
+            r#"invalid filter "2022/25-20": days 25-20 are backwards"#,
+            err("2022/25-20")
+        );
+        assert_eq!(
+            r#"invalid filter "5/3": part 3 isn't between 1 and 2"#,
+            err("5/3")
+        );
+        assert_eq!(
+            r#"invalid filter "2023/x": expected a day but got "x""#,
+            err("2023/x")
+        );
+        assert_eq!(
+            r#"invalid filter "2023/1,,2": expected a day but got """#,
+            err("2023/1,,2")
+        );
+        assert_eq!(
+            r#"invalid filter "23/1/1": expected YYYY but got "23""#,
+            err("23/1/1")
+        );
+        assert_eq!(
+            r#"invalid filter "2023/1/1/1": too many components"#,
+            err("2023/1/1/1")
+        );
+        assert_eq!(
+            r#"invalid filter "/": needs a part number after the /"#,
+            err("/")
+        );
+        assert_eq!(
+            r#"invalid filter "5@": needs a label after the @"#,
+            err("5@")
+        );
+        assert_eq!(
+            r#"invalid filter "!5": '!' only goes at the start"#,
+            err("!5")
+        );
+        assert_eq!(r#"invalid filter "": it's empty"#, err(""));
+        assert_eq!(
+            r#"invalid filter "2014": year 2014 isn't between 2015 and 9999"#,
+            err("2014")
+        );
+    }
+
+    #[test]
+    fn match_terms() {
+        let f = filter(&["2022/20-25", "2023/1,5/2"]);
+        assert!(f.matches(&solver(2022, 20, 1, None, false)));
+        assert!(f.matches(&solver(2022, 25, 2, None, false)));
+        assert!(!f.matches(&solver(2022, 19, 1, None, false)));
+        assert!(f.matches(&solver(2023, 5, 2, None, false)));
+        assert!(!f.matches(&solver(2023, 5, 1, None, false)));
+        assert!(!f.matches(&solver(2023, 2, 2, None, false)));
+    }
+
+    #[test]
+    fn match_labels_and_exclusions() {
+        let f = filter(&["@orig"]);
+        assert!(f.matches(&solver(2023, 5, 1, Some("orig"), false)));
+        assert!(!f.matches(&solver(2023, 5, 1, Some("new"), false)));
+        assert!(!f.matches(&solver(2023, 5, 1, None, false)));
+
+        let f = filter(&["2023", "!slow", "!@new"]);
+        assert!(f.matches(&solver(2023, 5, 1, Some("orig"), false)));
+        assert!(!f.matches(&solver(2023, 5, 1, Some("new"), false)));
+        assert!(!f.matches(&solver(2023, 5, 1, None, true)));
+        assert!(!f.matches(&solver(2022, 5, 1, None, false)));
+    }
+
+    #[test]
+    fn wants_slow() {
+        assert!(filter(&["slow"]).wants_slow());
+        assert!(filter(&["2023/5", "slow"]).wants_slow());
+        assert!(!filter(&["2023/5"]).wants_slow());
+        assert!(!filter(&["2023", "!slow"]).wants_slow());
+    }
+
+    #[test]
+    fn single_day() {
+        assert_eq!(Some((2023, 5)), filter(&["2023/5"]).single_day());
+        assert_eq!(Some((2023, 5)), filter(&["2023/5/2"]).single_day());
+        assert_eq!(None, filter(&["2023/5-6"]).single_day());
+        assert_eq!(None, filter(&["2023/5", "2023/6"]).single_day());
+        assert_eq!(None, filter(&["2023/5", "!slow"]).single_day());
+        assert_eq!(None, filter(&["5"]).single_day());
+    }
+
+    #[test]
+    fn single_term() {
+        assert_eq!(
+            Some((Some(2023), Some(5), Some(1))),
+            Term::parse("2023/5/1").unwrap().single()
+        );
+        assert_eq!(
+            Some((None, Some(5), None)),
+            Term::parse("5").unwrap().single()
+        );
+        assert_eq!(None, Term::parse("2023/5,6").unwrap().single());
+        assert_eq!(None, Term::parse("5@orig").unwrap().single());
+    }
+}
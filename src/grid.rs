@@ -0,0 +1,398 @@
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+};
+
+use crate::vis::Frame;
+
+// A position in a grid, as (row, col) from the top left.
+pub type Pos = (usize, usize);
+
+// Steps to the neighbours of a cell, as (rows, cols): up, right, down, left.
+pub const DIRS4: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+
+// The same, plus the diagonals, clockwise from up.
+#[allow(dead_code)]
+pub const DIRS8: [(isize, isize); 8] = [
+    (-1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+    (-1, -1),
+];
+
+// A rectangle of cells, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    rows: usize,
+    cols: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(rows: usize, cols: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            rows,
+            cols,
+            cells: vec![fill; rows * cols],
+        }
+    }
+
+    // Panics if the rows aren't all the same length.
+    pub fn from_rows(rows: impl IntoIterator<Item = Vec<T>>) -> Self {
+        let mut grid = Self {
+            rows: 0,
+            cols: 0,
+            cells: Vec::new(),
+        };
+        for row in rows {
+            grid.push_row(row);
+        }
+        grid
+    }
+
+    // One cell per char, one row per line. Blank lines at the start and end
+    // are skipped, and so is a \r at the end of each line, but spaces are
+    // cells like anything else.
+    pub fn parse(input: &str, cell: impl FnMut(char) -> T) -> Self {
+        let mut cell = cell;
+        Self::from_rows(input.trim_matches('\n').lines().map(|line| {
+            let line = line.strip_suffix('\r').unwrap_or(line);
+            line.chars().map(&mut cell).collect()
+        }))
+    }
+
+    // The same, without any whitespace around each line, for puzzles where
+    // spaces aren't cells.
+    pub fn parse_trimmed(input: &str, cell: impl FnMut(char) -> T) -> Self {
+        let mut cell = cell;
+        Self::from_rows(
+            input
+                .trim()
+                .lines()
+                .map(|line| line.trim().chars().map(&mut cell).collect()),
+        )
+    }
+
+    // Adds a row at the bottom.
+    pub fn push_row(&mut self, row: Vec<T>) {
+        if self.rows == 0 {
+            self.cols = row.len();
+        }
+        assert_eq!(
+            self.cols,
+            row.len(),
+            "row {} has {} cells instead of {}",
+            self.rows,
+            row.len(),
+            self.cols
+        );
+        self.cells.extend(row);
+        self.rows += 1;
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn contains(&self, (r, c): Pos) -> bool {
+        r < self.rows && c < self.cols
+    }
+
+    #[allow(dead_code)]
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.contains(pos).then(|| &self[pos])
+    }
+
+    #[allow(dead_code)]
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.contains(pos).then(|| &mut self[pos])
+    }
+
+    // For grids that are indexed with signed coordinates. Anything outside
+    // the grid is None.
+    pub fn pos_signed(&self, (r, c): (isize, isize)) -> Option<Pos> {
+        let pos = (usize::try_from(r).ok()?, usize::try_from(c).ok()?);
+        self.contains(pos).then_some(pos)
+    }
+
+    pub fn get_signed(&self, pos: (isize, isize)) -> Option<&T> {
+        self.pos_signed(pos).map(|pos| &self[pos])
+    }
+
+    // For grids that repeat forever in every direction.
+    #[allow(dead_code)]
+    pub fn pos_wrapping(&self, (r, c): (isize, isize)) -> Pos {
+        (
+            r.rem_euclid(self.rows as isize) as usize,
+            c.rem_euclid(self.cols as isize) as usize,
+        )
+    }
+
+    #[allow(dead_code)]
+    pub fn get_wrapping(&self, pos: (isize, isize)) -> &T {
+        &self[self.pos_wrapping(pos)]
+    }
+
+    // Where `(dr, dc)` from `pos` goes, if it's still in the grid.
+    pub fn step(&self, (r, c): Pos, (dr, dc): (isize, isize)) -> Option<Pos> {
+        let pos = (r.checked_add_signed(dr)?, c.checked_add_signed(dc)?);
+        self.contains(pos).then_some(pos)
+    }
+
+    // The neighbours above, right, below and left that are in the grid.
+    pub fn neighbors4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        DIRS4.iter().filter_map(move |&d| self.step(pos, d))
+    }
+
+    // The same, plus the diagonals.
+    #[allow(dead_code)]
+    pub fn neighbors8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        DIRS8.iter().filter_map(move |&d| self.step(pos, d))
+    }
+
+    pub fn row(&self, r: usize) -> &[T] {
+        &self.cells[r * self.cols..(r + 1) * self.cols]
+    }
+
+    #[allow(dead_code)]
+    pub fn row_mut(&mut self, r: usize) -> &mut [T] {
+        &mut self.cells[r * self.cols..(r + 1) * self.cols]
+    }
+
+    // Top to bottom.
+    pub fn col(&self, c: usize) -> impl DoubleEndedIterator<Item = &T> + '_ {
+        assert!(c < self.cols, "column {c} is outside the grid");
+        self.cells.iter().skip(c).step_by(self.cols.max(1))
+    }
+
+    pub fn iter_rows(&self) -> impl DoubleEndedIterator<Item = &[T]> + '_ {
+        (0..self.rows).map(|r| self.row(r))
+    }
+
+    // Every cell, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> + '_ {
+        let cols = self.cols;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, t)| ((i / cols, i % cols), t))
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let cols = self.cols;
+        (0..self.rows).flat_map(move |r| (0..cols).map(move |c| (r, c)))
+    }
+
+    // The first cell that matches, row by row.
+    pub fn find(&self, f: impl Fn(&T) -> bool) -> Option<Pos> {
+        self.iter().find(|(_, t)| f(t)).map(|(pos, _)| pos)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            rows: self.rows,
+            cols: self.cols,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    // Makes a new grid with `cell(pos)` from this one at each position.
+    fn remap(&self, rows: usize, cols: usize, cell: impl Fn(Pos) -> Pos) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..rows)
+            .flat_map(|r| (0..cols).map(move |c| (r, c)))
+            .map(|pos| self[cell(pos)].clone())
+            .collect();
+        Self { rows, cols, cells }
+    }
+
+    // Rows become columns.
+    #[allow(dead_code)]
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.remap(self.cols, self.rows, |(r, c)| (c, r))
+    }
+
+    // A quarter turn clockwise, so the left column becomes the top row.
+    pub fn rotate_cw(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.remap(self.cols, self.rows, |(r, c)| (self.rows - 1 - c, r))
+    }
+
+    #[allow(dead_code)]
+    pub fn rotate_ccw(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.remap(self.cols, self.rows, |(r, c)| (c, self.cols - 1 - r))
+    }
+
+    // Mirrors left and right.
+    #[allow(dead_code)]
+    pub fn flip_horizontal(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.remap(self.rows, self.cols, |(r, c)| (r, self.cols - 1 - c))
+    }
+
+    // Mirrors top and bottom.
+    #[allow(dead_code)]
+    pub fn flip_vertical(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.remap(self.rows, self.cols, |(r, c)| (self.rows - 1 - r, c))
+    }
+}
+
+impl<T: Display> Grid<T> {
+    // For the visualizer. Each cell's Display should be one char.
+    pub fn frame(&self, caption: impl Display) -> Frame {
+        Frame::new(
+            caption,
+            self.iter_rows()
+                .map(|row| row.iter().map(|t| t.to_string()).collect()),
+        )
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (r, c): Pos) -> &T {
+        assert!(self.contains((r, c)), "{:?} is outside the grid", (r, c));
+        &self.cells[r * self.cols + c]
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, (r, c): Pos) -> &mut T {
+        assert!(self.contains((r, c)), "{:?} is outside the grid", (r, c));
+        &mut self.cells[r * self.cols + c]
+    }
+}
+
+// One line per row, with nothing between the cells.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.iter_rows() {
+            for t in row {
+                write!(f, "{t}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Grid;
+
+    fn grid(s: &str) -> Grid<char> {
+        Grid::parse(s, |c| c)
+    }
+
+    #[test]
+    fn parse() {
+        let g = Grid::parse("\n12\n34\n56\n", |c| c.to_digit(10).unwrap());
+        assert_eq!((3, 2), (g.rows(), g.cols()));
+        assert_eq!(4, g[(1, 1)]);
+        assert_eq!(&[5, 6], g.row(2));
+        assert_eq!(vec![&2, &4, &6], g.col(1).collect::<Vec<_>>());
+        assert_eq!(Some((2, 0)), g.find(|&n| n == 5));
+        assert_eq!(None, g.find(|&n| n == 7));
+        assert_eq!("12\n34\n56\n", g.to_string());
+
+        // Spaces are cells too, even at the end of a line, but \r isn't.
+        let g = grid("a \r\nb \r\n");
+        assert_eq!("a \nb \n", g.to_string());
+        let g = grid("a \r\nb \r");
+        assert_eq!("a \nb \n", g.to_string());
+        let g = Grid::parse_trimmed("\n  ab \r\n\tcd\r\n  ", |c| c);
+        assert_eq!("ab\ncd\n", g.to_string());
+
+        let g = grid("  #\n # \n#  ");
+        assert_eq!((3, 3), (g.rows(), g.cols()));
+        assert_eq!(vec![&'#', &' ', &' '], g.col(0).rev().collect::<Vec<_>>());
+    }
+
+    #[test]
+    #[should_panic(expected = "row 1 has 3 cells instead of 2")]
+    fn ragged() {
+        grid("ab\ncde");
+    }
+
+    #[test]
+    fn coordinates() {
+        let g = grid("abc\ndef");
+        assert_eq!(Some(&'f'), g.get((1, 2)));
+        assert_eq!(None, g.get((2, 0)));
+        assert_eq!(Some(&'d'), g.get_signed((1, 0)));
+        assert_eq!(None, g.get_signed((-1, 0)));
+        assert_eq!(&'f', g.get_wrapping((-1, -1)));
+        assert_eq!(&'a', g.get_wrapping((4, 6)));
+        assert_eq!(Some((0, 1)), g.step((1, 2), (-1, -1)));
+        assert_eq!(None, g.step((0, 0), (0, -1)));
+    }
+
+    #[test]
+    fn neighbors() {
+        let g = grid("abc\ndef\nghi");
+        let around = |pos, eight| -> String {
+            match eight {
+                false => g.neighbors4(pos).map(|p| g[p]).collect(),
+                true => g.neighbors8(pos).map(|p| g[p]).collect(),
+            }
+        };
+        assert_eq!("bfhd", around((1, 1), false));
+        assert_eq!("bcfihgda", around((1, 1), true));
+        assert_eq!("bd", around((0, 0), false));
+        assert_eq!("fhe", around((2, 2), true));
+    }
+
+    #[test]
+    fn transforms() {
+        let g = grid("abc\ndef");
+        assert_eq!("ad\nbe\ncf\n", g.transpose().to_string());
+        assert_eq!("da\neb\nfc\n", g.rotate_cw().to_string());
+        assert_eq!("cf\nbe\nad\n", g.rotate_ccw().to_string());
+        assert_eq!("cba\nfed\n", g.flip_horizontal().to_string());
+        assert_eq!("def\nabc\n", g.flip_vertical().to_string());
+        assert_eq!(g, g.rotate_cw().rotate_cw().rotate_cw().rotate_cw());
+        assert_eq!(g.rotate_cw(), g.transpose().flip_horizontal());
+    }
+
+    #[test]
+    fn push_row() {
+        let mut g = Grid::new(1, 3, 0);
+        g.push_row(vec![1, 2, 3]);
+        g[(0, 1)] = 9;
+        *g.get_mut((1, 2)).unwrap() = 4;
+        g.row_mut(0)[2] = 8;
+        assert_eq!(None, g.get_mut((2, 0)));
+        assert_eq!("098\n124\n", g.to_string());
+        assert_eq!(
+            vec![((1, 0), &1), ((1, 1), &2)],
+            g.iter().skip(3).take(2).collect::<Vec<_>>()
+        );
+    }
+}
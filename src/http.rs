@@ -0,0 +1,223 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex, OnceLock,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use reqwest::{
+    blocking::{Client, RequestBuilder},
+    StatusCode,
+};
+
+pub const BASE_URL: &str = "https://adventofcode.com";
+
+// adventofcode.com asks automated tools to say who to contact about them.
+const USER_AGENT: &str = concat!(
+    "advent-of-code-rust/",
+    env!("CARGO_PKG_VERSION"),
+    " (+https://github.com/spraints/advent-of-code-rust)"
+);
+
+// Everything that talks to adventofcode.com goes through here, so that it can
+// be polite about it.
+pub struct Http {
+    client: Client,
+    // The shortest time between requests.
+    interval: Duration,
+    // How long to wait before the first retry. This doubles after each one.
+    backoff: Duration,
+    attempts: u32,
+    last: Mutex<Option<Instant>>,
+    offline: AtomicBool,
+}
+
+pub fn http() -> &'static Http {
+    static HTTP: OnceLock<Http> = OnceLock::new();
+    HTTP.get_or_init(|| Http::new(Duration::from_secs(1), Duration::from_secs(2), 4))
+}
+
+impl Http {
+    fn new(interval: Duration, backoff: Duration, attempts: u32) -> Self {
+        Self {
+            client: Client::builder()
+                .user_agent(USER_AGENT)
+                .build()
+                .expect("http client"),
+            interval,
+            backoff,
+            attempts,
+            last: Mutex::new(None),
+            offline: AtomicBool::new(false),
+        }
+    }
+
+    pub fn set_offline(&self, offline: bool) {
+        self.offline.store(offline, Ordering::SeqCst);
+    }
+
+    pub fn is_offline(&self) -> bool {
+        self.offline.load(Ordering::SeqCst)
+    }
+
+    pub fn get(&self, url: &str, token: &str) -> anyhow::Result<String> {
+        self.send(url, true, || {
+            self.client
+                .get(url)
+                .header("Cookie", format!("session={}", token))
+        })
+    }
+
+    // Posts aren't retried, because the server might have gotten the first one.
+    pub fn post_form(
+        &self,
+        url: &str,
+        token: &str,
+        form: &[(&str, String)],
+    ) -> anyhow::Result<String> {
+        self.send(url, false, || {
+            self.client
+                .post(url)
+                .header("Cookie", format!("session={}", token))
+                .form(form)
+        })
+    }
+
+    fn send(
+        &self,
+        url: &str,
+        retry: bool,
+        req: impl Fn() -> RequestBuilder,
+    ) -> anyhow::Result<String> {
+        anyhow::ensure!(
+            !self.is_offline(),
+            "{url}: not connecting because of --offline"
+        );
+        let mut delay = self.backoff;
+        let mut attempt = 1;
+        loop {
+            self.throttle();
+            let err = match req().send() {
+                Ok(resp) if resp.status().is_success() => return Ok(resp.text()?),
+                Ok(resp) if resp.status().is_server_error() => {
+                    anyhow::anyhow!("{}: {}", url, resp.status())
+                }
+                Ok(resp) => anyhow::bail!(explain(url, resp.status())),
+                Err(e) if e.is_connect() || e.is_timeout() => anyhow::anyhow!("{url}: {e}"),
+                Err(e) => return Err(e.into()),
+            };
+            if !retry || attempt >= self.attempts {
+                return Err(err);
+            }
+            eprintln!("{err:#}, retrying in {delay:?}");
+            thread::sleep(delay);
+            delay *= 2;
+            attempt += 1;
+        }
+    }
+
+    fn throttle(&self) {
+        let mut last = self.last.lock().unwrap();
+        if let Some(last) = *last {
+            let next = last + self.interval;
+            let now = Instant::now();
+            if next > now {
+                thread::sleep(next - now);
+            }
+        }
+        *last = Some(Instant::now());
+    }
+}
+
+fn explain(url: &str, status: StatusCode) -> String {
+    match status {
+        StatusCode::BAD_REQUEST => format!(
+            "{url}: {status}: the session token is probably missing or expired, set a new one with --set-token"
+        ),
+        StatusCode::NOT_FOUND => {
+            format!("{url}: {status}: the puzzle isn't unlocked yet, or doesn't exist")
+        }
+        _ => format!("{url}: {status}"),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        io::{Read, Write},
+        net::TcpListener,
+        thread,
+        time::Duration,
+    };
+
+    use super::Http;
+
+    // Answers each request with the next of `statuses`, and returns the requests.
+    fn serve(statuses: &'static [u16]) -> (String, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let mut reqs = Vec::new();
+            for status in statuses {
+                let (mut conn, _) = listener.accept().unwrap();
+                let mut req = Vec::new();
+                let mut buf = [0; 1024];
+                while !String::from_utf8_lossy(&req).contains("\r\n\r\n") {
+                    let n = conn.read(&mut buf).unwrap();
+                    req.extend_from_slice(&buf[..n]);
+                }
+                write!(
+                    conn,
+                    "HTTP/1.1 {status} X\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok"
+                )
+                .unwrap();
+                reqs.push(String::from_utf8(req).unwrap());
+            }
+            reqs
+        });
+        (base_url, server)
+    }
+
+    fn test_http() -> Http {
+        Http::new(Duration::ZERO, Duration::ZERO, 3)
+    }
+
+    #[test]
+    fn retries_server_errors() {
+        let (base_url, server) = serve(&[500, 503, 200]);
+        let body = test_http().get(&format!("{base_url}/2023/day/1/input"), "abc");
+        assert_eq!("ok", body.unwrap());
+        let reqs = server.join().unwrap();
+        assert_eq!(3, reqs.len());
+        assert!(reqs[0].starts_with("GET /2023/day/1/input "), "{}", reqs[0]);
+        assert!(reqs[0].contains("session=abc"), "{}", reqs[0]);
+        assert!(
+            reqs[0]
+                .to_lowercase()
+                .contains("user-agent: advent-of-code-rust/"),
+            "{}",
+            reqs[0]
+        );
+    }
+
+    #[test]
+    fn explains_client_errors() {
+        let (base_url, server) = serve(&[400, 404]);
+        let http = test_http();
+        let err = http.get(&base_url, "abc").unwrap_err().to_string();
+        assert!(err.contains("--set-token"), "{err}");
+        let err = http.get(&base_url, "abc").unwrap_err().to_string();
+        assert!(err.contains("isn't unlocked yet"), "{err}");
+        assert_eq!(2, server.join().unwrap().len());
+    }
+
+    #[test]
+    fn offline() {
+        let http = test_http();
+        http.set_offline(true);
+        let err = http.get("http://127.0.0.1:1/", "abc").unwrap_err();
+        assert!(err.to_string().contains("--offline"), "{err}");
+    }
+}
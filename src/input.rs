@@ -1,33 +1,152 @@
-use std::fs;
+use std::{
+    fs,
+    io::{ErrorKind, Read},
+    path::Path,
+};
 
-use reqwest::blocking::Client;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
-pub fn get_input(year: i32, day: u32, token: &str) -> anyhow::Result<String> {
-    let input_file = file_for(year, day);
-    if let Ok(s) = fs::read_to_string(&input_file) {
-        return Ok(s);
+use crate::{
+    http::{http, BASE_URL},
+    profile::Profile,
+};
+
+pub fn get_input(profile: &Profile, year: i32, day: u32, token: &str) -> anyhow::Result<String> {
+    let input_file = file_for(profile, year, day);
+    match read_cached(&input_file) {
+        Ok(Some(s)) => return Ok(s),
+        Ok(None) => (),
+        Err(e) if !http().is_offline() => eprintln!("{e:#}, downloading it again"),
+        Err(e) => return Err(e),
     }
     let url = url_for(year, day);
-    println!("downloading {} ...", url);
-    let resp = Client::new()
-        .get(&url)
-        .header("Cookie", format!("session={}", token))
-        .send()?;
-    anyhow::ensure!(resp.status().is_success(), "{}: {}", url, resp.status());
-    fs::create_dir_all(year_dir(year))?;
-    let s = resp.text()?;
-    fs::write(&input_file, &s)?;
+    eprintln!("downloading {} ...", url);
+    let s = http().get(&url, token)?;
+    write_cached(&input_file, &url, &s)?;
+    Ok(s)
+}
+
+// Saved next to each downloaded file, in `{file}.json`.
+#[derive(Serialize, Deserialize)]
+struct CacheInfo {
+    url: String,
+    downloaded: String,
+    sha256: String,
+}
+
+// Reads a file that was saved by `write_cached`, and checks that it's the
+// same as when it was downloaded.
+pub fn read_cached(file: &str) -> anyhow::Result<Option<String>> {
+    let s = match fs::read_to_string(file) {
+        Ok(s) => s,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    match fs::read_to_string(format!("{file}.json")) {
+        Ok(info) => {
+            let info: CacheInfo = serde_json::from_str(&info)
+                .map_err(|e| anyhow::anyhow!("error reading {file}.json: {e}"))?;
+            anyhow::ensure!(
+                info.sha256 == sha256(&s),
+                "{file} is corrupt, it changed since it was downloaded from {} at {}",
+                info.url,
+                info.downloaded
+            );
+        }
+        // It was downloaded before checksums were saved.
+        Err(e) if e.kind() == ErrorKind::NotFound => (),
+        Err(e) => return Err(e.into()),
+    }
+    Ok(Some(s))
+}
+
+pub fn write_cached(file: &str, url: &str, s: &str) -> anyhow::Result<()> {
+    if let Some(dir) = Path::new(file).parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(file, s)?;
+    let info = CacheInfo {
+        url: url.to_string(),
+        downloaded: chrono::Utc::now().to_rfc3339(),
+        sha256: sha256(s),
+    };
+    fs::write(format!("{file}.json"), serde_json::to_string_pretty(&info)?)?;
+    Ok(())
+}
+
+fn sha256(s: &str) -> String {
+    hex::encode(Sha256::digest(s.as_bytes()))
+}
+
+// Reads an input file, or stdin if the path is "-".
+pub fn read_input(path: &str) -> anyhow::Result<String> {
+    let mut s = String::new();
+    if path == "-" {
+        std::io::stdin().read_to_string(&mut s)?;
+    } else {
+        s = fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("error reading input from {path:?}: {e}"))?;
+    }
     Ok(s)
 }
 
+pub fn describe_input(path: &str) -> String {
+    match path {
+        "-" => "stdin".to_string(),
+        p => format!("{p:?}"),
+    }
+}
+
+pub fn get_example(year: i32, day: u32, n: u32) -> anyhow::Result<String> {
+    let file = example_file_for(year, day, n);
+    fs::read_to_string(&file)
+        .map_err(|e| anyhow::anyhow!("no example {n} for {year}/{day} in {file}: {e}"))
+}
+
+// Examples are numbered from 1, in the order they appear in the puzzle.
+pub fn example_file_for(year: i32, day: u32, n: u32) -> String {
+    format!("{}/examples/{}/{}", year_dir(year), day, n)
+}
+
+pub fn page_file_for(year: i32, day: u32) -> String {
+    format!("{}/pages/{}.html", year_dir(year), day)
+}
+
 fn url_for(year: i32, day: u32) -> String {
-    format!("https://adventofcode.com/{}/day/{}/input", year, day)
+    format!("{}/{}/day/{}/input", BASE_URL, year, day)
 }
 
 fn year_dir(year: i32) -> String {
     format!("inputs/{}", year)
 }
 
-fn file_for(year: i32, day: u32) -> String {
-    format!("{}/{}", year_dir(year), day)
+fn file_for(profile: &Profile, year: i32, day: u32) -> String {
+    format!("{}/{}/{}", profile.dir("inputs"), year, day)
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use super::{read_cached, write_cached};
+
+    #[test]
+    fn detects_corrupt_cache() {
+        let dir = std::env::temp_dir().join(format!("aoc-cache-test-{}", std::process::id()));
+        let file = dir.join("5").to_string_lossy().into_owned();
+        assert_eq!(None, read_cached(&file).unwrap());
+
+        write_cached(&file, "https://example.com/5", "1\n2\n3\n").unwrap();
+        assert_eq!(Some("1\n2\n3\n".to_string()), read_cached(&file).unwrap());
+
+        fs::write(&file, "1\n2").unwrap();
+        let err = read_cached(&file).unwrap_err().to_string();
+        assert!(err.contains("is corrupt"), "{err}");
+
+        // Files from before checksums were saved are fine.
+        fs::remove_file(format!("{file}.json")).unwrap();
+        assert_eq!(Some("1\n2".to_string()), read_cached(&file).unwrap());
+        fs::remove_dir_all(dir).unwrap();
+    }
 }
@@ -0,0 +1,354 @@
+use std::ops::Range;
+
+use num::PrimInt;
+
+// Some of the numbers in a type, kept as the ranges they make up.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct IntervalSet<T> {
+    // In order, and with gaps between them, so there's only one way to write
+    // any set.
+    ranges: Vec<Range<T>>,
+}
+
+impl<T: PrimInt> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self { ranges: Vec::new() }
+    }
+
+    fn normalize(mut ranges: Vec<Range<T>>) -> Self {
+        ranges.retain(|r| r.start < r.end);
+        ranges.sort_unstable_by_key(|r| r.start);
+        // Merge in place, so a set that's cleared and refilled never allocates.
+        ranges.dedup_by(|r, last| {
+            let overlaps = r.start <= last.end;
+            if overlaps {
+                last.end = last.end.max(r.end);
+            }
+            overlaps
+        });
+        Self { ranges }
+    }
+
+    pub fn ranges(&self) -> impl DoubleEndedIterator<Item = Range<T>> + '_ {
+        self.ranges.iter().cloned()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    // How many numbers are in the set.
+    pub fn len(&self) -> T {
+        self.ranges
+            .iter()
+            .fold(T::zero(), |len, r| len + (r.end - r.start))
+    }
+
+    pub fn first(&self) -> Option<T> {
+        self.ranges.first().map(|r| r.start)
+    }
+
+    #[allow(dead_code)]
+    pub fn contains(&self, n: T) -> bool {
+        match self.ranges.binary_search_by(|r| r.start.cmp(&n)) {
+            Ok(_) => true,
+            Err(0) => false,
+            Err(i) => n < self.ranges[i - 1].end,
+        }
+    }
+
+    pub fn insert(&mut self, r: Range<T>) {
+        let mut ranges = std::mem::take(&mut self.ranges);
+        ranges.push(r);
+        *self = Self::normalize(ranges);
+    }
+
+    pub fn remove(&mut self, r: Range<T>) {
+        if r.start >= r.end {
+            return;
+        }
+        // The ranges that overlap `r` are all together, and only their ends
+        // can be left.
+        let from = self.ranges.partition_point(|x| x.end <= r.start);
+        let to = self.ranges.partition_point(|x| x.start < r.end);
+        if from == to {
+            return;
+        }
+        let (start, end) = (self.ranges[from].start, self.ranges[to - 1].end);
+        self.ranges.drain(from..to);
+        if r.end < end {
+            self.ranges.insert(from, r.end..end);
+        }
+        if start < r.start {
+            self.ranges.insert(from, start..r.start);
+        }
+    }
+
+    // Empties the set, but keeps its room for the next ranges.
+    pub fn clear(&mut self) {
+        self.ranges.clear();
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        Self::normalize(self.ranges().chain(other.ranges()).collect())
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let (a, b) = (&self.ranges, &other.ranges);
+        let (mut i, mut j) = (0, 0);
+        let mut ranges = Vec::new();
+        while i < a.len() && j < b.len() {
+            let (start, end) = (a[i].start.max(b[j].start), a[i].end.min(b[j].end));
+            if start < end {
+                ranges.push(start..end);
+            }
+            // Whichever finishes first can't overlap anything else.
+            if a[i].end < b[j].end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        Self { ranges }
+    }
+
+    // What's in this set but not the other.
+    pub fn difference(&self, other: &Self) -> Self {
+        match (self.ranges.first(), self.ranges.last()) {
+            (Some(first), Some(last)) => {
+                self.intersection(&other.complement(first.start..last.end))
+            }
+            _ => Self::new(),
+        }
+    }
+
+    // What's in `bounds` but not in the set.
+    pub fn complement(&self, bounds: Range<T>) -> Self {
+        let mut ranges = Vec::new();
+        let mut at = bounds.start;
+        for r in &self.ranges {
+            if r.start >= bounds.end {
+                break;
+            }
+            if r.start > at {
+                ranges.push(at..r.start);
+            }
+            at = at.max(r.end);
+        }
+        if at < bounds.end {
+            ranges.push(at..bounds.end);
+        }
+        Self { ranges }
+    }
+}
+
+impl<T: PrimInt> From<Range<T>> for IntervalSet<T> {
+    fn from(r: Range<T>) -> Self {
+        Self::normalize(vec![r])
+    }
+}
+
+impl<T: PrimInt> FromIterator<Range<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        Self::normalize(iter.into_iter().collect())
+    }
+}
+
+// A range along each of N dimensions: a rectangle, a cuboid, and so on.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Box<T, const N: usize> {
+    sides: [Range<T>; N],
+}
+
+impl<T: PrimInt, const N: usize> Box<T, N> {
+    pub fn new(sides: [Range<T>; N]) -> Self {
+        Self { sides }
+    }
+
+    pub fn sides(&self) -> &[Range<T>; N] {
+        &self.sides
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sides.iter().any(|r| r.start >= r.end)
+    }
+
+    // How many points are inside.
+    pub fn volume(&self) -> T {
+        if self.is_empty() {
+            return T::zero();
+        }
+        self.sides
+            .iter()
+            .fold(T::one(), |volume, r| volume * (r.end - r.start))
+    }
+
+    #[allow(dead_code)]
+    pub fn contains(&self, point: [T; N]) -> bool {
+        self.sides.iter().zip(point).all(|(r, n)| r.contains(&n))
+    }
+
+    #[allow(dead_code)]
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let sides = std::array::from_fn(|d| {
+            let (a, b) = (&self.sides[d], &other.sides[d]);
+            a.start.max(b.start)..a.end.min(b.end)
+        });
+        Some(Self { sides }).filter(|b| !b.is_empty())
+    }
+
+    // Cuts across dimension `dim`: the part below `at`, and the part from
+    // `at` up. Either is None if there's nothing in it.
+    pub fn split(&self, dim: usize, at: T) -> (Option<Self>, Option<Self>) {
+        let side = &self.sides[dim];
+        let at = at.clamp(side.start, side.end.max(side.start));
+        let part = |side: Range<T>| {
+            let mut part = self.clone();
+            part.sides[dim] = side;
+            Some(part).filter(|p| !p.is_empty())
+        };
+        (part(side.start..at), part(at..side.end))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::ops::Range;
+
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::{Box, IntervalSet};
+
+    // Sets of numbers under 32, checked against the bits of a u32.
+    const MAX: u32 = 32;
+
+    fn bits(set: &IntervalSet<u32>) -> u32 {
+        (0..MAX).filter(|&n| set.contains(n)).map(|n| 1 << n).sum()
+    }
+
+    fn random_range(rng: &mut StdRng, max: u32) -> Range<u32> {
+        let (a, b) = (rng.gen_range(0..=max), rng.gen_range(0..=max));
+        a.min(b)..a.max(b)
+    }
+
+    fn random_set(rng: &mut StdRng) -> (IntervalSet<u32>, u32) {
+        let ranges: Vec<_> = (0..rng.gen_range(0..4))
+            .map(|_| random_range(rng, MAX))
+            .collect();
+        let expected = ranges
+            .iter()
+            .flat_map(|r| r.clone())
+            .map(|n| 1 << n)
+            .fold(0, |a, b| a | b);
+        (ranges.into_iter().collect(), expected)
+    }
+
+    #[test]
+    fn sets() {
+        let set: IntervalSet<i64> = [5..8, -2..1, 1..3, 7..7, 6..10].into_iter().collect();
+        assert_eq!(vec![-2..3, 5..10], set.ranges().collect::<Vec<_>>());
+        assert_eq!(10, set.len());
+        assert_eq!(Some(-2), set.first());
+        assert!(set.contains(-2) && set.contains(2) && !set.contains(3) && !set.contains(10));
+        assert_eq!(
+            vec![-5..-2, 3..5, 10..12],
+            set.complement(-5..12).ranges().collect::<Vec<_>>()
+        );
+        let mut set = set;
+        set.insert(3..5);
+        assert_eq!(IntervalSet::from(-2..10), set);
+        assert!(IntervalSet::<u8>::from(3..3).is_empty());
+    }
+
+    #[test]
+    fn sets_match_brute_force() {
+        let mut rng = StdRng::seed_from_u64(2022);
+        for _ in 0..2000 {
+            let (a, a_bits) = random_set(&mut rng);
+            let (b, b_bits) = random_set(&mut rng);
+            assert_eq!(a_bits, bits(&a), "{a:?}");
+            assert_eq!(a_bits.count_ones(), a.len(), "{a:?}");
+            assert_eq!(
+                (a_bits != 0).then(|| a_bits.trailing_zeros()),
+                a.first(),
+                "{a:?}"
+            );
+            assert_eq!(a_bits | b_bits, bits(&a.union(&b)), "{a:?} | {b:?}");
+            assert_eq!(a_bits & b_bits, bits(&a.intersection(&b)), "{a:?} & {b:?}");
+            assert_eq!(a_bits & !b_bits, bits(&a.difference(&b)), "{a:?} - {b:?}");
+            let bounds = random_range(&mut rng, MAX);
+            let in_bounds = bounds.clone().map(|n| 1 << n).sum::<u32>();
+            assert_eq!(
+                in_bounds & !a_bits,
+                bits(&a.complement(bounds.clone())),
+                "{bounds:?} - {a:?}"
+            );
+            // There's only one way to write each set.
+            let c: IntervalSet<_> = a.ranges().rev().chain(b.ranges()).collect();
+            assert_eq!(a.union(&b), c);
+            let mut d = a.clone();
+            d.remove(bounds.clone());
+            assert_eq!(a_bits & !in_bounds, bits(&d), "{a:?} - {bounds:?}");
+            d.clear();
+            assert_eq!(IntervalSet::new(), d);
+        }
+    }
+
+    fn points(b: &Box<u32, 3>) -> Vec<[u32; 3]> {
+        let mut points = Vec::new();
+        for x in 0..8 {
+            for y in 0..8 {
+                for z in 0..8 {
+                    if b.contains([x, y, z]) {
+                        points.push([x, y, z]);
+                    }
+                }
+            }
+        }
+        points
+    }
+
+    fn random_box(rng: &mut StdRng) -> Box<u32, 3> {
+        Box::new(std::array::from_fn(|_| random_range(rng, 8)))
+    }
+
+    #[test]
+    fn boxes() {
+        let b = Box::new([1..4001, 1..4001, 1..4001, 1..4001]);
+        assert_eq!(256_000_000_000_000u64, b.volume());
+        let (lower, upper) = b.split(2, 2006);
+        assert_eq!(Some(Box::new([1..4001, 1..4001, 1..2006, 1..4001])), lower);
+        assert_eq!(
+            b.volume(),
+            lower.unwrap().volume() + upper.unwrap().volume()
+        );
+        assert_eq!((None, Some(b.clone())), b.split(0, 1));
+        assert_eq!((Some(b.clone()), None), b.split(3, 5000));
+        assert_eq!(0, Box::new([1..3, 2..2]).volume());
+    }
+
+    #[test]
+    fn boxes_match_brute_force() {
+        let mut rng = StdRng::seed_from_u64(2023);
+        for _ in 0..500 {
+            let (a, b) = (random_box(&mut rng), random_box(&mut rng));
+            let inside = points(&a);
+            assert_eq!(inside.len() as u32, a.volume(), "{a:?}");
+            assert_eq!(inside.is_empty(), a.is_empty(), "{a:?}");
+
+            let both: Vec<_> = inside.iter().filter(|&&p| b.contains(p)).copied().collect();
+            let overlap = a.intersection(&b);
+            assert_eq!(both, overlap.as_ref().map(points).unwrap_or_default());
+
+            let (dim, at) = (rng.gen_range(0..3), rng.gen_range(0..=8));
+            let (below, above) = a.split(dim, at);
+            let below = below.as_ref().map(points).unwrap_or_default();
+            let above = above.as_ref().map(points).unwrap_or_default();
+            assert!(below.iter().all(|p| p[dim] < at), "{a:?} at {dim}={at}");
+            assert!(above.iter().all(|p| p[dim] >= at), "{a:?} at {dim}={at}");
+            let mut parts = [below, above].concat();
+            parts.sort();
+            assert_eq!(inside, parts, "{a:?} at {dim}={at}");
+        }
+    }
+}
@@ -8,173 +8,798 @@
 // - 'cargo run --year Y [--day D] [--part 1|2]' - run all or part of a year.
 //    needed.
 // - 'cargo run --all' - run everything.
+// - 'cargo run 2022/20-25 2023/1,5,12/2' - run some days. See filter.rs.
+// - 'cargo run --all @orig' or 'cargo run 2023 !slow' - pick by label, or leave some out.
 // - 'cargo run --set-token TOKEN' - stash my auth token.
+// - 'cargo run --confirm' - save the results as known-good answers.
+// - 'cargo run --check' - fail if any result differs from a known-good answer.
+// - 'cargo run 2023/5/2 --submit' - send the answer to adventofcode.com.
+// - 'cargo run --all --jobs 8' - run solvers in parallel.
+// - 'cargo run 2023/5 --input FILE' - run with some other input. '-' is stdin.
+// - 'cargo run 2023/5 --fetch-examples' - save the examples from the puzzle description.
+// - 'cargo run 2023/5 --example 1' - run with a saved example.
+// - 'cargo run --all --cross-check [--bench 10]' - compare the solvers for each part.
+// - 'cargo run --features mem -- --all --mem' - count what each solver allocates.
+// - 'cargo run 2022/14 --visualize' - show the solver's work.
+// - 'cargo run 2022/14 --vis-fps 30' - animate it in the terminal.
+// - 'cargo run 2022/14 --vis-dir DIR' - save each frame to a file.
+// - 'cargo run 2022/14 --vis-out sand.gif [--vis-scale 4 --vis-skip 10]' - draw it as a GIF (or PNG).
+// - 'cargo run --all --timeout 30' - report solvers that take too long.
+// - 'cargo run --all --format json|csv|junit' - print results for other tools.
+// - 'cargo run --bench 10 [--save-baseline]' - time solvers, compare with the saved baseline.
+// - 'cargo run read [2023/5] [--markdown]' - show the puzzle description.
+// - 'cargo run --profile alice --set-token TOKEN' - stash someone else's token.
+// - 'cargo run --profile alice' - use their token, inputs and answers. AOC_SESSION overrides the default profile's token.
+// - 'cargo run --all-profiles' - run with everyone's inputs.
+// - 'cargo run whoami [--profile alice]' - check the token.
+// - 'cargo run --list [2023]' - show which solvers there are.
+// - 'cargo run calendar [2023]' - show the stars, and what's left to write.
+// - 'cargo run --offline' - fail instead of downloading anything.
+// - 'cargo run --wait' - wait for the next puzzle, then get the input and run everything.
 //
-// https://github.com/gobanos/aoc-runner-derive/blob/master/src/lib.rs if attr is useful.
+// Solvers register themselves with #[aoc(year = Y, day = D, part = P)].
 
+mod answer;
+mod answers;
+mod bench;
+mod calendar;
+mod crosscheck;
 mod curday;
+mod cycle;
+mod examples;
+mod execute;
+mod filter;
+mod grid;
+mod http;
 mod input;
+mod interval;
+mod mem;
+mod output;
+mod pool;
+mod profile;
+mod puzzle;
 mod registry;
+mod search;
 mod solutionset;
 mod solver;
+mod submit;
 mod token;
+mod vis;
+mod wait;
+mod whoami;
 mod years;
 
 #[cfg(test)]
 mod test;
 
-use std::{collections::BTreeSet, fmt::Display};
+use std::{
+    collections::{hash_map::Entry, BTreeMap, BTreeSet, HashMap},
+    io::IsTerminal,
+    path::PathBuf,
+    sync::Arc,
+    time::Duration,
+};
 
+use answer::IntoAnswer;
+use answers::{get_answer, get_submissions, record_submission, save_answer, Check};
 use anyhow::bail;
-use chrono::Datelike;
+use bench::Baseline;
+use chrono::{DateTime, Datelike, FixedOffset};
 use clap::Parser;
-use curday::aoc_now;
-use input::get_input;
-use solutionset::SolutionSet;
+use crosscheck::CrossCheck;
+use curday::{aoc_now, next_puzzle, unlock_time};
+use examples::{fetch_examples, load_examples};
+use execute::{execute, Failure, Run};
+use filter::{only, Filter};
+use input::{describe_input, example_file_for, get_example, get_input, read_input};
+use output::{write_records, Format, Record, Status};
+use profile::Profile;
+use solutionset::{Flags, SolutionSet};
 use solver::Solver;
-use token::{get_token, set_token};
+use submit::{already_tried, submit_answer, Outcome};
+use token::{env_token, get_token, set_token, token_problems};
+use vis::Visualizer;
 
 #[macro_use]
 extern crate advent_of_code_registry;
 
 fn main() {
-    let cli = Cli::parse();
-    if let Err(e) = match cli.set_token {
-        Some(token) => set_token(token),
-        None => do_run(cli),
+    let mut cli = Cli::parse();
+    http::http().set_offline(cli.offline);
+    if let Err(e) = match (cli.set_token.take(), cli.command.take()) {
+        (Some(token), _) => cli.profile().and_then(|p| set_token(&p, token)),
+        (
+            None,
+            Some(Command::Read {
+                filter,
+                markdown,
+                refresh,
+            }),
+        ) => cli
+            .profile()
+            .and_then(|p| read(&p, filter.as_deref(), markdown, refresh)),
+        (None, Some(Command::Whoami)) => cli.profile().and_then(|p| whoami(&p)),
+        (None, Some(Command::Calendar { filter })) => {
+            cli.profile().and_then(|p| calendar(&p, &filter))
+        }
+        (None, None) => do_run(cli),
     } {
         eprintln!("error: {e}");
+        std::process::exit(1);
+    }
+}
+
+fn read(
+    profile: &Profile,
+    filter: Option<&str>,
+    markdown: bool,
+    refresh: bool,
+) -> anyhow::Result<()> {
+    let now = aoc_now();
+    let (year, day, part) = match filter {
+        Some(filter) => parse_filter(filter, false)?,
+        None => (None, None, None),
+    };
+    let year = year.unwrap_or(now.year());
+    let day = match day {
+        Some(day) => day,
+        None if year == now.year() && now.month() == 12 && now.day() <= 25 => now.day(),
+        None => bail!("which day? like 'read {year}/1'"),
+    };
+    if is_future(&now, year, day) {
+        bail!("{year}/{day} isn't unlocked yet");
+    }
+    let html = puzzle::get_page(http::BASE_URL, || get_token(profile), year, day, refresh)?;
+    let articles = puzzle::articles(&html);
+    if articles.is_empty() {
+        bail!("no puzzle description in the page for {year}/{day}");
+    }
+    let articles = match part {
+        None => articles,
+        Some(part) => match articles.get(part as usize - 1) {
+            Some(a) => vec![*a],
+            None => bail!("part {part} of {year}/{day} isn't unlocked yet"),
+        },
+    };
+    let style = if markdown {
+        puzzle::Style::Markdown
+    } else if std::io::stdout().is_terminal() {
+        puzzle::Style::Terminal
+    } else {
+        puzzle::Style::Plain
+    };
+    for (i, article) in articles.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        print!("{}", puzzle::render(article, style, http::BASE_URL));
     }
+    Ok(())
+}
+
+fn whoami(profile: &Profile) -> anyhow::Result<()> {
+    let token = get_token(profile)?;
+    if env_token(profile).is_some() {
+        println!("using the token from AOC_SESSION");
+    }
+    for problem in token_problems(&token) {
+        println!("warning: the token {problem}");
+    }
+    let now = aoc_now();
+    let year = match now.month() {
+        12 => now.year(),
+        _ => now.year() - 1,
+    };
+    let user = whoami::whoami(http::BASE_URL, &token, year)?;
+    println!("{profile}: logged in as {user}");
+    if let Some(stars) = user.stars {
+        println!("{stars}* in {year}");
+    }
+    Ok(())
+}
+
+fn calendar(profile: &Profile, filter: &[String]) -> anyhow::Result<()> {
+    let mut runner = Runner::new();
+    registry::register(&mut runner);
+    let filter = Filter::parse(filter)?;
+    let solvers: Vec<&Solver> = runner
+        .solvers
+        .iter()
+        .filter(|s| filter.matches(s))
+        .collect();
+    let years: BTreeSet<i32> = solvers.iter().map(|s| s.year).collect();
+    if years.is_empty() {
+        bail!("no solvers match");
+    }
+
+    // A part is done once any of its solvers isn't a placeholder.
+    let mut progress = HashMap::new();
+    for solver in &solvers {
+        let p = progress
+            .entry((solver.year, solver.day, solver.part))
+            .or_insert(calendar::Progress::Todo);
+        if !solver.unsolved {
+            *p = calendar::Progress::Done;
+        }
+    }
+
+    for (i, year) in years.into_iter().enumerate() {
+        let mut parts = BTreeMap::new();
+        for day in 1..=25 {
+            for part in 1..=2 {
+                let solved = get_answer(profile, year, day, part)?.is_some()
+                    || get_submissions(profile, year, day, part)?
+                        .iter()
+                        .any(|(_, outcome)| *outcome == Outcome::Correct);
+                let solver = progress
+                    .get(&(year, day, part))
+                    .copied()
+                    .unwrap_or_default();
+                parts.insert((day, part), calendar::Part { solved, solver });
+            }
+        }
+        if i > 0 {
+            println!();
+        }
+        print!("{}", calendar::render(year, &parts));
+    }
+    Ok(())
 }
 
 fn do_run(cli: Cli) -> anyhow::Result<()> {
-    let mut runner = Runner::new(cli.visualize);
-    /*
-     * TODO:
-     * register here like:
-     *   register![
-     *     y2021::day1part1,
-     *     y2021::day1part2,
-     *     y2021::day2part1,
-     *     y2021::day2part2,
-     *     ...
-     *   ]
-     * even better would be to not need to list everything, let register! scan all the modules.
-     *
-     * annotate days like:
-     *   #[aoc(year = 2021, day = 1, part = 1, label = "optional extra string")]
-     *   pub fn day1part1(...
-     */
-    years::register(&mut runner);
+    let mut runner = Runner::new();
+    registry::register(&mut runner);
     runner.run(cli)
 }
 
 struct Runner {
-    visualize: bool,
     solvers: BTreeSet<Solver>,
 }
 
 impl Runner {
-    fn new(visualize: bool) -> Self {
+    fn new() -> Self {
         Self {
-            visualize,
             solvers: BTreeSet::new(),
         }
     }
 
+    fn insert(&mut self, solver: Solver) {
+        let (year, day, part, label) = (solver.year, solver.day, solver.part, solver.label);
+        if !self.solvers.insert(solver) {
+            panic!("{year}: Dec {day:02}: part {part}: more than one solver with label {label:?}");
+        }
+    }
+
     fn run(self, mut cli: Cli) -> anyhow::Result<()> {
-        let token = get_token()?;
-        let now = aoc_now();
-        cli.set_today(&now);
+        if cli.bench.is_some() && cli.jobs > 1 {
+            bail!("--bench can't be used with --jobs, the timings would be meaningless");
+        }
+        if cli.mem && !mem::ENABLED {
+            bail!("--mem needs the counting allocator, build with --features mem");
+        }
+        // Without --vis-dir or --vis-out, the work is drawn on stdout, where it
+        // would get mixed in with the records.
+        let vis_on_stdout = cli.vis_dir.is_none() && cli.vis_out.is_none();
+        let vis = cli.visualize || cli.vis_fps.is_some();
+        if vis && vis_on_stdout && cli.format != Format::Text {
+            bail!("--format needs the solvers' work to go to --vis-dir or --vis-out");
+        }
+        let mut now = aoc_now();
+        if cli.wait && cli.filter.is_empty() && cli.year.is_none() && cli.day.is_none() {
+            let (year, day) = next_puzzle(&now);
+            (cli.year, cli.day) = (Some(year), Some(day));
+        }
+        cli.set_today(&now)?;
+        if cli.list {
+            return self.list(&cli);
+        }
+        // Known-good answers, the input cache and the token are only for the real input.
+        let custom_input = cli.input.is_some() || cli.example.is_some();
+        let profiles = match cli.all_profiles {
+            true => token::profiles()?,
+            false => vec![cli.profile()?],
+        };
+        if profiles.is_empty() {
+            bail!("no profiles have tokens, set one with --set-token");
+        }
+        let multi = profiles.len() > 1;
+        let tokens = profiles
+            .iter()
+            .map(|p| match custom_input {
+                true => Ok(String::new()),
+                false => get_token(p),
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let (profile, token) = (&profiles[0], &tokens[0]);
+        if cli.fetch_examples {
+            return self.fetch_examples(&cli, &now, token);
+        }
+        if cli.wait {
+            let Some((year, day)) = cli.selection.single_day() else {
+                bail!("--wait needs a single day, like 2023/5");
+            };
+            if !self.solvers.iter().any(|s| cli.matches(s)) {
+                bail!("--wait: there aren't any solvers for {year}/{day} yet");
+            }
+            wait::wait_for_unlock(unlock_time(year, day)?, &format!("{year}: Dec {day:02}"));
+            now = aoc_now();
+            // Downloads are already retried when the server is busy.
+            get_input(profile, year, day, token)?;
+            if let Err(e) = fetch_examples(http::BASE_URL, token, year, day) {
+                eprintln!("{year}: Dec {day:02}: no examples: {e:#}");
+            }
+        }
 
-        fn res(cli: &Cli, res: Box<dyn Display>) -> Box<dyn Display> {
+        fn res<'a>(cli: &Cli, res: &'a str) -> &'a str {
             if cli.no_spoilers {
-                Box::new("(result hidden)")
+                "(result hidden)"
             } else {
                 res
             }
         }
 
+        // With structured output, stdout only gets the records.
+        let say = |s: String| match cli.format {
+            Format::Text => println!("{s}"),
+            _ => eprintln!("{s}"),
+        };
+
+        if cli.wait {
+            self.check_examples(&cli, &say)?;
+        }
+
+        let input_override = match &cli.input {
+            Some(path) => {
+                // One input is only for one puzzle.
+                if cli.selection.single_day().is_none() {
+                    bail!("--input needs a single day, like 2023/5");
+                }
+                say(format!("using input from {}", describe_input(path)));
+                Some(read_input(path)?)
+            }
+            None => None,
+        };
+        let mut inputs = HashMap::new();
+        let mut planned = Vec::new();
+        for solver in &self.solvers {
+            let (year, day) = (solver.year, solver.day);
+            if !cli.matches(solver) {
+                continue;
+            }
+            for (p, profile) in profiles.iter().enumerate() {
+                if is_future(&now, year, day) {
+                    planned.push((solver, p, Plan::Future));
+                } else if solver.slow && !cli.run_slow_parts() {
+                    planned.push((solver, p, Plan::Slow));
+                } else {
+                    if let Entry::Vacant(e) = inputs.entry((p, year, day)) {
+                        e.insert(match (&input_override, cli.example) {
+                            (Some(input), _) => input.clone(),
+                            (None, Some(n)) => {
+                                say(format!(
+                                    "{year}: Dec {day:02}: using example {n} from {}",
+                                    example_file_for(year, day, n)
+                                ));
+                                get_example(year, day, n)?
+                            }
+                            (None, None) => get_input(profile, year, day, &tokens[p])?,
+                        });
+                    }
+                    planned.push((solver, p, Plan::Run));
+                }
+            }
+        }
+
         let mut total_time = std::time::Duration::ZERO;
         let mut count = 0;
-        for solver in self.solvers {
+        let mut mismatches = 0;
+        let mut results = Vec::new();
+        let mut failures = Vec::new();
+        let mut records = Vec::new();
+        // With more than one profile, each solver's answers are printed on one line.
+        let mut cells = Vec::new();
+        let baseline = match cli.bench {
+            Some(_) => Baseline::load(&cli.baseline)?.unwrap_or_default(),
+            None => Baseline::default(),
+        };
+        let mut new_baseline = Baseline::default();
+        let mut cross_check = CrossCheck::default();
+        // With --confirm, every solver's answer for each part, to check that
+        // they agree before saving one.
+        let mut confirms: BTreeMap<(usize, i32, u32, u8), Vec<String>> = BTreeMap::new();
+        let shared_vis = planned
+            .iter()
+            .filter(|(_, _, plan)| matches!(plan, Plan::Run))
+            .count()
+            > 1;
+        let work = |(solver, p, plan): &(&Solver, usize, Plan)| match plan {
+            Plan::Run => Some(
+                cli.visualizer(solver, multi.then_some(&profiles[*p]), shared_vis)
+                    .map_err(Failure::Error)
+                    .and_then(|vis| {
+                        execute(
+                            solver,
+                            &inputs[&(*p, solver.year, solver.day)],
+                            vis,
+                            cli.bench,
+                            cli.mem,
+                            cli.timeout,
+                        )
+                    }),
+            ),
+            Plan::Future | Plan::Slow => None,
+        };
+        pool::run_ordered(cli.jobs, &planned, work, |(solver, p, plan), run| {
             let Solver {
                 year,
                 day,
                 part,
                 label,
-                f,
                 slow,
-            } = solver;
-            if cli.matches(year, day, part) {
-                if is_future(&now, year, day) {
-                    println!("{year}: Dec {day:02}: part {part}: (future)");
-                    continue;
+                ..
+            } = **solver;
+            let label_suffix = match label {
+                Some(s) => format!(" ({})", s),
+                None => "".to_string(),
+            };
+            let profile = &profiles[*p];
+            let mut record = Record::new(year, day, part, label, slow);
+            if multi {
+                record.profile = Some(profile.to_string());
+            }
+            let mut emit = |cell: String| {
+                cells.push(match multi {
+                    true => format!("{profile}: {cell}"),
+                    false => cell,
+                });
+                if *p + 1 == profiles.len() {
+                    say(format!(
+                        "{year}: Dec {day:02}: part {part}: {}{label_suffix}",
+                        cells.join(" | ")
+                    ));
+                    cells.clear();
                 }
-                if slow && !cli.run_slow_parts() {
-                    println!("{year}: Dec {day:02}: part {part}: (skipped because it's slow)");
-                    continue;
+            };
+            let run = match (plan, run) {
+                (_, Some(Ok(run))) => run,
+                (_, Some(Err(failure))) => {
+                    emit(failure.to_string());
+                    let who = match multi {
+                        true => format!(" [{profile}]"),
+                        false => "".to_string(),
+                    };
+                    failures.push(format!(
+                        "{year}: Dec {day:02}: part {part}{label_suffix}{who}: {failure}"
+                    ));
+                    cross_check.add(
+                        (year, day, part),
+                        record.profile.clone(),
+                        label,
+                        Err(failure.to_string()),
+                        Duration::ZERO,
+                    );
+                    record.status = Status::Failed;
+                    record.error = Some(failure.to_string());
+                    records.push(record);
+                    return Ok(());
                 }
-                count += 1;
-                let input = get_input(year, day, &token)?;
-                let now = std::time::Instant::now();
-                let result = f(input, self.visualize);
-                let elapsed = now.elapsed();
-                total_time += elapsed;
-                println!(
-                    "{}: Dec {:02}: part {}: {} ({:.2?}){}",
-                    year,
-                    day,
-                    part,
-                    res(&cli, result),
-                    elapsed,
-                    match label {
-                        Some(s) => format!(" ({})", s),
-                        None => "".to_string(),
-                    },
+                (Plan::Future, None) => {
+                    emit("(future)".to_string());
+                    record.status = Status::Future;
+                    records.push(record);
+                    return Ok(());
+                }
+                (_, None) => {
+                    emit("(skipped because it's slow)".to_string());
+                    record.status = Status::Skipped;
+                    records.push(record);
+                    return Ok(());
+                }
+            };
+            let Run {
+                answer,
+                elapsed,
+                stats,
+                mem,
+                vis_error,
+            } = run;
+            let result = answer.to_string();
+            count += 1;
+            total_time += elapsed;
+            let mut check = match (custom_input, cli.example) {
+                (false, _) => Some(Check::new(get_answer(profile, year, day, part)?, &result)),
+                (true, Some(n)) => Some(Check::new(example_answer(year, day, n, part)?, &result)),
+                (true, None) => None,
+            };
+            if answer.is_unsolved() {
+                record.status = Status::Unsolved;
+            } else if cli.confirm {
+                confirms
+                    .entry((*p, year, day, part))
+                    .or_default()
+                    .push(result.clone());
+            }
+            // Confirmed answers replace the known-good ones, so they aren't mismatches.
+            if !cli.confirm && check.as_ref().is_some_and(Check::is_mismatch) {
+                mismatches += 1;
+            }
+            record.check = check.as_ref().map(Check::name);
+            if cli.no_spoilers {
+                check = check.map(Check::hide_expected);
+            } else {
+                record.answer = Some(result.clone());
+            }
+            emit(format!(
+                "{}{} ({:.2?}){}",
+                res(&cli, &result),
+                match check {
+                    Some(c) => format!(" {c}"),
+                    None => "".to_string(),
+                },
+                elapsed,
+                match mem {
+                    Some(usage) => format!(" [{usage}]"),
+                    None => "".to_string(),
+                },
+            ));
+            record.mem = mem;
+            if let Some(e) = vis_error {
+                eprintln!("{year}: Dec {day:02}: part {part}{label_suffix}: {e:#}");
+            }
+            if !answer.is_unsolved() {
+                cross_check.add(
+                    (year, day, part),
+                    record.profile.clone(),
+                    label,
+                    Ok(result.clone()),
+                    stats.map_or(elapsed, |s| s.median),
                 );
             }
+            if let Some(stats) = stats {
+                match baseline.get(year, day, part, label) {
+                    Some(base) => say(format!(
+                        "    {stats} ({:+.1}% vs baseline median {:.2?})",
+                        stats.change_from(base),
+                        base.median
+                    )),
+                    None => say(format!("    {stats}")),
+                }
+                new_baseline.insert(year, day, part, label, stats);
+            }
+            record.bench = stats;
+            records.push(record.with_duration(elapsed));
+            if !answer.is_unsolved() {
+                results.push((year, day, part, result));
+            }
+            Ok(())
+        })?;
+        if cli.format != Format::Text {
+            write_records(cli.format, &records, &mut std::io::stdout().lock())?;
         }
-        if count == 0 {
-            println!("No matches found! {:?}", cli);
-        } else {
+        let mut unconfirmed = 0;
+        for ((p, year, day, part), answers) in &confirms {
+            let who = match multi {
+                true => format!(" [{}]", profiles[*p]),
+                false => "".to_string(),
+            };
+            if answers.iter().all(|a| a == &answers[0]) {
+                save_answer(&profiles[*p], *year, *day, *part, &answers[0])?;
+            } else {
+                say(format!(
+                    "{year}: Dec {day:02}: part {part}{who}: not confirmed, the solvers disagree"
+                ));
+                unconfirmed += 1;
+            }
+        }
+        if cli.save_baseline {
+            // Keep the solvers that weren't run this time.
+            let mut baseline = baseline;
+            baseline.merge(new_baseline);
+            baseline.save(&cli.baseline)?;
+            say(format!("wrote baseline to {:?}", cli.baseline));
+        }
+        if count == 0 && failures.is_empty() {
+            say(format!("No matches found! {:?}", cli));
+        } else if count > 0 {
             let avg = total_time / count;
-            println!("total time: {total_time:.2?} / avg: {avg:.2?}");
+            say(format!("total time: {total_time:.2?} / avg: {avg:.2?}"));
+        }
+        let mut disagreements = 0;
+        if cli.cross_check {
+            let report = cross_check.report();
+            say("cross-check, fastest first:".to_string());
+            for line in &report.lines {
+                say(format!("  {line}"));
+            }
+            disagreements = report.disagreements;
+        }
+        if cli.submit {
+            submit(profile, token, &results, &say)?;
+        }
+        if mismatches > 0 {
+            say(format!(
+                "{mismatches} answer(s) did not match the known-good answers"
+            ));
+            if cli.check {
+                bail!("--check failed");
+            }
+        }
+        if unconfirmed > 0 {
+            bail!("{unconfirmed} part(s) weren't confirmed because their solvers disagree");
+        }
+        if disagreements > 0 {
+            say(format!(
+                "{disagreements} part(s) have solvers that disagree"
+            ));
+            if cli.check {
+                bail!("--check failed");
+            }
+        }
+        if !failures.is_empty() {
+            say(format!("{} solver(s) failed:", failures.len()));
+            for f in &failures {
+                say(format!("  {f}"));
+            }
+            bail!("{} solver(s) failed", failures.len());
+        }
+        Ok(())
+    }
+
+    fn list(&self, cli: &Cli) -> anyhow::Result<()> {
+        let mut count = 0;
+        for solver in self.solvers.iter().filter(|s| cli.matches(s)) {
+            let (year, day, part) = (solver.year, solver.day, solver.part);
+            let label_suffix = match solver.label {
+                Some(s) => format!(" ({})", s),
+                None => "".to_string(),
+            };
+            let slow = if solver.slow { " [slow]" } else { "" };
+            println!("{year}: Dec {day:02}: part {part}{label_suffix}{slow}");
+            count += 1;
+        }
+        if count == 0 {
+            bail!("No matches found! {:?}", cli);
+        }
+        Ok(())
+    }
+
+    // Runs the selected solvers on the saved examples that have answers.
+    fn check_examples(&self, cli: &Cli, say: &dyn Fn(String)) -> anyhow::Result<()> {
+        for solver in &self.solvers {
+            let (year, day, part) = (solver.year, solver.day, solver.part);
+            if !cli.matches(solver) {
+                continue;
+            }
+            let label_suffix = match solver.label {
+                Some(s) => format!(" ({})", s),
+                None => "".to_string(),
+            };
+            for (i, example) in load_examples(year, day)?.iter().enumerate() {
+                let Some(expected) = example.meta.expected(part) else {
+                    continue;
+                };
+                let timeout = cli.timeout;
+                let res = match execute(
+                    solver,
+                    &example.input,
+                    Visualizer::off(),
+                    None,
+                    false,
+                    timeout,
+                ) {
+                    Ok(run) if run.answer.is_unsolved() => "todo".to_string(),
+                    Ok(run) => {
+                        let answer = run.answer.to_string();
+                        let check = Check::new(Some(expected.to_string()), &answer);
+                        format!("{answer} {check}")
+                    }
+                    Err(failure) => failure.to_string(),
+                };
+                say(format!(
+                    "{year}: Dec {day:02}: part {part}: example {}: {res}{label_suffix}",
+                    i + 1
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn fetch_examples(
+        &self,
+        cli: &Cli,
+        now: &DateTime<FixedOffset>,
+        token: &str,
+    ) -> anyhow::Result<()> {
+        let days: BTreeSet<(i32, u32)> = self
+            .solvers
+            .iter()
+            .filter(|s| cli.matches(s) && !is_future(now, s.year, s.day))
+            .map(|s| (s.year, s.day))
+            .collect();
+        if days.is_empty() {
+            bail!("No matches found! {:?}", cli);
+        }
+        for (year, day) in days {
+            let n = fetch_examples(http::BASE_URL, token, year, day)?;
+            println!("{year}: Dec {day:02}: {n} example(s)");
         }
         Ok(())
     }
 }
 
+// The answer from the puzzle description for a saved example.
+fn example_answer(year: i32, day: u32, n: u32, part: u8) -> anyhow::Result<Option<String>> {
+    let examples = load_examples(year, day)?;
+    Ok(examples
+        .get(n as usize - 1)
+        .and_then(|e| e.meta.expected(part))
+        .map(str::to_string))
+}
+
+enum Plan {
+    Future,
+    Slow,
+    Run,
+}
+
+fn submit(
+    profile: &Profile,
+    token: &str,
+    results: &[(i32, u32, u8, String)],
+    say: &dyn Fn(String),
+) -> anyhow::Result<()> {
+    let Some((year, day, part, answer)) = results.first() else {
+        bail!("nothing to submit");
+    };
+    let (year, day, part) = (*year, *day, *part);
+    if results.iter().any(|r| (r.0, r.1, r.2) != (year, day, part)) {
+        bail!("--submit needs a filter that selects a single part, like 2023/5/2");
+    }
+    if results.iter().any(|r| &r.3 != answer) {
+        bail!("not submitting: the solvers for {year}/{day}/{part} disagree");
+    }
+    if answer.contains('\n') {
+        bail!("not submitting: the answer has more than one line");
+    }
+    if let Some(reason) = already_tried(&get_submissions(profile, year, day, part)?, answer) {
+        bail!("not submitting: {reason}");
+    }
+    let outcome = submit_answer(http::BASE_URL, token, year, day, part, answer)?;
+    say(format!(
+        "{year}: Dec {day:02}: part {part}: submitted {answer}: {outcome}"
+    ));
+    record_submission(profile, year, day, part, answer, &outcome)?;
+    if outcome == Outcome::Correct {
+        save_answer(profile, year, day, part, answer)?;
+    }
+    Ok(())
+}
+
 fn is_future<D: Datelike>(now: &D, year: i32, day: u32) -> bool {
     year > now.year() || (year == now.year() && day > now.day())
 }
 
 impl SolutionSet for Runner {
-    fn add<F>(&mut self, year: i32, day: u32, part: u8, label: Option<&'static str>, f: F)
-    where
-        F: Fn(String, bool) -> Box<dyn std::fmt::Display> + 'static,
-    {
-        self.solvers.insert(Solver {
-            year,
-            day,
-            part,
-            label,
-            f: Box::new(f),
-            slow: false,
-        });
-    }
-
-    fn add_slow<F>(&mut self, year: i32, day: u32, part: u8, label: Option<&'static str>, f: F)
-    where
-        F: Fn(String, bool) -> Box<dyn std::fmt::Display> + 'static,
+    fn add<F, R>(
+        &mut self,
+        year: i32,
+        day: u32,
+        part: u8,
+        label: Option<&'static str>,
+        flags: Flags,
+        f: F,
+    ) where
+        F: Fn(String, &Visualizer) -> R + Send + Sync + 'static,
+        R: IntoAnswer,
     {
-        self.solvers.insert(Solver {
+        self.insert(Solver {
             year,
             day,
             part,
             label,
-            f: Box::new(f),
-            slow: true,
+            f: Arc::new(move |input, vis| f(input, vis).into_answer()),
+            slow: flags.slow,
+            unsolved: flags.unsolved,
         });
     }
 }
@@ -182,10 +807,28 @@ impl SolutionSet for Runner {
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Set your token instead of solving any puzzles. This is the value of the session cookie on adventofcode.com.
     #[arg(long)]
     set_token: Option<String>,
 
+    /// Use this profile's token, inputs and answers.
+    #[arg(long, global = true)]
+    profile: Option<String>,
+
+    /// Run with the inputs of every profile that has a token, and show the answers side by side.
+    #[arg(
+        long,
+        conflicts_with_all = ["profile", "input", "example", "submit", "bench", "wait", "fetch_examples"]
+    )]
+    all_profiles: bool,
+
+    /// Only use cached inputs and pages, and fail instead of connecting to adventofcode.com.
+    #[arg(long, global = true)]
+    offline: bool,
+
     /// Run all solvers.
     #[arg(long)]
     all: bool,
@@ -210,146 +853,305 @@ struct Cli {
     #[arg(short, long)]
     part: Option<u8>,
 
-    /// Include visualizations.
+    /// Print what the solvers show of their work.
     #[arg(short, long)]
     visualize: bool,
 
-    /// Run parts that are considered 'slow'.
+    /// Animate the solvers' work in the terminal, at up to FPS frames per second.
+    /// With --vis-out, the speed of the GIF.
+    #[arg(long, value_name = "FPS", conflicts_with = "vis_dir")]
+    vis_fps: Option<f64>,
+
+    /// Save the solvers' work in DIR, with one file per frame.
+    #[arg(long, value_name = "DIR")]
+    vis_dir: Option<PathBuf>,
+
+    /// Draw the solvers' work as an animated GIF, or the last frame as a PNG.
+    #[arg(long, value_name = "FILE", conflicts_with = "vis_dir")]
+    vis_out: Option<PathBuf>,
+
+    /// With --vis-out, draw each cell as an NxN square.
+    #[arg(long, value_name = "N", default_value_t = 4, requires = "vis_out")]
+    vis_scale: usize,
+
+    /// With --vis-out, only keep every Nth frame (and the last).
+    #[arg(long, value_name = "N", default_value_t = 1, requires = "vis_out")]
+    vis_skip: usize,
+
+    /// With --vis-out, colour cells differently, like '#=ffffff,.=000000'.
+    #[arg(long, value_name = "CHAR=RRGGBB,...", requires = "vis_out")]
+    vis_palette: Option<String>,
+
+    /// Run parts that are considered 'slow'. A 'slow' filter term does this too.
     #[arg(long)]
     include_slow: bool,
 
-    /// YYYY or [YYYY/]DD[/PART] to run.
-    filter: Option<String>,
+    /// Save this run's results as the known-good answers.
+    #[arg(long)]
+    confirm: bool,
+
+    /// Exit with an error if any result differs from its known-good answer.
+    #[arg(long)]
+    check: bool,
+
+    /// Count the memory that each solver allocates on its own thread. Needs the `mem` feature.
+    #[arg(long)]
+    mem: bool,
+
+    /// Compare the answers from each part's solvers, and rank them by speed.
+    #[arg(long)]
+    cross_check: bool,
+
+    /// Submit the result to adventofcode.com. The filter must select a single part.
+    #[arg(long)]
+    submit: bool,
+
+    /// Read the input from this file instead of the downloaded input. Use - for stdin. The filter must select a single day.
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["example", "confirm", "submit"])]
+    input: Option<String>,
+
+    /// List the solvers instead of running them. Without a filter, this lists all of them.
+    #[arg(long, conflicts_with_all = ["input", "example", "confirm", "submit", "bench", "fetch_examples", "wait"])]
+    list: bool,
+
+    /// Save the examples and their answers from the puzzle descriptions, instead of running anything.
+    #[arg(long, conflicts_with_all = ["input", "example", "confirm", "submit", "bench"])]
+    fetch_examples: bool,
+
+    /// Wait for the puzzle to unlock, then get the input and run the examples and the solvers. Without a day, this is the next puzzle.
+    #[arg(long, conflicts_with_all = ["all", "input", "example", "fetch_examples", "offline"])]
+    wait: bool,
+
+    /// Use the Nth saved example for the day instead of the downloaded input.
+    #[arg(
+        long,
+        value_name = "N",
+        value_parser = clap::value_parser!(u32).range(1..),
+        conflicts_with_all = ["confirm", "submit"]
+    )]
+    example: Option<u32>,
+
+    /// Time each solver N times, after a warm-up run, and compare with the baseline.
+    #[arg(long, value_name = "N")]
+    bench: Option<u32>,
+
+    /// How to print the results.
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+
+    /// Give up on a solver after this many seconds.
+    #[arg(long, value_name = "SECS", value_parser = parse_seconds)]
+    timeout: Option<Duration>,
+
+    /// Run up to N solvers at the same time.
+    #[arg(short, long, value_name = "N", default_value_t = 1)]
+    jobs: usize,
+
+    /// Baseline file for --bench.
+    #[arg(long, default_value = "bench/baseline.json")]
+    baseline: PathBuf,
+
+    /// Save the --bench results as the new baseline.
+    #[arg(long, requires = "bench")]
+    save_baseline: bool,
+
+    /// What to run: YYYY, [YYYY/]DD[/PART], with ranges and lists like
+    /// 2023/1-5,12/2, @LABEL, or slow. Start one with ! to leave those out.
+    filter: Vec<String>,
+
+    #[arg(skip)]
+    selection: Filter,
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum Command {
+    /// Show the puzzle description.
+    Read {
+        /// [YYYY/]DD[/PART] to show. Defaults to today's puzzle.
+        filter: Option<String>,
+
+        /// Print Markdown instead of styled text.
+        #[arg(long)]
+        markdown: bool,
+
+        /// Download the page again, even if it's cached.
+        #[arg(long)]
+        refresh: bool,
+    },
+
+    /// Check the token, and show who it logs in as.
+    Whoami,
+
+    /// Show the stars for each year, and which parts don't have a solver yet.
+    Calendar {
+        /// Which solvers to include, like the filter for running them.
+        filter: Vec<String>,
+    },
 }
 
 impl Cli {
-    fn set_today<D: Datelike>(&mut self, today: &D) {
-        if let Some(filter) = &self.filter {
+    fn profile(&self) -> anyhow::Result<Profile> {
+        Profile::new(self.profile.as_deref())
+    }
+
+    fn set_today<D: Datelike>(&mut self, today: &D) -> anyhow::Result<()> {
+        let mut selection = if self.filter.is_empty() {
+            Filter {
+                include: vec![filter::Term {
+                    years: self.year.and_then(only),
+                    days: self.day.and_then(only),
+                    parts: self.part.and_then(only),
+                    ..Default::default()
+                }],
+                exclude: vec![],
+            }
+        } else {
             if self.year.is_some() || self.day.is_some() || self.part.is_some() {
-                panic!("arg conflict: SPEC may not be provided when --year or --day or --part are used");
+                bail!("a filter can't be used with --year, --day or --part");
             }
-            let (y, d, p) = parse_filter(filter, false).unwrap();
-            self.year = y;
-            self.day = d;
-            self.part = p;
-        }
+            Filter::parse(&self.filter)?
+        };
 
         let today_year = today.year();
         let today_day = today.day();
-        match (
-            self.year.is_some(),
-            self.day.is_some(),
-            self.this_year,
-            today.month(),
-        ) {
-            // No args, today is a day on the advent calendar => run today only.
-            // No args, today is not on the calendar => run nothing.
-            (false, false, false, _) => {
-                self.year = Some(today_year);
-                self.day = Some(today_day);
-            }
-            // --this-year, today is still December => run today's.
-            (false, false, true, 12) => {
-                self.year = Some(today_year);
-            }
-            // --this-year, today is not December => run last year's.
-            (false, false, true, _) => {
-                self.year = Some(today_year - 1);
-            }
-            // --year Y => run everything from year Y.
-            (true, _, _, _) => (),
-            // --day D, today is December => run this year's day D.
-            (false, true, _, 12) => {
-                self.year = Some(today_year);
+        for term in &mut selection.include {
+            // --all, --list, @LABEL and slow pick from every year.
+            let everything = self.all || (self.list && !self.this_year);
+            if everything || (term.is_undated() && (term.label.is_some() || term.slow)) {
+                continue;
             }
-            // --day D, today is not December => run last year's day D.
-            (false, true, _, _) => {
-                self.year = Some(today_year - 1);
-            }
-        }
-        if self.year.is_none() && self.day.is_none() && today.month() == 12 {
-            self.year = Some(today.year());
-            if !self.this_year {
-                self.day = Some(today.day());
+            match (
+                term.years.is_some(),
+                term.days.is_some(),
+                self.this_year,
+                today.month(),
+            ) {
+                // No args, today is a day on the advent calendar => run today only.
+                // No args, today is not on the calendar => run nothing.
+                (false, false, false, _) => {
+                    term.years = only(today_year);
+                    term.days = only(today_day);
+                }
+                // --this-year, today is still December => run today's.
+                (false, false, true, 12) => {
+                    term.years = only(today_year);
+                }
+                // --this-year, today is not December => run last year's.
+                (false, false, true, _) => {
+                    term.years = only(today_year - 1);
+                }
+                // --year Y => run everything from year Y.
+                (true, _, _, _) => (),
+                // --day D, today is December => run this year's day D.
+                (false, true, _, 12) => {
+                    term.years = only(today_year);
+                }
+                // --day D, today is not December => run last year's day D.
+                (false, true, _, _) => {
+                    term.years = only(today_year - 1);
+                }
             }
         }
+        self.selection = selection;
+        Ok(())
+    }
+
+    fn matches(&self, solver: &Solver) -> bool {
+        self.selection.matches(solver)
     }
 
-    fn matches(&self, year: i32, day: u32, part: u8) -> bool {
-        if self.all {
-            return true;
+    // Where the solver's work goes, if anywhere. With `shared`, other solvers
+    // are running too, so --vis-out files get the solver's name.
+    fn visualizer(
+        &self,
+        solver: &Solver,
+        profile: Option<&Profile>,
+        shared: bool,
+    ) -> anyhow::Result<Visualizer> {
+        let mut name = format!("{}-{:02}-{}", solver.year, solver.day, solver.part);
+        if let Some(label) = solver.label {
+            name.push('-');
+            name.extend(
+                label
+                    .chars()
+                    .map(|c| if c.is_alphanumeric() { c } else { '-' }),
+            );
         }
-        match (self.year, self.day, self.part) {
-            (None, None, None) => false,
-            (Some(y), _, _) if y != year => false,
-            (_, Some(d), _) if d != day => false,
-            (_, _, Some(p)) if p != part => false,
-            _ => true,
+        if let Some(out) = &self.vis_out {
+            let palette = match &self.vis_palette {
+                Some(spec) => vis::Palette::parse(spec)?,
+                None => vis::Palette::default(),
+            };
+            let mut path = out.clone();
+            if shared {
+                let stem = out.file_stem().unwrap_or_default().to_string_lossy();
+                let ext = out.extension().unwrap_or_default().to_string_lossy();
+                path.set_file_name(match profile {
+                    Some(profile) => format!("{stem}-{profile}-{name}.{ext}"),
+                    None => format!("{stem}-{name}.{ext}"),
+                });
+            }
+            let fps = self.vis_fps.unwrap_or(10.0);
+            let image = vis::Image::new(path, palette, self.vis_scale, self.vis_skip, fps)?;
+            return Ok(Visualizer::new(image));
+        }
+        if let Some(dir) = &self.vis_dir {
+            if let Some(profile) = profile {
+                name = format!("{profile}/{name}");
+            }
+            return Ok(Visualizer::new(vis::Recorder::new(dir.join(name))?));
         }
+        Ok(match (self.vis_fps, self.visualize) {
+            (Some(fps), _) => {
+                anyhow::ensure!(fps > 0.0, "--vis-fps needs to be more than 0");
+                Visualizer::new(vis::Animator::new(fps))
+            }
+            (None, true) => Visualizer::new(vis::Stdout),
+            (None, false) => Visualizer::off(),
+        })
     }
 
     fn run_slow_parts(&self) -> bool {
-        self.include_slow
+        self.include_slow || self.selection.wants_slow()
+    }
+}
+
+// A number of seconds more than 0, like 2.5.
+fn parse_seconds(s: &str) -> Result<Duration, String> {
+    let secs: f64 = s.parse().map_err(|_| format!("{s:?} isn't a number"))?;
+    if !(secs.is_finite() && secs > 0.0) {
+        return Err(format!("{s} isn't a number of seconds more than 0"));
     }
+    Duration::try_from_secs_f64(secs).map_err(|e| format!("{s} seconds: {e}"))
 }
 
+// Just one [YYYY/]DD[/PART], for commands that show one puzzle.
 fn parse_filter(
     filter: &str,
     verbose: bool,
 ) -> anyhow::Result<(Option<i32>, Option<u32>, Option<u8>)> {
-    #[derive(Debug)]
-    enum State {
-        YearOrDay,
-        DayOrFin,
-        Part,
-        PartOrFin,
-        Fin,
-    }
-    let mut state = State::YearOrDay;
-    let mut year = None;
-    let mut day = None;
-    let mut part = None;
-    for filter_part in filter.split('/') {
-        if verbose {
-            println!("{filter}: {state:?} {filter_part:?}");
-        }
-        state = match (state, filter_part) {
-            (State::YearOrDay, "") => State::Part,
-            (State::YearOrDay, d) if d.len() < 3 => {
-                day = Some(d.parse()?);
-                State::PartOrFin
-            }
-            (State::YearOrDay, y) if y.len() == 4 => {
-                year = Some(y.parse()?);
-                State::DayOrFin
-            }
-            (State::YearOrDay, x) => bail!("expected YYYY or DD but got {x:?}"),
-
-            (State::DayOrFin, d) if d.len() < 3 => {
-                day = Some(d.parse()?);
-                State::PartOrFin
-            }
-            (State::DayOrFin, x) => bail!("expected DD but got {x:?}"),
-
-            (State::Part | State::PartOrFin, p) if p.len() == 1 => {
-                part = Some(p.parse()?);
-                State::Fin
-            }
-            (State::Part | State::PartOrFin, x) => bail!("expected PART but got {x:?}"),
-
-            (State::Fin, _) => bail!("invalid filter {filter:?}: too many components"),
-        };
-    }
-    match state {
-        State::DayOrFin | State::PartOrFin | State::Fin => Ok((year, day, part)),
-        State::YearOrDay => unreachable!(),
-        State::Part => bail!("invalid filter {filter:?}: needs a part number after the /"),
+    let term = filter::Term::parse(filter)?;
+    if verbose {
+        println!("{filter}: {term:?}");
     }
+    term.single()
+        .ok_or_else(|| anyhow::anyhow!("invalid filter {filter:?}: expected [YYYY/]DD[/PART]"))
 }
 
 #[cfg(test)]
 mod tests {
-    use super::parse_filter;
+    use std::time::Duration;
+
+    use super::{parse_filter, parse_seconds};
+
+    #[test]
+    fn seconds() {
+        assert_eq!(Ok(Duration::from_millis(2500)), parse_seconds("2.5"));
+        assert_eq!(Ok(Duration::from_secs(30)), parse_seconds("30"));
+        for bad in ["0", "-1", "NaN", "inf", "1e30", "soon"] {
+            assert!(parse_seconds(bad).is_err(), "{bad}");
+        }
+    }
 
     #[test]
     fn parse_year_only() {
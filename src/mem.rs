@@ -0,0 +1,221 @@
+use std::fmt::Display;
+
+use serde::Serialize;
+
+// Allocations are only counted with the `mem` feature, since the allocator
+// that counts them adds a check to every allocation, even with --bench.
+pub const ENABLED: bool = cfg!(feature = "mem");
+
+#[cfg(feature = "mem")]
+pub use counting::measure;
+
+// Without the `mem` feature, there's nothing to count.
+#[cfg(not(feature = "mem"))]
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Usage) {
+    (f(), Usage::default())
+}
+
+#[cfg(feature = "mem")]
+mod counting {
+    use std::{
+        alloc::{GlobalAlloc, Layout, System},
+        cell::Cell,
+    };
+
+    use super::Usage;
+
+    // The system allocator, plus counts of what the current thread allocates
+    // while it's being measured. When nothing is being measured, this only
+    // costs a check of a thread local. Other threads aren't counted, so a
+    // solver that starts its own only shows what it allocates itself.
+    struct Counting;
+
+    #[global_allocator]
+    static ALLOCATOR: Counting = Counting;
+
+    thread_local! {
+        static MEASURING: Cell<bool> = const { Cell::new(false) };
+        static COUNTS: Cell<Counts> = const { Cell::new(Counts::ZERO) };
+    }
+
+    #[derive(Clone, Copy)]
+    struct Counts {
+        live: usize,
+        peak: usize,
+        allocations: u64,
+        bytes: u64,
+    }
+
+    impl Counts {
+        const ZERO: Counts = Counts {
+            live: 0,
+            peak: 0,
+            allocations: 0,
+            bytes: 0,
+        };
+    }
+
+    fn count(alloc: usize, dealloc: usize) {
+        // try_with, because the thread locals might be gone while a thread exits.
+        if !MEASURING.try_with(Cell::get).unwrap_or(false) {
+            return;
+        }
+        let _ = COUNTS.try_with(|c| {
+            let mut counts = c.get();
+            // Memory from before the measurement started can be freed during it.
+            counts.live = counts.live.saturating_sub(dealloc) + alloc;
+            counts.peak = counts.peak.max(counts.live);
+            if alloc > 0 {
+                counts.allocations += 1;
+                counts.bytes += alloc as u64;
+            }
+            c.set(counts);
+        });
+    }
+
+    unsafe impl GlobalAlloc for Counting {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let p = System.alloc(layout);
+            if !p.is_null() {
+                count(layout.size(), 0);
+            }
+            p
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let p = System.alloc_zeroed(layout);
+            if !p.is_null() {
+                count(layout.size(), 0);
+            }
+            p
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+            count(0, layout.size());
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let p = System.realloc(ptr, layout, new_size);
+            if !p.is_null() {
+                count(new_size, layout.size());
+            }
+            p
+        }
+    }
+
+    // Stops measuring even if `f` panics.
+    struct Stop;
+
+    impl Drop for Stop {
+        fn drop(&mut self) {
+            MEASURING.set(false);
+        }
+    }
+
+    // Runs `f` and counts what it allocates on this thread.
+    pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Usage) {
+        COUNTS.set(Counts::ZERO);
+        MEASURING.set(true);
+        let stop = Stop;
+        let result = f();
+        drop(stop);
+        let counts = COUNTS.get();
+        (
+            result,
+            Usage {
+                peak_bytes: counts.peak,
+                allocations: counts.allocations,
+                allocated_bytes: counts.bytes,
+            },
+        )
+    }
+}
+
+// What a solver allocated on its thread. Threads it starts aren't counted.
+#[derive(Serialize, Debug, Clone, Copy, Default, PartialEq)]
+pub struct Usage {
+    // The most that was allocated at once.
+    pub peak_bytes: usize,
+    // Including reallocations.
+    pub allocations: u64,
+    pub allocated_bytes: u64,
+}
+
+impl Display for Usage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "peak {}, {} allocs, {} allocated",
+            bytes(self.peak_bytes as u64),
+            self.allocations,
+            bytes(self.allocated_bytes)
+        )
+    }
+}
+
+fn bytes(n: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if n < 1024 {
+        return format!("{n} B");
+    }
+    let mut size = n as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit + 1 < UNITS.len() {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{size:.1} {}", UNITS[unit])
+}
+
+#[cfg(test)]
+mod test {
+    use super::bytes;
+    #[cfg(feature = "mem")]
+    use super::measure;
+
+    #[cfg(feature = "mem")]
+    #[test]
+    fn counts_allocations() {
+        let (sum, usage) = measure(|| {
+            let mut total = 0;
+            for _ in 0..3 {
+                let v = vec![1u8; 1000];
+                total += v.len();
+            }
+            total
+        });
+        assert_eq!(3000, sum);
+        assert_eq!(3, usage.allocations);
+        assert_eq!(3000, usage.allocated_bytes);
+        assert_eq!(1000, usage.peak_bytes);
+    }
+
+    #[cfg(feature = "mem")]
+    #[test]
+    fn counts_growth() {
+        let (_, usage) = measure(|| {
+            let mut v: Vec<u64> = Vec::with_capacity(4);
+            v.extend(0..8);
+            v
+        });
+        assert_eq!(2, usage.allocations);
+        assert_eq!(64, usage.peak_bytes);
+    }
+
+    #[cfg(feature = "mem")]
+    #[test]
+    fn stops_after() {
+        let (_, usage) = measure(|| ());
+        let v: Vec<u8> = Vec::with_capacity(100);
+        assert_eq!(100, v.capacity());
+        assert_eq!(0, usage.allocations);
+    }
+
+    #[test]
+    fn format_bytes() {
+        assert_eq!("512 B", bytes(512));
+        assert_eq!("1.5 KiB", bytes(1536));
+        assert_eq!("3.0 MiB", bytes(3 << 20));
+    }
+}
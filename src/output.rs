@@ -0,0 +1,316 @@
+use std::{collections::BTreeMap, io::Write, time::Duration};
+
+use serde::Serialize;
+
+use crate::{bench::Stats, mem::Usage};
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+    Junit,
+}
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    Ok,
+    Skipped,
+    Future,
+    Failed,
+    Unsolved,
+}
+
+#[derive(Serialize, Debug)]
+pub struct Record {
+    pub year: i32,
+    pub day: u32,
+    pub part: u8,
+    pub label: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+    pub slow: bool,
+    pub status: Status,
+    // None when hidden by --no-spoilers.
+    pub answer: Option<String>,
+    // "match", "mismatch" or "new", compared to the known-good answer.
+    pub check: Option<&'static str>,
+    pub duration_secs: Option<f64>,
+    pub error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bench: Option<Stats>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mem: Option<Usage>,
+}
+
+impl Record {
+    pub fn new(year: i32, day: u32, part: u8, label: Option<&'static str>, slow: bool) -> Self {
+        Self {
+            year,
+            day,
+            part,
+            label,
+            profile: None,
+            slow,
+            status: Status::Ok,
+            answer: None,
+            check: None,
+            duration_secs: None,
+            error: None,
+            bench: None,
+            mem: None,
+        }
+    }
+
+    pub fn with_duration(mut self, d: Duration) -> Self {
+        self.duration_secs = Some(d.as_secs_f64());
+        self
+    }
+}
+
+pub fn write_records(
+    format: Format,
+    records: &[Record],
+    out: &mut impl Write,
+) -> anyhow::Result<()> {
+    match format {
+        Format::Text => anyhow::bail!("text output is printed while running"),
+        Format::Json => {
+            serde_json::to_writer_pretty(&mut *out, records)?;
+            writeln!(out)?;
+        }
+        Format::Csv => write_csv(records, out)?,
+        Format::Junit => write_junit(records, out)?,
+    }
+    Ok(())
+}
+
+fn write_csv(records: &[Record], out: &mut impl Write) -> std::io::Result<()> {
+    writeln!(
+        out,
+        "year,day,part,label,slow,status,answer,check,duration_secs,error,profile,peak_bytes,allocations,allocated_bytes"
+    )?;
+    for r in records {
+        writeln!(
+            out,
+            "{},{},{},{},{},{},{},{},{},{},{},{},{},{}",
+            r.year,
+            r.day,
+            r.part,
+            csv_field(r.label.unwrap_or("")),
+            r.slow,
+            csv_field(status_name(r.status)),
+            csv_field(r.answer.as_deref().unwrap_or("")),
+            r.check.unwrap_or(""),
+            r.duration_secs.map(|d| d.to_string()).unwrap_or_default(),
+            csv_field(r.error.as_deref().unwrap_or("")),
+            csv_field(r.profile.as_deref().unwrap_or("")),
+            r.mem.map(|m| m.peak_bytes.to_string()).unwrap_or_default(),
+            r.mem.map(|m| m.allocations.to_string()).unwrap_or_default(),
+            r.mem
+                .map(|m| m.allocated_bytes.to_string())
+                .unwrap_or_default(),
+        )?;
+    }
+    Ok(())
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+fn status_name(s: Status) -> &'static str {
+    match s {
+        Status::Ok => "ok",
+        Status::Skipped => "skipped",
+        Status::Future => "future",
+        Status::Failed => "failed",
+        Status::Unsolved => "unsolved",
+    }
+}
+
+fn write_junit(records: &[Record], out: &mut impl Write) -> std::io::Result<()> {
+    let mut years: BTreeMap<i32, Vec<&Record>> = BTreeMap::new();
+    for r in records {
+        years.entry(r.year).or_default().push(r);
+    }
+    let failed = |r: &Record| r.status == Status::Failed || r.check == Some("mismatch");
+    let skipped = |r: &Record| {
+        matches!(
+            r.status,
+            Status::Skipped | Status::Future | Status::Unsolved
+        )
+    };
+    let time = |rs: &[&Record]| {
+        rs.iter()
+            .filter_map(|r| r.duration_secs)
+            .fold(0.0, |a, b| a + b)
+    };
+
+    let all: Vec<&Record> = records.iter().collect();
+    writeln!(out, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(
+        out,
+        r#"<testsuites name="advent-of-code" tests="{}" failures="{}" skipped="{}" time="{}">"#,
+        all.len(),
+        all.iter().filter(|r| failed(r)).count(),
+        all.iter().filter(|r| skipped(r)).count(),
+        time(&all),
+    )?;
+    for (year, rs) in &years {
+        writeln!(
+            out,
+            r#"  <testsuite name="{}" tests="{}" failures="{}" skipped="{}" time="{}">"#,
+            year,
+            rs.len(),
+            rs.iter().filter(|r| failed(r)).count(),
+            rs.iter().filter(|r| skipped(r)).count(),
+            time(rs),
+        )?;
+        for r in rs {
+            let mut name = match r.label {
+                Some(l) => format!("part {} ({})", r.part, l),
+                None => format!("part {}", r.part),
+            };
+            if let Some(profile) = &r.profile {
+                name = format!("{name} [{profile}]");
+            }
+            writeln!(
+                out,
+                r#"    <testcase classname="{}.day{:02}" name="{}" time="{}">"#,
+                r.year,
+                r.day,
+                xml_escape(&name),
+                r.duration_secs.unwrap_or(0.0),
+            )?;
+            if let Some(mem) = r.mem {
+                writeln!(out, "      <properties>")?;
+                for (name, value) in [
+                    ("peak_bytes", mem.peak_bytes as u64),
+                    ("allocations", mem.allocations),
+                    ("allocated_bytes", mem.allocated_bytes),
+                ] {
+                    writeln!(out, r#"        <property name="{name}" value="{value}"/>"#)?;
+                }
+                writeln!(out, "      </properties>")?;
+            }
+            match r.status {
+                Status::Failed => writeln!(
+                    out,
+                    r#"      <failure message="{}"/>"#,
+                    xml_escape(r.error.as_deref().unwrap_or("failed"))
+                )?,
+                Status::Skipped => writeln!(out, r#"      <skipped message="slow"/>"#)?,
+                Status::Future => writeln!(out, r#"      <skipped message="future"/>"#)?,
+                Status::Unsolved => writeln!(out, r#"      <skipped message="unsolved"/>"#)?,
+                Status::Ok if r.check == Some("mismatch") => writeln!(
+                    out,
+                    r#"      <failure message="answer does not match the known-good answer"/>"#
+                )?,
+                Status::Ok => (),
+            }
+            if let Some(answer) = &r.answer {
+                writeln!(out, "      <system-out>{}</system-out>", xml_escape(answer))?;
+            }
+            writeln!(out, "    </testcase>")?;
+        }
+        writeln!(out, "  </testsuite>")?;
+    }
+    writeln!(out, "</testsuites>")?;
+    Ok(())
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::{write_records, Format, Record, Status};
+    use crate::mem::Usage;
+
+    fn records() -> Vec<Record> {
+        let mut ok = Record::new(2023, 5, 1, None, false).with_duration(Duration::from_millis(2));
+        ok.answer = Some("35".to_string());
+        ok.check = Some("match");
+        ok.mem = Some(Usage {
+            peak_bytes: 1024,
+            allocations: 3,
+            allocated_bytes: 2048,
+        });
+        let mut failed = Record::new(2023, 7, 2, Some("a, \"b\""), false);
+        failed.status = Status::Failed;
+        failed.error = Some("PANIC: <oops>".to_string());
+        let mut slow = Record::new(2022, 19, 1, None, true);
+        slow.status = Status::Skipped;
+        slow.profile = Some("alice".to_string());
+        vec![ok, failed, slow]
+    }
+
+    fn render(format: Format) -> String {
+        let mut out = Vec::new();
+        write_records(format, &records(), &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn json() {
+        let v: serde_json::Value = serde_json::from_str(&render(Format::Json)).unwrap();
+        assert_eq!(3, v.as_array().unwrap().len());
+        assert_eq!("35", v[0]["answer"]);
+        assert_eq!(0.002, v[0]["duration_secs"]);
+        assert_eq!("failed", v[1]["status"]);
+        assert_eq!(true, v[2]["slow"]);
+        assert_eq!("skipped", v[2]["status"]);
+        assert_eq!("alice", v[2]["profile"]);
+        assert!(v[0].get("profile").is_none());
+        assert_eq!(1024, v[0]["mem"]["peak_bytes"]);
+        assert!(v[1].get("mem").is_none());
+    }
+
+    #[test]
+    fn csv() {
+        assert_eq!(
+            "year,day,part,label,slow,status,answer,check,duration_secs,error,profile,peak_bytes,allocations,allocated_bytes\n\
+             2023,5,1,,false,ok,35,match,0.002,,,1024,3,2048\n\
+             2023,7,2,\"a, \"\"b\"\"\",false,failed,,,,PANIC: <oops>,,,,\n\
+             2022,19,1,,true,skipped,,,,,alice,,,\n",
+            render(Format::Csv)
+        );
+    }
+
+    #[test]
+    fn junit() {
+        let xml = render(Format::Junit);
+        assert!(xml.contains(r#"<testsuites name="advent-of-code" tests="3" failures="1" skipped="1" time="0.002">"#), "{xml}");
+        assert!(
+            xml.contains(r#"<testsuite name="2022" tests="1" failures="0" skipped="1" time="0">"#),
+            "{xml}"
+        );
+        assert!(
+            xml.contains(
+                r#"<testcase classname="2023.day07" name="part 2 (a, &quot;b&quot;)" time="0">"#
+            ),
+            "{xml}"
+        );
+        assert!(
+            xml.contains(r#"<failure message="PANIC: &lt;oops&gt;"/>"#),
+            "{xml}"
+        );
+        assert!(xml.contains("<system-out>35</system-out>"), "{xml}");
+        assert!(
+            xml.contains(r#"<property name="allocations" value="3"/>"#),
+            "{xml}"
+        );
+    }
+}
@@ -0,0 +1,107 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+};
+
+// Calls `work` for each item on `jobs` worker threads, and passes each result
+// to `report` in the same order as `items`, as soon as it and everything
+// before it are done.
+pub fn run_ordered<T, R, W, P>(
+    jobs: usize,
+    items: &[T],
+    work: W,
+    mut report: P,
+) -> anyhow::Result<()>
+where
+    T: Sync,
+    R: Send,
+    W: Fn(&T) -> R + Sync,
+    P: FnMut(&T, R) -> anyhow::Result<()>,
+{
+    let next = AtomicUsize::new(0);
+    thread::scope(|scope| {
+        let (tx, rx) = mpsc::channel();
+        for _ in 0..jobs.clamp(1, items.len().max(1)) {
+            let tx = tx.clone();
+            let (next, work) = (&next, &work);
+            scope.spawn(move || loop {
+                let i = next.fetch_add(1, Ordering::SeqCst);
+                if i >= items.len() {
+                    break;
+                }
+                if tx.send((i, work(&items[i]))).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(tx);
+
+        let mut done = HashMap::new();
+        for (i, item) in items.iter().enumerate() {
+            let res = loop {
+                if let Some(res) = done.remove(&i) {
+                    break res;
+                }
+                let (j, res) = rx.recv()?;
+                done.insert(j, res);
+            };
+            if let Err(e) = report(item, res) {
+                // Stop handing out work, and let the workers finish what they're doing.
+                next.store(items.len(), Ordering::SeqCst);
+                return Err(e);
+            }
+        }
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use std::{thread, time::Duration};
+
+    use super::run_ordered;
+
+    #[test]
+    fn reports_in_order() {
+        let items: Vec<u64> = (0..20).collect();
+        let mut seen = Vec::new();
+        run_ordered(
+            4,
+            &items,
+            |i| {
+                // Make the early items finish last.
+                thread::sleep(Duration::from_millis(20 - i));
+                i * 10
+            },
+            |i, res| {
+                seen.push((*i, res));
+                Ok(())
+            },
+        )
+        .unwrap();
+        let expected: Vec<(u64, u64)> = (0..20).map(|i| (i, i * 10)).collect();
+        assert_eq!(expected, seen);
+    }
+
+    #[test]
+    fn stops_on_error() {
+        let items: Vec<u32> = (0..100).collect();
+        let mut count = 0;
+        let res = run_ordered(
+            2,
+            &items,
+            |i| *i,
+            |_, res| {
+                count += 1;
+                anyhow::ensure!(res < 3, "too big");
+                Ok(())
+            },
+        );
+        assert!(res.is_err());
+        assert_eq!(4, count);
+    }
+}
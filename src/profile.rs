@@ -0,0 +1,63 @@
+use std::fmt::Display;
+
+// Whose token, inputs and answers to use. The default profile keeps its
+// inputs and answers in inputs/ and answers/, and the others keep theirs in
+// profiles/NAME/.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Profile(Option<String>);
+
+impl Profile {
+    pub fn new(name: Option<&str>) -> anyhow::Result<Self> {
+        match name {
+            None | Some("default") => Ok(Profile(None)),
+            Some(name) => {
+                anyhow::ensure!(
+                    !name.is_empty()
+                        && name
+                            .chars()
+                            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'),
+                    "a profile name can only have letters, numbers, '-' and '_', not {name:?}"
+                );
+                Ok(Profile(Some(name.to_string())))
+            }
+        }
+    }
+
+    pub fn is_default(&self) -> bool {
+        self.0.is_none()
+    }
+
+    // Where this profile's "inputs" or "answers" are.
+    pub fn dir(&self, kind: &str) -> String {
+        match &self.0 {
+            None => kind.to_string(),
+            Some(name) => format!("profiles/{}/{}", name, kind),
+        }
+    }
+}
+
+impl Display for Profile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0.as_deref().unwrap_or("default"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Profile;
+
+    #[test]
+    fn dirs() {
+        let default = Profile::new(None).unwrap();
+        assert_eq!("inputs", default.dir("inputs"));
+        assert_eq!(default, Profile::new(Some("default")).unwrap());
+        assert_eq!("default", default.to_string());
+
+        let alice = Profile::new(Some("alice")).unwrap();
+        assert_eq!("profiles/alice/answers", alice.dir("answers"));
+        assert_eq!("alice", alice.to_string());
+
+        assert!(Profile::new(Some("../bob")).is_err());
+        assert!(Profile::new(Some("")).is_err());
+    }
+}
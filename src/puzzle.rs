@@ -0,0 +1,388 @@
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+use crate::{
+    http::http,
+    input::{page_file_for, read_cached, write_cached},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Style {
+    // Bold and colors for the terminal.
+    Terminal,
+    Plain,
+    Markdown,
+}
+
+const WIDTH: usize = 80;
+
+// Returns the puzzle page, from the cache if it's there. A cached page that
+// only has part 1 is downloaded again, in case part 2 is unlocked now. If
+// that fails, or with --offline, the cached page is used anyway. The token
+// is only needed for downloading.
+pub fn get_page(
+    base_url: &str,
+    token: impl FnOnce() -> anyhow::Result<String>,
+    year: i32,
+    day: u32,
+    refresh: bool,
+) -> anyhow::Result<String> {
+    let file = page_file_for(year, day);
+    let cached = match read_cached(&file) {
+        Ok(cached) => cached,
+        Err(e) => {
+            eprintln!("{e:#}");
+            None
+        }
+    };
+    if let Some(html) = &cached {
+        if http().is_offline() || (!refresh && articles(html).len() >= 2) {
+            return Ok(html.clone());
+        }
+    }
+    let url = format!("{}/{}/day/{}", base_url, year, day);
+    eprintln!("downloading {} ...", url);
+    match (token().and_then(|token| http().get(&url, &token)), cached) {
+        (Ok(html), _) => {
+            write_cached(&file, &url, &html)?;
+            Ok(html)
+        }
+        (Err(e), Some(html)) => {
+            eprintln!("{e:#}, using the cached page");
+            Ok(html)
+        }
+        (Err(e), None) => Err(e),
+    }
+}
+
+// The puzzle descriptions on a page, part 1 first. Part 2's is only there
+// once part 1 is solved.
+pub fn articles(html: &str) -> Vec<&str> {
+    static ARTICLE: OnceLock<Regex> = OnceLock::new();
+    ARTICLE
+        .get_or_init(|| Regex::new(r#"(?s)<article class="day-desc">(.*?)</article>"#).unwrap())
+        .captures_iter(html)
+        .map(|c| c.get(1).unwrap().as_str())
+        .collect()
+}
+
+// Converts a puzzle description from HTML. This only knows about the tags
+// that show up in puzzle descriptions.
+pub fn render(article: &str, style: Style, base_url: &str) -> String {
+    let mut r = Renderer {
+        style,
+        out: String::new(),
+        line: String::new(),
+        styles: Vec::new(),
+        links: Vec::new(),
+        pre: false,
+        code: false,
+        prefix: "",
+    };
+    static TOKEN: OnceLock<Regex> = OnceLock::new();
+    static HREF: OnceLock<Regex> = OnceLock::new();
+    let token = TOKEN.get_or_init(|| Regex::new(r"<(/?)([a-zA-Z0-9]+)([^>]*)>|([^<]+)").unwrap());
+    let href = HREF.get_or_init(|| Regex::new(r#"href="([^"]*)""#).unwrap());
+    for t in token.captures_iter(article) {
+        if let Some(text) = t.get(4) {
+            r.text(&unescape(text.as_str()));
+            continue;
+        }
+        let close = &t[1] == "/";
+        match (&t[2], close) {
+            ("h2", false) => r.open("## ", "\x1b[1;97m"),
+            ("h2", true) => {
+                r.close("", true);
+                r.flush();
+            }
+            ("p" | "br", _) | ("ul", false) => r.flush(),
+            ("ul", true) => r.out.push('\n'),
+            ("li", false) => {
+                r.flush();
+                r.prefix = "- ";
+            }
+            ("li", true) => {
+                r.flush();
+                r.prefix = "";
+                // Keep list items together.
+                r.out.pop();
+            }
+            ("pre", false) => {
+                r.flush();
+                r.pre = true;
+                if style == Style::Markdown {
+                    r.out.push_str("```\n");
+                }
+            }
+            ("pre", true) => {
+                if !r.line.is_empty() {
+                    r.end_pre_line();
+                }
+                r.pre = false;
+                if style == Style::Markdown {
+                    r.out.push_str("```\n");
+                }
+                r.out.push('\n');
+            }
+            ("code", false) if !r.pre => {
+                r.open("`", "\x1b[36m");
+                r.code = true;
+            }
+            ("code", true) if !r.pre => {
+                r.code = false;
+                r.close("`", false);
+            }
+            ("em", false) => match (r.pre || r.code, style) {
+                (true, Style::Markdown) => r.styles.push(None),
+                _ => r.open("*", "\x1b[1;97m"),
+            },
+            ("em", true) => r.close("*", false),
+            ("a", false) => {
+                let url = href
+                    .captures(&t[3])
+                    .map(|c| c[1].to_string())
+                    .unwrap_or_default();
+                let url = match url.starts_with('/') {
+                    true => format!("{}{}", base_url, url),
+                    false => url,
+                };
+                r.links.push(url);
+                r.open("[", "\x1b[4m");
+            }
+            ("a", true) => {
+                let url = r.links.pop().unwrap_or_default();
+                r.close(&format!("]({url})"), false);
+            }
+            _ => (),
+        }
+    }
+    r.flush();
+    while r.out.ends_with("\n\n") {
+        r.out.pop();
+    }
+    r.out
+}
+
+struct Renderer {
+    style: Style,
+    out: String,
+    // The current paragraph, or the current line of a <pre>.
+    line: String,
+    // The escape codes in effect, so they can be restored after a reset.
+    // None for styles that don't show up in this style.
+    styles: Vec<Option<&'static str>>,
+    links: Vec<String>,
+    pre: bool,
+    code: bool,
+    prefix: &'static str,
+}
+
+impl Renderer {
+    fn open(&mut self, markdown: &str, ansi: &'static str) {
+        match self.style {
+            Style::Markdown => self.line.push_str(markdown),
+            Style::Terminal => self.line.push_str(ansi),
+            Style::Plain => (),
+        }
+        self.styles.push(Some(ansi));
+    }
+
+    fn close(&mut self, markdown: &str, block: bool) {
+        let Some(Some(_)) = self.styles.pop() else {
+            return;
+        };
+        match self.style {
+            Style::Markdown if !block => self.line.push_str(markdown),
+            Style::Terminal => {
+                self.line.push_str("\x1b[0m");
+                for s in self.styles.iter().flatten() {
+                    self.line.push_str(s);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn text(&mut self, s: &str) {
+        if self.pre {
+            for (i, l) in s.split('\n').enumerate() {
+                if i > 0 {
+                    self.end_pre_line();
+                }
+                self.line.push_str(l);
+            }
+            return;
+        }
+        // Outside of <pre>, any run of whitespace is one space.
+        let raw = self.raw_text();
+        let words = s.split_whitespace().collect::<Vec<_>>().join(" ");
+        if s.starts_with(char::is_whitespace) && !raw.is_empty() && !raw.ends_with(' ') {
+            self.line.push(' ');
+        }
+        self.line.push_str(&words);
+        if s.ends_with(char::is_whitespace) && !words.is_empty() {
+            self.line.push(' ');
+        }
+    }
+
+    fn end_pre_line(&mut self) {
+        if self.style != Style::Markdown && !self.line.is_empty() {
+            self.out.push_str("    ");
+        }
+        self.out.push_str(&self.line);
+        self.out.push('\n');
+        self.line.clear();
+    }
+
+    // The current line without any escape codes.
+    fn raw_text(&self) -> String {
+        escape_code().replace_all(&self.line, "").into_owned()
+    }
+
+    // Ends the current paragraph.
+    fn flush(&mut self) {
+        if self.pre {
+            return;
+        }
+        let raw = self.raw_text();
+        if raw.trim().is_empty() {
+            self.line.clear();
+            return;
+        }
+        let line = std::mem::take(&mut self.line);
+        let line = line.trim_end();
+        let indent = " ".repeat(self.prefix.len());
+        match self.style {
+            Style::Markdown => {
+                self.out.push_str(self.prefix);
+                self.out.push_str(line);
+                self.out.push('\n');
+            }
+            Style::Terminal | Style::Plain => {
+                for (i, l) in wrap(line, WIDTH - self.prefix.len()).iter().enumerate() {
+                    self.out
+                        .push_str(if i == 0 { self.prefix } else { &indent });
+                    self.out.push_str(l);
+                    self.out.push('\n');
+                }
+            }
+        }
+        self.out.push('\n');
+    }
+}
+
+// Breaks a line into lines of at most `width` visible characters, where it can.
+fn wrap(line: &str, width: usize) -> Vec<String> {
+    let visible = |s: &str| escape_code().replace_all(s, "").chars().count();
+    let mut lines = vec![String::new()];
+    for word in line.split(' ') {
+        let cur = lines.last_mut().unwrap();
+        if !cur.is_empty() && visible(cur) + 1 + visible(word) > width {
+            lines.push(word.to_string());
+        } else {
+            if !cur.is_empty() {
+                cur.push(' ');
+            }
+            cur.push_str(word);
+        }
+    }
+    lines
+}
+
+// A terminal color or style.
+fn escape_code() -> &'static Regex {
+    static ESCAPE: OnceLock<Regex> = OnceLock::new();
+    ESCAPE.get_or_init(|| Regex::new("\x1b\\[[0-9;]*m").unwrap())
+}
+
+pub(crate) fn strip_tags(s: &str) -> String {
+    static TAG: OnceLock<Regex> = OnceLock::new();
+    TAG.get_or_init(|| Regex::new(r"<[^>]*>").unwrap())
+        .replace_all(s, "")
+        .into_owned()
+}
+
+pub(crate) fn unescape(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod test {
+    use super::{articles, render, wrap, Style};
+
+    const PAGE: &str = include_str!("../fixtures/2022-day-1.html");
+
+    #[test]
+    fn find_articles() {
+        let parts = articles(PAGE);
+        assert_eq!(2, parts.len());
+        assert!(parts[0].starts_with("<h2>--- Day 1: Calorie Counting ---</h2>"));
+        assert!(parts[1].starts_with(r#"<h2 id="part2">--- Part Two ---</h2>"#));
+        assert_eq!(
+            1,
+            articles(include_str!("../fixtures/2022-day-14-part1.html")).len()
+        );
+    }
+
+    #[test]
+    fn render_markdown() {
+        let md = render(articles(PAGE)[0], Style::Markdown, "https://example.com");
+        assert!(md.starts_with("## --- Day 1: Calorie Counting ---\n\nSanta's reindeer typically eat regular reindeer food, but they need a lot of [magical energy](https://example.com/2018/day/25) to deliver presents on Christmas.\n\n"), "{md}");
+        assert!(
+            md.contains("following list:\n\n```\n1000\n2000\n3000\n\n4000\n"),
+            "{md}"
+        );
+        assert!(md.contains("\n10000\n```\n\nThis list"), "{md}");
+        assert!(md.contains("- The fourth Elf is carrying food with `7000`, `8000`, and `9000` Calories, a total of `24000` Calories.\n\nIn case"), "{md}");
+        assert!(
+            md.contains("this is *`24000`* (carried by the fourth Elf)."),
+            "{md}"
+        );
+        assert!(
+            md.ends_with("*How many total Calories is that Elf carrying?*\n"),
+            "{md}"
+        );
+    }
+
+    #[test]
+    fn render_plain() {
+        let text = render(articles(PAGE)[1], Style::Plain, "");
+        assert_eq!(
+            "--- Part Two ---\n\
+             \n\
+             By the time you calculate the answer to the Elves' question, they've already\n\
+             realized that the Elf carrying the most Calories of food might eventually run\n\
+             out of snacks.\n",
+            &text[..text.find("\n\nIn the example").unwrap() + 1]
+        );
+        assert!(text.lines().all(|l| l.chars().count() <= 80), "{text}");
+    }
+
+    #[test]
+    fn render_terminal() {
+        let text = render(articles(PAGE)[0], Style::Terminal, "");
+        assert!(text.starts_with("\x1b[1;97m--- Day 1: Calorie Counting ---\x1b[0m\n\n"));
+        assert!(
+            text.contains("\n  \x1b[36m\x1b[1;97m24000\x1b[0m\x1b[36m\x1b[0m Calories."),
+            "{text:?}"
+        );
+        assert!(
+            text.contains("\n    1000\n    2000\n    3000\n\n    4000\n"),
+            "{text}"
+        );
+    }
+
+    #[test]
+    fn wrap_ignores_escapes() {
+        assert_eq!(
+            vec!["\x1b[1maaa\x1b[0m bb", "cc"],
+            wrap("\x1b[1maaa\x1b[0m bb cc", 6)
+        );
+        assert_eq!(vec!["toolongword", "x"], wrap("toolongword x", 4));
+    }
+}
@@ -1,15 +1,97 @@
-macro_rules! aoc_part {
-    ($s:expr, $year:expr, $day:expr, $part:expr, $f:expr) => {
-        $s.add($year, $day, $part, Some(stringify!($f)), $f);
-    };
+use crate::{
+    answer::Answer,
+    solutionset::{Flags, SolutionSet},
+    vis::Visualizer,
+};
+
+// One of these is submitted for every function annotated with #[aoc(...)].
+pub struct Registration {
+    pub year: i32,
+    pub day: u32,
+    pub part: u8,
+    pub label: Option<&'static str>,
+    pub slow: bool,
+    // Only a placeholder, which returns "todo".
+    pub unsolved: bool,
+    pub f: fn(String, &Visualizer) -> anyhow::Result<Answer>,
 }
 
-pub(crate) use aoc_part;
+inventory::collect!(Registration);
 
-macro_rules! slow_aoc_part {
-    ($s:expr, $year:expr, $day:expr, $part:expr, $f:expr) => {
-        $s.add_slow($year, $day, $part, Some(stringify!($f)), $f);
-    };
+pub fn register<S: SolutionSet>(s: &mut S) {
+    for r in inventory::iter::<Registration> {
+        let flags = Flags {
+            slow: r.slow,
+            unsolved: r.unsolved,
+        };
+        s.add(r.year, r.day, r.part, r.label, flags, r.f);
+    }
 }
 
-pub(crate) use slow_aoc_part;
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use super::{register, Registration};
+    use crate::{
+        answer::IntoAnswer,
+        solutionset::{Flags, SolutionSet},
+        vis::Visualizer,
+    };
+
+    type Key = (i32, u32, u8, Option<&'static str>);
+
+    #[derive(Default)]
+    struct Added(Vec<(Key, Flags)>);
+
+    impl SolutionSet for Added {
+        fn add<F, R>(
+            &mut self,
+            year: i32,
+            day: u32,
+            part: u8,
+            label: Option<&'static str>,
+            flags: Flags,
+            _: F,
+        ) where
+            F: Fn(String, &Visualizer) -> R + Send + Sync + 'static,
+            R: IntoAnswer,
+        {
+            self.0.push(((year, day, part, label), flags));
+        }
+    }
+
+    #[test]
+    fn registrations_are_unique() {
+        let mut seen = HashSet::new();
+        for r in inventory::iter::<Registration> {
+            assert!(
+                seen.insert((r.year, r.day, r.part, r.label)),
+                "{}/{}/{} {:?} is registered more than once",
+                r.year,
+                r.day,
+                r.part,
+                r.label
+            );
+        }
+    }
+
+    #[test]
+    fn flags_are_passed_on() {
+        let mut added = Added::default();
+        register(&mut added);
+        let expected: Vec<_> = inventory::iter::<Registration>
+            .into_iter()
+            .map(|r| {
+                let flags = Flags {
+                    slow: r.slow,
+                    unsolved: r.unsolved,
+                };
+                ((r.year, r.day, r.part, r.label), flags)
+            })
+            .collect();
+        assert_eq!(expected, added.0);
+        assert!(added.0.iter().any(|(_, f)| f.slow));
+        assert!(added.0.iter().any(|(_, f)| f.unsolved));
+    }
+}
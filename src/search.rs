@@ -0,0 +1,310 @@
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::{hash_map::Entry, BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+// What a path costs. Zero is the default.
+pub trait Cost: Copy + Ord + Add<Output = Self> + Default {}
+
+impl<T: Copy + Ord + Add<Output = T> + Default> Cost for T {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Found<N, C> {
+    pub cost: C,
+    // From a start to the goal, both included.
+    pub path: Vec<N>,
+    // How many nodes were taken off the queue and looked at.
+    pub expanded: usize,
+}
+
+// The nodes seen so far, with the one each was reached from.
+struct Tree<N> {
+    nodes: Vec<(N, Option<usize>)>,
+}
+
+impl<N: Clone> Tree<N> {
+    fn add(&mut self, node: N, parent: Option<usize>) -> usize {
+        self.nodes.push((node, parent));
+        self.nodes.len() - 1
+    }
+
+    fn path(&self, mut i: usize) -> Vec<N> {
+        let mut path = vec![self.nodes[i].0.clone()];
+        while let Some(parent) = self.nodes[i].1 {
+            path.push(self.nodes[parent].0.clone());
+            i = parent;
+        }
+        path.reverse();
+        path
+    }
+}
+
+// The fewest steps from any of `starts` to a node where `goal` is true. Nodes
+// with the same `key` are only visited once.
+pub fn bfs<N, K, I>(
+    starts: impl IntoIterator<Item = N>,
+    key: impl Fn(&N) -> K,
+    mut successors: impl FnMut(&N) -> I,
+    mut goal: impl FnMut(&N) -> bool,
+) -> Option<Found<N, usize>>
+where
+    N: Clone,
+    K: Hash + Eq,
+    I: IntoIterator<Item = N>,
+{
+    let mut tree = Tree { nodes: Vec::new() };
+    let mut seen = HashSet::new();
+    let mut todo = VecDeque::new();
+    for start in starts {
+        if seen.insert(key(&start)) {
+            todo.push_back((tree.add(start, None), 0));
+        }
+    }
+    let mut expanded = 0;
+    while let Some((i, steps)) = todo.pop_front() {
+        expanded += 1;
+        if goal(&tree.nodes[i].0) {
+            return Some(Found {
+                cost: steps,
+                path: tree.path(i),
+                expanded,
+            });
+        }
+        for next in successors(&tree.nodes[i].0) {
+            if seen.insert(key(&next)) {
+                todo.push_back((tree.add(next, Some(i)), steps + 1));
+            }
+        }
+    }
+    None
+}
+
+// The cheapest path from any of `starts` to a node where `goal` is true.
+// `successors` gives each next node with the cost of getting there.
+pub fn dijkstra<N, K, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    key: impl Fn(&N) -> K,
+    successors: impl FnMut(&N) -> I,
+    goal: impl FnMut(&N) -> bool,
+) -> Option<Found<N, C>>
+where
+    N: Clone,
+    K: Hash + Eq,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(starts, key, successors, |_| C::default(), goal)
+}
+
+// Like dijkstra, but nodes that `heuristic` says are closer to the goal are
+// tried first. It must never be more than the real cost to the goal.
+pub fn astar<N, K, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    key: impl Fn(&N) -> K,
+    mut successors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut goal: impl FnMut(&N) -> bool,
+) -> Option<Found<N, C>>
+where
+    N: Clone,
+    K: Hash + Eq,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut tree = Tree { nodes: Vec::new() };
+    let mut best = HashMap::new();
+    // Ties go to the node that was found first.
+    let mut todo = BinaryHeap::new();
+    for start in starts {
+        if let Entry::Vacant(e) = best.entry(key(&start)) {
+            e.insert(C::default());
+            let estimate = heuristic(&start);
+            todo.push(Reverse((estimate, tree.add(start, None), C::default())));
+        }
+    }
+    let mut expanded = 0;
+    while let Some(Reverse((_, i, cost))) = todo.pop() {
+        let node = &tree.nodes[i].0;
+        // There's a cheaper way here, so this one's already been looked at.
+        if best[&key(node)] < cost {
+            continue;
+        }
+        expanded += 1;
+        if goal(node) {
+            return Some(Found {
+                cost,
+                path: tree.path(i),
+                expanded,
+            });
+        }
+        for (next, step) in successors(node) {
+            let next_cost = cost + step;
+            match best.entry(key(&next)) {
+                Entry::Occupied(e) if *e.get() <= next_cost => continue,
+                Entry::Occupied(mut e) => *e.get_mut() = next_cost,
+                Entry::Vacant(e) => {
+                    e.insert(next_cost);
+                }
+            }
+            let estimate = next_cost + heuristic(&next);
+            todo.push(Reverse((estimate, tree.add(next, Some(i)), next_cost)));
+        }
+    }
+    None
+}
+
+// The cost of the cheapest paths to a goal, and how many different ones there
+// are. Nodes with the same key count as the same node. Every step has to cost
+// more than zero.
+pub fn count_shortest_paths<N, K, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    key: impl Fn(&N) -> K,
+    mut successors: impl FnMut(&N) -> I,
+    mut goal: impl FnMut(&N) -> bool,
+) -> Option<(C, u64)>
+where
+    K: Hash + Eq + Clone,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    // The cheapest way to each node so far, and how many paths cost that.
+    let mut best: HashMap<K, (C, u64)> = HashMap::new();
+    let mut done = HashSet::new();
+    let mut todo = BinaryHeap::new();
+    let mut nodes = Vec::new();
+    for start in starts {
+        let (_, count) = best.entry(key(&start)).or_insert((C::default(), 0));
+        *count += 1;
+        nodes.push(start);
+        todo.push(Reverse((C::default(), nodes.len() - 1)));
+    }
+    let mut found: Option<(C, u64)> = None;
+    while let Some(Reverse((cost, i))) = todo.pop() {
+        if found.is_some_and(|(c, _)| cost > c) {
+            break;
+        }
+        let k = key(&nodes[i]);
+        if !done.insert(k.clone()) {
+            continue;
+        }
+        // Everything that leads here is cheaper, so it's all been counted.
+        let (_, count) = best[&k];
+        if goal(&nodes[i]) {
+            let (_, total) = found.get_or_insert((cost, 0));
+            *total += count;
+            continue;
+        }
+        for (next, step) in successors(&nodes[i]) {
+            let next_cost = cost + step;
+            match best.entry(key(&next)) {
+                Entry::Occupied(mut e) => match next_cost.cmp(&e.get().0) {
+                    Ordering::Greater => continue,
+                    Ordering::Equal => {
+                        e.get_mut().1 += count;
+                        continue;
+                    }
+                    Ordering::Less => *e.get_mut() = (next_cost, count),
+                },
+                Entry::Vacant(e) => {
+                    e.insert((next_cost, count));
+                }
+            }
+            nodes.push(next);
+            todo.push(Reverse((next_cost, nodes.len() - 1)));
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod test {
+    use super::{astar, bfs, count_shortest_paths, dijkstra};
+    use crate::grid::{Grid, Pos};
+
+    const MAZE: &str = "\
+S..#....
+.#.#.##.
+.#...#..
+.####.#.
+......#E";
+
+    fn maze() -> (Grid<char>, Pos, Pos) {
+        let grid = Grid::parse(MAZE, |c| c);
+        let start = grid.find(|&c| c == 'S').unwrap();
+        let end = grid.find(|&c| c == 'E').unwrap();
+        (grid, start, end)
+    }
+
+    fn open(grid: &Grid<char>, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        grid.neighbors4(pos).filter(|&p| grid[p] != '#')
+    }
+
+    fn distance((r1, c1): Pos, (r2, c2): Pos) -> usize {
+        r1.abs_diff(r2) + c1.abs_diff(c2)
+    }
+
+    #[test]
+    fn breadth_first() {
+        let (grid, start, end) = maze();
+        let found = bfs([start], |&p| p, |&p| open(&grid, p), |&p| p == end).unwrap();
+        assert_eq!(15, found.cost);
+        assert_eq!(16, found.path.len());
+        assert_eq!((start, end), (found.path[0], found.path[15]));
+        for w in found.path.windows(2) {
+            assert_eq!(1, distance(w[0], w[1]));
+        }
+        assert_eq!(None, bfs([start], |&p| p, |&p| open(&grid, p), |_| false));
+    }
+
+    #[test]
+    fn weighted() {
+        // Going right costs 1, down costs 10, so the cheapest way goes right
+        // first.
+        let grid = Grid::new(3, 3, ());
+        let steps = |&(r, c): &Pos| {
+            let right = grid.step((r, c), (0, 1)).map(|p| (p, 1));
+            let down = grid.step((r, c), (1, 0)).map(|p| (p, 10));
+            right.into_iter().chain(down)
+        };
+        let found = dijkstra([(0, 0)], |&p| p, steps, |&p| p == (2, 2)).unwrap();
+        assert_eq!(22, found.cost);
+        assert_eq!(vec![(0, 0), (0, 1), (0, 2), (1, 2), (2, 2)], found.path);
+    }
+
+    #[test]
+    fn heuristic_expands_less() {
+        let (grid, start, end) = maze();
+        let steps = |&p: &Pos| open(&grid, p).map(|p| (p, 1)).collect::<Vec<_>>();
+        let plain = dijkstra([start], |&p| p, steps, |&p| p == end).unwrap();
+        let guided = astar([start], |&p| p, steps, |&p| distance(p, end), |&p| p == end).unwrap();
+        assert_eq!(plain.cost, guided.cost);
+        assert!(guided.expanded < plain.expanded, "{guided:?} vs {plain:?}");
+    }
+
+    #[test]
+    fn counts_paths() {
+        // On an open 3x4 grid, going right and down only: C(5, 2) ways.
+        let grid = Grid::new(3, 4, ());
+        let steps = |&p: &Pos| {
+            let next = [grid.step(p, (0, 1)), grid.step(p, (1, 0))];
+            next.into_iter().flatten().map(|p| (p, 1))
+        };
+        assert_eq!(
+            Some((5, 10)),
+            count_shortest_paths([(0, 0)], |&p| p, steps, |&p| p == (2, 3))
+        );
+        // Any direction: the longer paths don't count.
+        let steps = |&p: &Pos| grid.neighbors4(p).map(|p| (p, 1)).collect::<Vec<_>>();
+        assert_eq!(
+            Some((5, 10)),
+            count_shortest_paths([(0, 0)], |&p| p, steps, |&p| p == (2, 3))
+        );
+        // Two goals the same distance away.
+        assert_eq!(
+            Some((1, 2)),
+            count_shortest_paths([(0, 0)], |&p| p, steps, |&p| p == (0, 1) || p == (1, 0))
+        );
+    }
+}
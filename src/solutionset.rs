@@ -1,11 +1,23 @@
-use std::fmt::Display;
+use crate::{answer::IntoAnswer, vis::Visualizer};
 
-pub trait SolutionSet {
-    fn add<F>(&mut self, year: i32, day: u32, part: u8, label: Option<&'static str>, f: F)
-    where
-        F: Fn(String, bool) -> Box<dyn Display> + 'static;
+// The words that can follow the label in #[aoc(...)], which may be combined.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Flags {
+    pub slow: bool,
+    // A placeholder for a part that hasn't been solved yet.
+    pub unsolved: bool,
+}
 
-    fn add_slow<F>(&mut self, year: i32, day: u32, part: u8, label: Option<&'static str>, f: F)
-    where
-        F: Fn(String, bool) -> Box<dyn Display> + 'static;
+pub trait SolutionSet {
+    fn add<F, R>(
+        &mut self,
+        year: i32,
+        day: u32,
+        part: u8,
+        label: Option<&'static str>,
+        flags: Flags,
+        f: F,
+    ) where
+        F: Fn(String, &Visualizer) -> R + Send + Sync + 'static,
+        R: IntoAnswer;
 }
@@ -1,12 +1,18 @@
-use std::fmt::Display;
+use std::sync::Arc;
+
+use crate::{answer::Answer, vis::Visualizer};
+
+pub type SolverFn = dyn Fn(String, &Visualizer) -> anyhow::Result<Answer> + Send + Sync;
 
 pub struct Solver {
     pub year: i32,
     pub day: u32,
     pub part: u8,
     pub label: Option<&'static str>,
-    pub f: Box<dyn Fn(String, bool) -> Box<dyn Display>>,
+    pub f: Arc<SolverFn>,
     pub slow: bool,
+    // A placeholder, registered with `unsolved`.
+    pub unsolved: bool,
 }
 
 impl Solver {
@@ -0,0 +1,291 @@
+use std::{fmt::Display, time::Duration};
+
+use crate::http::http;
+
+#[derive(Debug, PartialEq)]
+pub enum Outcome {
+    Correct,
+    Wrong(Option<Hint>),
+    RateLimited(Duration),
+    AlreadySolved,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "correct"),
+            Outcome::Wrong(None) => write!(f, "wrong"),
+            Outcome::Wrong(Some(Hint::TooHigh)) => write!(f, "too high"),
+            Outcome::Wrong(Some(Hint::TooLow)) => write!(f, "too low"),
+            Outcome::RateLimited(d) => write!(f, "rate limited, wait {d:?}"),
+            Outcome::AlreadySolved => write!(f, "already solved"),
+        }
+    }
+}
+
+impl Outcome {
+    // How it's saved in the submission history. Unlike the Display text, this
+    // can't change, or the history wouldn't be understood any more.
+    pub fn key(&self) -> String {
+        match self {
+            Outcome::Correct => "correct".to_string(),
+            Outcome::Wrong(None) => "wrong".to_string(),
+            Outcome::Wrong(Some(Hint::TooHigh)) => "too-high".to_string(),
+            Outcome::Wrong(Some(Hint::TooLow)) => "too-low".to_string(),
+            Outcome::RateLimited(d) => format!("rate-limited-{}", d.as_secs()),
+            Outcome::AlreadySolved => "already-solved".to_string(),
+        }
+    }
+
+    // Also understands the text that older histories have.
+    pub fn from_key(key: &str) -> Option<Self> {
+        Some(match key {
+            "correct" => Outcome::Correct,
+            "wrong" => Outcome::Wrong(None),
+            "too-high" | "too high" => Outcome::Wrong(Some(Hint::TooHigh)),
+            "too-low" | "too low" => Outcome::Wrong(Some(Hint::TooLow)),
+            "already-solved" | "already solved" => Outcome::AlreadySolved,
+            _ => {
+                let secs = match key.strip_prefix("rate-limited-") {
+                    Some(secs) => secs.parse().ok()?,
+                    None if key.starts_with("rate limited") => 0,
+                    None => return None,
+                };
+                Outcome::RateLimited(Duration::from_secs(secs))
+            }
+        })
+    }
+}
+
+pub fn submit_answer(
+    base_url: &str,
+    token: &str,
+    year: i32,
+    day: u32,
+    part: u8,
+    answer: &str,
+) -> anyhow::Result<Outcome> {
+    let url = format!("{}/{}/day/{}/answer", base_url, year, day);
+    eprintln!("submitting to {} ...", url);
+    let html = http().post_form(
+        &url,
+        token,
+        &[("level", part.to_string()), ("answer", answer.to_string())],
+    )?;
+    parse_response(&html)
+}
+
+// Explains why `answer` shouldn't be submitted, given the earlier (answer, outcome) pairs.
+pub fn already_tried(history: &[(String, Outcome)], answer: &str) -> Option<String> {
+    let num = |s: &str| s.parse::<i128>().ok();
+    for (prev, outcome) in history {
+        let worse = match (outcome, num(prev), num(answer)) {
+            (Outcome::Wrong(Some(Hint::TooHigh)), Some(p), Some(a)) => a >= p,
+            (Outcome::Wrong(Some(Hint::TooLow)), Some(p), Some(a)) => a <= p,
+            _ => false,
+        };
+        match outcome {
+            Outcome::Correct => return Some(format!("{prev} was already accepted")),
+            Outcome::Wrong(_) if prev == answer => {
+                return Some(format!("{answer} was already submitted and was {outcome}"))
+            }
+            _ if worse => return Some(format!("{prev} was {outcome}")),
+            _ => (),
+        }
+    }
+    None
+}
+
+fn parse_response(html: &str) -> anyhow::Result<Outcome> {
+    let main = match (html.find("<main>"), html.find("</main>")) {
+        (Some(start), Some(end)) if start < end => &html[start..end],
+        _ => html,
+    };
+    if main.contains("That's the right answer") {
+        Ok(Outcome::Correct)
+    } else if main.contains("That's not the right answer") {
+        Ok(Outcome::Wrong(if main.contains("too high") {
+            Some(Hint::TooHigh)
+        } else if main.contains("too low") {
+            Some(Hint::TooLow)
+        } else {
+            None
+        }))
+    } else if main.contains("You gave an answer too recently") {
+        let re = regex::Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait").unwrap();
+        let wait = match re.captures(main) {
+            Some(c) => {
+                let m: u64 = c.get(1).map_or(Ok(0), |m| m.as_str().parse())?;
+                let s: u64 = c[2].parse()?;
+                Duration::from_secs(m * 60 + s)
+            }
+            None => Duration::ZERO,
+        };
+        Ok(Outcome::RateLimited(wait))
+    } else if main.contains("You don't seem to be solving the right level") {
+        Ok(Outcome::AlreadySolved)
+    } else {
+        anyhow::bail!("unrecognized response: {main}")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        io::{Read, Write},
+        net::TcpListener,
+        thread,
+        time::Duration,
+    };
+
+    use super::{already_tried, parse_response, submit_answer, Hint, Outcome};
+
+    fn page(article: &str) -> String {
+        format!("<html><body><main>\n<article><p>{article}</p></article>\n</main></body></html>")
+    }
+
+    #[test]
+    fn parse_correct() {
+        assert_eq!(
+            Outcome::Correct,
+            parse_response(&page(
+                "That's the right answer!  You are one gold star closer to saving your vacation."
+            ))
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn parse_wrong() {
+        assert_eq!(
+            Outcome::Wrong(Some(Hint::TooHigh)),
+            parse_response(&page("That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data.")).unwrap()
+        );
+        assert_eq!(
+            Outcome::Wrong(Some(Hint::TooLow)),
+            parse_response(&page(
+                "That's not the right answer; your answer is too low."
+            ))
+            .unwrap()
+        );
+        assert_eq!(
+            Outcome::Wrong(None),
+            parse_response(&page("That's not the right answer.  If you're stuck, make sure you're using the full input data.")).unwrap()
+        );
+    }
+
+    #[test]
+    fn parse_rate_limited() {
+        assert_eq!(
+            Outcome::RateLimited(Duration::from_secs(4 * 60 + 12)),
+            parse_response(&page("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 4m 12s left to wait.")).unwrap()
+        );
+        assert_eq!(
+            Outcome::RateLimited(Duration::from_secs(37)),
+            parse_response(&page("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 37s left to wait.")).unwrap()
+        );
+    }
+
+    #[test]
+    fn parse_already_solved() {
+        assert_eq!(
+            Outcome::AlreadySolved,
+            parse_response(&page(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            ))
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn parse_unknown() {
+        assert!(parse_response(&page("Something else")).is_err());
+    }
+
+    #[test]
+    fn skip_known_wrong_answers() {
+        let history = vec![
+            ("100".to_string(), Outcome::Wrong(Some(Hint::TooHigh))),
+            ("10".to_string(), Outcome::Wrong(Some(Hint::TooLow))),
+            ("50".to_string(), Outcome::Wrong(None)),
+            (
+                "60".to_string(),
+                Outcome::RateLimited(Duration::from_secs(30)),
+            ),
+        ];
+        assert!(already_tried(&history, "100").is_some());
+        assert!(already_tried(&history, "120").is_some());
+        assert!(already_tried(&history, "5").is_some());
+        assert!(already_tried(&history, "50").is_some());
+        assert_eq!(None, already_tried(&history, "60"));
+        assert_eq!(None, already_tried(&history, "70"));
+        assert_eq!(None, already_tried(&[], "70"));
+        let correct = [("42".to_string(), Outcome::Correct)];
+        assert_eq!(
+            Some("42 was already accepted".to_string()),
+            already_tried(&correct, "43")
+        );
+    }
+
+    #[test]
+    fn keys() {
+        for outcome in [
+            Outcome::Correct,
+            Outcome::Wrong(None),
+            Outcome::Wrong(Some(Hint::TooHigh)),
+            Outcome::Wrong(Some(Hint::TooLow)),
+            Outcome::RateLimited(Duration::from_secs(252)),
+            Outcome::AlreadySolved,
+        ] {
+            assert_eq!(Some(&outcome), Outcome::from_key(&outcome.key()).as_ref());
+        }
+        assert_eq!(
+            Some(Outcome::Wrong(Some(Hint::TooLow))),
+            Outcome::from_key("too low")
+        );
+        assert_eq!(
+            Some(Outcome::RateLimited(Duration::ZERO)),
+            Outcome::from_key("rate limited, wait 30s")
+        );
+        assert_eq!(None, Outcome::from_key("rate-limited-soon"));
+        assert_eq!(None, Outcome::from_key("maybe"));
+    }
+
+    #[test]
+    fn submit_to_mock_server() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let (mut conn, _) = listener.accept().unwrap();
+            let mut req = Vec::new();
+            let mut buf = [0; 1024];
+            while !String::from_utf8_lossy(&req).ends_with("answer=46") {
+                let n = conn.read(&mut buf).unwrap();
+                req.extend_from_slice(&buf[..n]);
+            }
+            let body = page("That's the right answer!");
+            write!(
+                conn,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            )
+            .unwrap();
+            String::from_utf8(req).unwrap()
+        });
+
+        let outcome = submit_answer(&base_url, "abc", 2023, 5, 2, "46").unwrap();
+        assert_eq!(Outcome::Correct, outcome);
+
+        let req = server.join().unwrap();
+        assert!(req.starts_with("POST /2023/day/5/answer "), "{req}");
+        assert!(req.contains("session=abc"), "{req}");
+        assert!(req.contains("level=2&answer=46"), "{req}");
+    }
+}
@@ -1,29 +1,81 @@
-use std::fmt::Display;
+use crate::{
+    answer::{Answer, IntoAnswer},
+    vis::{Stdout, Visualizer},
+};
 
-pub fn dotest<F, R>(expected: R, input: &str, f: F)
+pub fn dotest<F, R, E>(expected: E, input: &str, f: F)
 where
-    F: FnOnce(String, bool) -> Box<dyn Display>,
-    R: Display,
+    F: FnOnce(String, &Visualizer) -> R,
+    R: IntoAnswer,
+    E: Into<Answer>,
 {
     assert_eq!(
-        format!("{}", expected),
-        format!("{}", f(input.to_string(), true))
+        expected.into(),
+        f(input.to_string(), &Visualizer::new(Stdout))
+            .into_answer()
+            .unwrap()
     );
 }
 
-pub fn dotest2<F, R>(expected: R, input: &str, f: F, msg: &str)
+pub fn dotest2<F, R, E>(expected: E, input: &str, f: F, msg: &str)
 where
-    F: FnOnce(String, bool) -> Box<dyn Display>,
-    R: Display,
+    F: FnOnce(String, &Visualizer) -> R,
+    R: IntoAnswer,
+    E: Into<Answer>,
 {
+    let actual = f(input.to_string(), &Visualizer::new(Stdout))
+        .into_answer()
+        .unwrap_or_else(|e| panic!("error from {}: {:#}", msg, e));
     assert_eq!(
-        format!("{}", expected),
-        format!("{}", f(input.to_string(), true)),
+        expected.into(),
+        actual,
         "expected vs actual result from {}",
         msg
     );
 }
 
+// Runs `f` on each saved example that has an expected answer for `part`.
+// Examples are saved by 'cargo run YEAR/DAY --fetch-examples'. Parts that
+// aren't written yet are skipped.
+pub fn dotest_examples<F, R>(year: i32, day: u32, part: u8, f: F)
+where
+    F: Fn(String, &Visualizer) -> R,
+    R: IntoAnswer,
+{
+    let examples = crate::examples::load_examples(year, day).unwrap();
+    for (i, example) in examples.iter().enumerate() {
+        let Some(expected) = example.meta.expected(part) else {
+            continue;
+        };
+        let msg = format!("part {} with example {}", part, i + 1);
+        let actual = f(example.input.clone(), &Visualizer::off())
+            .into_answer()
+            .unwrap_or_else(|e| panic!("error from {}: {:#}", msg, e));
+        if !actual.is_unsolved() {
+            assert_eq!(Answer::parse(expected), actual, "{}", msg);
+        }
+    }
+}
+
+// Test macro example:
+//   aoc_examples!(2023, 1, 1 => part1, 2 => part2);
+// result:
+//   #[test]
+//   fn saved_examples() {
+//     dotest_examples(2023, 1, 1, super::part1);
+//     dotest_examples(2023, 1, 2, super::part2);
+//   }
+macro_rules! aoc_examples {
+    ($year:expr, $day:expr, $($part:expr => $f:ident),*) => {
+        #[test]
+        fn saved_examples() {
+            $(crate::test::dotest_examples($year, $day, $part, super::$f);)*
+        }
+    };
+}
+
+pub(crate) use aoc_examples;
+
 // Test macro examples:
 //   aoc_test!(example, "input", part1 => 7, part2 => 14);
 // result:
@@ -1,27 +1,138 @@
-use std::path::PathBuf;
+use std::{fs, io::ErrorKind, path::PathBuf};
 
-pub fn set_token(token: String) -> anyhow::Result<()> {
-    let cfg_file = token_path()?;
+use crate::profile::Profile;
+
+pub fn set_token(profile: &Profile, token: String) -> anyhow::Result<()> {
+    let cfg_file = token_path(profile)?;
     let cfg_dir = cfg_file
         .parent()
         .ok_or_else(|| anyhow::anyhow!("expected {cfg_file:?} to be in a directory"))?;
     std::fs::create_dir_all(cfg_dir)?;
-    std::fs::write(&cfg_file, token)
+    for problem in token_problems(&token) {
+        eprintln!("warning: the token {problem}");
+    }
+    write_private(&cfg_file, &token)
         .or_else(|e| anyhow::bail!("error writing token to {cfg_file:?}: {e:?}"))?;
     println!("wrote token to {:?}", cfg_file);
     Ok(())
 }
 
-pub fn get_token() -> anyhow::Result<String> {
-    let cfg_file = token_path()?;
+// Only the owner can read the token.
+#[cfg(unix)]
+fn write_private(path: &PathBuf, contents: &str) -> std::io::Result<()> {
+    use std::{io::Write, os::unix::fs::OpenOptionsExt, os::unix::fs::PermissionsExt};
+    let mut f = fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)?;
+    // The mode only applies to new files.
+    f.set_permissions(fs::Permissions::from_mode(0o600))?;
+    f.write_all(contents.as_bytes())
+}
+
+#[cfg(not(unix))]
+fn write_private(path: &PathBuf, contents: &str) -> std::io::Result<()> {
+    fs::write(path, contents)
+}
+
+// Mistakes that are easy to make when copying the cookie out of a browser.
+pub fn token_problems(token: &str) -> Vec<&'static str> {
+    let mut problems = Vec::new();
+    if token.trim() != token {
+        problems.push("has whitespace at the start or end");
+    }
+    if token.trim_start().starts_with("session=") {
+        problems.push("starts with 'session=', which isn't part of the cookie's value");
+    }
+    if token.trim().is_empty() {
+        problems.push("is empty");
+    }
+    problems
+}
+
+// AOC_SESSION, if it's set, is used instead of the default profile's saved
+// token. Named profiles always use their own.
+pub fn get_token(profile: &Profile) -> anyhow::Result<String> {
+    match env_token(profile) {
+        Some(token) => Ok(token),
+        None => get_saved_token(profile),
+    }
+}
+
+// The token from AOC_SESSION, if it's the one to use for the profile.
+pub fn env_token(profile: &Profile) -> Option<String> {
+    if !profile.is_default() {
+        return None;
+    }
+    std::env::var("AOC_SESSION").ok().filter(|t| !t.is_empty())
+}
+
+pub fn get_saved_token(profile: &Profile) -> anyhow::Result<String> {
+    let cfg_file = token_path(profile)?;
     std::fs::read_to_string(&cfg_file)
         .or_else(|e| anyhow::bail!("error reading token from {cfg_file:?}: {e:?}"))
 }
 
-fn token_path() -> anyhow::Result<PathBuf> {
+// Every profile with a token: a saved one, or AOC_SESSION for the default.
+pub fn profiles() -> anyhow::Result<Vec<Profile>> {
+    let mut profiles = Vec::new();
+    let default = Profile::new(None)?;
+    if env_token(&default).is_some() || token_path(&default)?.exists() {
+        profiles.push(default);
+    }
+    let dir = config_dir()?.join("tokens");
+    let entries = match std::fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(profiles),
+        Err(e) => return Err(e.into()),
+    };
+    let mut named = Vec::new();
+    for entry in entries {
+        if let Some(name) = entry?.file_name().to_str() {
+            if let Ok(profile) = Profile::new(Some(name)) {
+                named.push(profile);
+            }
+        }
+    }
+    named.sort();
+    profiles.extend(named);
+    Ok(profiles)
+}
+
+fn token_path(profile: &Profile) -> anyhow::Result<PathBuf> {
+    let mut cfg_file = config_dir()?;
+    if profile.is_default() {
+        cfg_file.push("token");
+    } else {
+        cfg_file.push("tokens");
+        cfg_file.push(profile.to_string());
+    }
+    Ok(cfg_file)
+}
+
+fn config_dir() -> anyhow::Result<PathBuf> {
     let app_dirs = platform_dirs::AppDirs::new(Some("advent-of-code"), false)
         .ok_or_else(|| anyhow::anyhow!("could not find advent-of-code app settings"))?;
-    let mut cfg_file = app_dirs.config_dir;
-    cfg_file.push("token");
-    Ok(cfg_file)
+    Ok(app_dirs.config_dir)
+}
+
+#[cfg(test)]
+mod test {
+    use super::token_problems;
+
+    #[test]
+    fn problems() {
+        assert!(token_problems("53616c7465645f5f").is_empty());
+        assert_eq!(
+            vec!["has whitespace at the start or end"],
+            token_problems("53616c7465645f5f\n")
+        );
+        assert_eq!(
+            vec!["starts with 'session=', which isn't part of the cookie's value"],
+            token_problems("session=53616c7465645f5f")
+        );
+        assert_eq!(vec!["is empty"], token_problems(""));
+    }
 }
@@ -0,0 +1,270 @@
+use std::{
+    fmt::Display,
+    fs,
+    io::Write,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+mod image;
+
+pub use image::{Image, Palette};
+
+// Solvers show their work through one of these. It's off unless
+// --visualize (or one of the other --vis options) is set, so anything
+// expensive to draw should check `on()` first.
+#[derive(Clone, Default)]
+pub struct Visualizer(Option<Arc<Mutex<Box<dyn Frontend>>>>);
+
+// A picture of a grid, one char per cell.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Frame {
+    // Shown above the grid, like "minute 5".
+    pub caption: String,
+    pub rows: Vec<String>,
+}
+
+pub trait Frontend: Send {
+    fn log(&mut self, line: &str);
+    fn frame(&mut self, frame: &Frame);
+    // Called once the solver is done.
+    fn finish(&mut self) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
+
+impl Visualizer {
+    pub fn off() -> Self {
+        Self(None)
+    }
+
+    pub fn new(frontend: impl Frontend + 'static) -> Self {
+        Self(Some(Arc::new(Mutex::new(Box::new(frontend)))))
+    }
+
+    pub fn on(&self) -> bool {
+        self.0.is_some()
+    }
+
+    pub fn log(&self, line: impl Display) {
+        if let Some(f) = &self.0 {
+            f.lock().unwrap().log(&line.to_string());
+        }
+    }
+
+    pub fn frame(&self, frame: Frame) {
+        if let Some(f) = &self.0 {
+            f.lock().unwrap().frame(&frame);
+        }
+    }
+
+    pub fn finish(&self) -> anyhow::Result<()> {
+        match &self.0 {
+            Some(f) => f.lock().unwrap().finish(),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Frame {
+    pub fn new(caption: impl Display, rows: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            caption: caption.to_string(),
+            rows: rows.into_iter().collect(),
+        }
+    }
+
+    // Draws the cells from (x0, y0) to (x1, y1), inclusive.
+    pub fn draw(
+        caption: impl Display,
+        (x0, y0): (i64, i64),
+        (x1, y1): (i64, i64),
+        cell: impl Fn(i64, i64) -> char,
+    ) -> Self {
+        Self::new(
+            caption,
+            (y0..=y1).map(|y| (x0..=x1).map(|x| cell(x, y)).collect()),
+        )
+    }
+}
+
+impl Display for Frame {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if !self.caption.is_empty() {
+            writeln!(f, "{}", self.caption)?;
+        }
+        for row in &self.rows {
+            writeln!(f, "{row}")?;
+        }
+        Ok(())
+    }
+}
+
+// Prints everything, as it happens.
+pub struct Stdout;
+
+impl Frontend for Stdout {
+    fn log(&mut self, line: &str) {
+        println!("{line}");
+    }
+
+    fn frame(&mut self, frame: &Frame) {
+        println!("{frame}");
+    }
+}
+
+// Redraws each frame in place, no faster than `fps`. Log lines are printed
+// below the frame.
+pub struct Animator {
+    interval: Duration,
+    last: Option<Instant>,
+}
+
+impl Animator {
+    pub fn new(fps: f64) -> Self {
+        Self {
+            interval: Duration::from_secs_f64(1.0 / fps),
+            last: None,
+        }
+    }
+}
+
+impl Frontend for Animator {
+    fn log(&mut self, line: &str) {
+        println!("{line}");
+    }
+
+    fn frame(&mut self, frame: &Frame) {
+        if let Some(last) = self.last {
+            let next = last + self.interval;
+            let now = Instant::now();
+            if next > now {
+                thread::sleep(next - now);
+            }
+        }
+        self.last = Some(Instant::now());
+        // Home, then clear the screen.
+        print!("\x1b[H\x1b[2J{frame}");
+        let _ = std::io::stdout().flush();
+    }
+}
+
+// Writes each frame to `{dir}/{n}.txt` and the log to `{dir}/log.txt`.
+pub struct Recorder {
+    dir: PathBuf,
+    frames: usize,
+    log: Vec<String>,
+    error: Option<std::io::Error>,
+}
+
+impl Recorder {
+    pub fn new(dir: impl Into<PathBuf>) -> anyhow::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)
+            .map_err(|e| anyhow::anyhow!("error creating {}: {e}", dir.display()))?;
+        Ok(Self {
+            dir,
+            frames: 0,
+            log: Vec::new(),
+            error: None,
+        })
+    }
+}
+
+impl Frontend for Recorder {
+    fn log(&mut self, line: &str) {
+        self.log.push(line.to_string());
+    }
+
+    fn frame(&mut self, frame: &Frame) {
+        self.frames += 1;
+        let file = self.dir.join(format!("{:05}.txt", self.frames));
+        if let Err(e) = fs::write(file, frame.to_string()) {
+            self.error.get_or_insert(e);
+        }
+    }
+
+    fn finish(&mut self) -> anyhow::Result<()> {
+        if let Some(e) = self.error.take() {
+            anyhow::bail!("error recording to {}: {e}", self.dir.display());
+        }
+        if !self.log.is_empty() {
+            let mut log = self.log.join("\n");
+            log.push('\n');
+            fs::write(self.dir.join("log.txt"), log)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::{Arc, Mutex};
+
+    use super::{Frame, Frontend, Recorder, Visualizer};
+
+    #[derive(Clone, Default)]
+    struct Capture(Arc<Mutex<Vec<String>>>);
+
+    impl Frontend for Capture {
+        fn log(&mut self, line: &str) {
+            self.0.lock().unwrap().push(format!("log {line}"));
+        }
+
+        fn frame(&mut self, frame: &Frame) {
+            self.0.lock().unwrap().push(format!("frame {frame}"));
+        }
+    }
+
+    #[test]
+    fn off() {
+        let vis = Visualizer::off();
+        assert!(!vis.on());
+        vis.log("nobody sees this");
+        vis.finish().unwrap();
+    }
+
+    #[test]
+    fn sends_to_frontend() {
+        let capture = Capture::default();
+        let vis = Visualizer::new(capture.clone());
+        assert!(vis.on());
+        vis.log(format_args!("step {}", 1));
+        vis.frame(Frame::draw("t=1", (0, 0), (2, 1), |x, y| {
+            if x == y {
+                '#'
+            } else {
+                '.'
+            }
+        }));
+        assert_eq!(
+            vec!["log step 1", "frame t=1\n#..\n.#.\n"],
+            *capture.0.lock().unwrap()
+        );
+    }
+
+    #[test]
+    fn records_frames() {
+        let dir = std::env::temp_dir().join(format!("aoc-vis-test-{}", std::process::id()));
+        let vis = Visualizer::new(Recorder::new(&dir).unwrap());
+        vis.frame(Frame::new("", ["ab".to_string()]));
+        vis.frame(Frame::new("second", ["cd".to_string()]));
+        vis.log("done");
+        vis.finish().unwrap();
+        assert_eq!(
+            "ab\n",
+            std::fs::read_to_string(dir.join("00001.txt")).unwrap()
+        );
+        assert_eq!(
+            "second\ncd\n",
+            std::fs::read_to_string(dir.join("00002.txt")).unwrap()
+        );
+        assert_eq!(
+            "done\n",
+            std::fs::read_to_string(dir.join("log.txt")).unwrap()
+        );
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
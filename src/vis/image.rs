@@ -0,0 +1,391 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs::File,
+    io::{BufWriter, Seek, SeekFrom, Write},
+    path::PathBuf,
+};
+
+use anyhow::{anyhow, bail, ensure, Context};
+
+use super::{Frame, Frontend};
+
+// The colour for each kind of cell. Cells it doesn't know get a colour made
+// up from the char, so they're at least different from each other.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Palette(HashMap<char, [u8; 3]>);
+
+const BACKGROUND: [u8; 3] = [15, 15, 35];
+
+impl Default for Palette {
+    fn default() -> Self {
+        let wall = [150, 150, 160];
+        let pipe = [210, 210, 210];
+        let blizzard = [110, 170, 255];
+        let colors = [
+            (' ', BACKGROUND),
+            ('.', BACKGROUND),
+            ('#', wall),
+            ('|', wall),
+            ('-', wall),
+            ('+', wall),
+            ('o', [230, 190, 90]),
+            ('~', [60, 120, 230]),
+            ('@', [255, 110, 60]),
+            ('E', [255, 110, 60]),
+            ('S', [255, 110, 60]),
+            ('L', pipe),
+            ('J', pipe),
+            ('7', pipe),
+            ('F', pipe),
+            ('I', [90, 200, 110]),
+            ('O', [230, 70, 70]),
+            ('^', blizzard),
+            ('v', blizzard),
+            ('<', blizzard),
+            ('>', blizzard),
+        ];
+        Self(colors.into_iter().collect())
+    }
+}
+
+impl Palette {
+    // Changes the default colours with a list like "#=ffffff,.=000000".
+    pub fn parse(spec: &str) -> anyhow::Result<Self> {
+        let mut palette = Self::default();
+        for item in spec.split(',').filter(|item| !item.is_empty()) {
+            let mut chars = item.chars();
+            let (Some(cell), Some('=')) = (chars.next(), chars.next()) else {
+                bail!("invalid palette entry {item:?}: expected CHAR=RRGGBB");
+            };
+            let hex = chars.as_str();
+            let rgb = hex::decode(hex)
+                .ok()
+                .and_then(|rgb| <[u8; 3]>::try_from(rgb).ok())
+                .ok_or_else(|| anyhow!("invalid palette entry {item:?}: {hex:?} isn't RRGGBB"))?;
+            palette.0.insert(cell, rgb);
+        }
+        Ok(palette)
+    }
+
+    pub fn color(&self, cell: char) -> [u8; 3] {
+        if let Some(&rgb) = self.0.get(&cell) {
+            return rgb;
+        }
+        // Spread the chars around the colour wheel.
+        let hue = (cell as u32).wrapping_mul(137) % 360;
+        let (sector, offset) = (hue / 60, (hue % 60 * 255 / 60) as u8);
+        let (hi, lo) = (230, 60);
+        let up = lo + (offset as u32 * (hi - lo) as u32 / 255) as u8;
+        let down = hi - (up - lo);
+        match sector {
+            0 => [hi, up, lo],
+            1 => [down, hi, lo],
+            2 => [lo, hi, up],
+            3 => [lo, down, hi],
+            4 => [up, lo, hi],
+            _ => [hi, lo, down],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Format {
+    Png,
+    Gif,
+}
+
+// Saves the frames as an animated GIF, or the last frame as a PNG. Each cell
+// is a `scale` by `scale` square, and only every `skip`th frame is kept (plus
+// the last one). Captions aren't drawn. GIF frames are written as they come,
+// so only the latest frame is ever kept.
+pub struct Image {
+    path: PathBuf,
+    format: Format,
+    palette: Palette,
+    scale: usize,
+    skip: usize,
+    // How long each GIF frame is shown, in hundredths of a second.
+    delay: u16,
+    seen: usize,
+    // The latest frame, if it hasn't been written yet.
+    last: Option<Frame>,
+    // Started with the first GIF frame.
+    gif: Option<Gif>,
+    // The first thing that went wrong while writing frames.
+    error: Option<anyhow::Error>,
+}
+
+struct Gif {
+    encoder: gif::Encoder<BufWriter<File>>,
+    // The size of the picture so far, in pixels. It grows to fit bigger
+    // frames, and smaller ones are padded.
+    size: (usize, usize),
+}
+
+impl Image {
+    pub fn new(
+        path: impl Into<PathBuf>,
+        palette: Palette,
+        scale: usize,
+        skip: usize,
+        fps: f64,
+    ) -> anyhow::Result<Self> {
+        let path = path.into();
+        let format = match path.extension().and_then(|e| e.to_str()) {
+            Some(e) if e.eq_ignore_ascii_case("png") => Format::Png,
+            Some(e) if e.eq_ignore_ascii_case("gif") => Format::Gif,
+            _ => bail!("{} needs to end in .png or .gif", path.display()),
+        };
+        ensure!(scale > 0, "the scale needs to be at least 1");
+        ensure!(skip > 0, "the frame skip needs to be at least 1");
+        ensure!(fps > 0.0, "the frame rate needs to be more than 0");
+        Ok(Self {
+            path,
+            format,
+            palette,
+            scale,
+            skip,
+            delay: (100.0 / fps).round().clamp(1.0, u16::MAX as f64) as u16,
+            seen: 0,
+            last: None,
+            gif: None,
+            error: None,
+        })
+    }
+
+    // The size of the frame, in pixels.
+    fn size(&self, frame: &Frame) -> (usize, usize) {
+        let cols = frame.rows.iter().map(|r| r.chars().count()).max();
+        (
+            cols.unwrap_or(0) * self.scale,
+            frame.rows.len() * self.scale,
+        )
+    }
+
+    // Numbers each kind of cell in the frame, for indexing into its colours.
+    // The background is 0.
+    fn index(frame: &Frame) -> anyhow::Result<BTreeMap<char, u8>> {
+        let mut index = BTreeMap::from([(' ', 0)]);
+        for c in frame.rows.iter().flat_map(|r| r.chars()) {
+            let n = index.len();
+            ensure!(
+                n < 256 || index.contains_key(&c),
+                "there are more than 256 kinds of cell, but an image can only have 256 colours"
+            );
+            index.entry(c).or_insert(n as u8);
+        }
+        Ok(index)
+    }
+
+    fn colors(&self, index: &BTreeMap<char, u8>) -> Vec<u8> {
+        let mut colors = vec![[0; 3]; index.len()];
+        for (&c, &i) in index {
+            colors[i as usize] = self.palette.color(c);
+        }
+        colors.concat()
+    }
+
+    // Frames smaller than the picture are padded with the background.
+    fn render(
+        &self,
+        frame: &Frame,
+        index: &BTreeMap<char, u8>,
+        (width, height): (usize, usize),
+    ) -> Vec<u8> {
+        let mut pixels = vec![0; width * height];
+        for (y, row) in frame.rows.iter().enumerate() {
+            for (x, c) in row.chars().enumerate() {
+                for dy in 0..self.scale {
+                    let start = (y * self.scale + dy) * width + x * self.scale;
+                    pixels[start..start + self.scale].fill(index[&c]);
+                }
+            }
+        }
+        pixels
+    }
+
+    fn write_png(&self, frame: &Frame) -> anyhow::Result<()> {
+        let (width, height) = self.size(frame);
+        let index = Self::index(frame)?;
+        let out = BufWriter::new(File::create(&self.path)?);
+        let mut encoder = png::Encoder::new(out, width as u32, height as u32);
+        encoder.set_color(png::ColorType::Indexed);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.set_palette(self.colors(&index));
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.render(frame, &index, (width, height)))?;
+        writer.finish()?;
+        Ok(())
+    }
+
+    // Each frame has its own colours, since the cells in later frames
+    // aren't known yet.
+    fn write_gif_frame(&mut self, frame: &Frame) -> anyhow::Result<()> {
+        let (cols, rows) = self.size(frame);
+        let size = match &self.gif {
+            Some(gif) => (gif.size.0.max(cols), gif.size.1.max(rows)),
+            None => (cols, rows),
+        };
+        let (Ok(w), Ok(h)) = (u16::try_from(size.0), u16::try_from(size.1)) else {
+            bail!(
+                "{}x{} is too big for a GIF, try a smaller scale",
+                size.0,
+                size.1
+            );
+        };
+        let index = Self::index(frame)?;
+        let mut pixels =
+            gif::Frame::from_indexed_pixels(w, h, self.render(frame, &index, size), None);
+        pixels.palette = Some(self.colors(&index));
+        pixels.delay = self.delay;
+        let gif = match &mut self.gif {
+            Some(gif) => gif,
+            None => {
+                let out = BufWriter::new(File::create(&self.path)?);
+                let mut encoder = gif::Encoder::new(out, w, h, &BACKGROUND)?;
+                encoder.set_repeat(gif::Repeat::Infinite)?;
+                self.gif.insert(Gif { encoder, size })
+            }
+        };
+        gif.size = size;
+        gif.encoder.write_frame(&pixels)?;
+        Ok(())
+    }
+
+    // The picture's size is at the start of the file, and it's only known
+    // now that every frame has been seen.
+    fn finish_gif(gif: Gif) -> anyhow::Result<()> {
+        let mut file = gif.encoder.into_inner()?.into_inner()?;
+        file.seek(SeekFrom::Start(6))?;
+        for n in [gif.size.0, gif.size.1] {
+            file.write_all(&(n as u16).to_le_bytes())?;
+        }
+        Ok(())
+    }
+
+    fn save(&mut self) -> anyhow::Result<()> {
+        if let Some(e) = self.error.take() {
+            return Err(e);
+        }
+        match self.format {
+            Format::Png => {
+                let frame = self.last.take().context("there's nothing to draw")?;
+                self.write_png(&frame)
+            }
+            Format::Gif => {
+                if let Some(last) = self.last.take() {
+                    self.write_gif_frame(&last)?;
+                }
+                Self::finish_gif(self.gif.take().context("there's nothing to draw")?)
+            }
+        }
+    }
+}
+
+impl Frontend for Image {
+    fn log(&mut self, _line: &str) {}
+
+    fn frame(&mut self, frame: &Frame) {
+        let keep = self.seen.is_multiple_of(self.skip);
+        self.seen += 1;
+        if self.format == Format::Png || !keep {
+            self.last = Some(frame.clone());
+            return;
+        }
+        self.last = None;
+        if self.error.is_none() {
+            if let Err(e) = self.write_gif_frame(frame) {
+                self.error = Some(e);
+            }
+        }
+    }
+
+    fn finish(&mut self) -> anyhow::Result<()> {
+        self.save()
+            .with_context(|| format!("error saving {}", self.path.display()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Image, Palette};
+    use crate::vis::{Frame, Visualizer};
+
+    fn frame(rows: &[&str]) -> Frame {
+        Frame::new("", rows.iter().map(|r| r.to_string()))
+    }
+
+    #[test]
+    fn palette() {
+        let palette = Palette::parse("#=ff0000,x=00ff80").unwrap();
+        assert_eq!([255, 0, 0], palette.color('#'));
+        assert_eq!([0, 255, 128], palette.color('x'));
+        assert_eq!(Palette::default().color('.'), palette.color('.'));
+        assert_ne!(palette.color('a'), palette.color('b'));
+        assert_eq!(
+            "invalid palette entry \"#ff0000\": expected CHAR=RRGGBB",
+            Palette::parse("#ff0000").unwrap_err().to_string()
+        );
+        assert_eq!(
+            "invalid palette entry \"#=red\": \"red\" isn't RRGGBB",
+            Palette::parse("#=red").unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn gif() {
+        let path = std::env::temp_dir().join(format!("aoc-image-test-{}.gif", std::process::id()));
+        let vis = Visualizer::new(Image::new(&path, Palette::default(), 2, 2, 25.0).unwrap());
+        vis.frame(frame(&["#.", ".#"]));
+        vis.frame(frame(&["skipped"]));
+        vis.frame(frame(&["o#o"]));
+        vis.frame(frame(&["##", "oo"]));
+        vis.finish().unwrap();
+
+        let mut options = gif::DecodeOptions::new();
+        options.set_color_output(gif::ColorOutput::Indexed);
+        let mut decoder = options
+            .read_info(std::fs::File::open(&path).unwrap())
+            .unwrap();
+        assert_eq!((6, 4), (decoder.width(), decoder.height()));
+        let mut widths = Vec::new();
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            assert_eq!(4, frame.delay);
+            widths.push(frame.width);
+        }
+        // The first, third and last. Each is as wide as the widest so far.
+        assert_eq!(vec![4, 6, 6], widths);
+        std::fs::remove_file(&path).unwrap();
+
+        let vis = Visualizer::new(Image::new(&path, Palette::default(), 1, 1, 1.0).unwrap());
+        assert!(vis
+            .finish()
+            .unwrap_err()
+            .root_cause()
+            .to_string()
+            .contains("nothing to draw"));
+    }
+
+    #[test]
+    fn png() {
+        let path = std::env::temp_dir().join(format!("aoc-image-test-{}.png", std::process::id()));
+        let image = Image::new(&path, Palette::default(), 3, 1, 10.0).unwrap();
+        let vis = Visualizer::new(image);
+        vis.frame(frame(&["#"]));
+        vis.frame(frame(&["#o", "o#"]));
+        vis.finish().unwrap();
+
+        let decoder =
+            png::Decoder::new(std::io::BufReader::new(std::fs::File::open(&path).unwrap()));
+        let reader = decoder.read_info().unwrap();
+        let info = reader.info();
+        assert_eq!((6, 6), (info.width, info.height));
+        assert_eq!(
+            [Palette::default().color(' '), Palette::default().color('#')].concat(),
+            info.palette.as_deref().unwrap()[..6]
+        );
+        std::fs::remove_file(path).unwrap();
+
+        assert!(Image::new("out.jpg", Palette::default(), 1, 1, 1.0).is_err());
+    }
+}
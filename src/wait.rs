@@ -0,0 +1,59 @@
+use std::{
+    io::{IsTerminal, Write},
+    thread,
+    time::Duration,
+};
+
+use chrono::{DateTime, FixedOffset};
+
+use crate::curday::aoc_now;
+
+// Shows a countdown on stderr until `unlock`, plus a moment so the server
+// agrees that the puzzle is out.
+pub fn wait_for_unlock(unlock: DateTime<FixedOffset>, what: &str) {
+    let unlock = unlock + chrono::Duration::seconds(1);
+    let live = std::io::stderr().is_terminal();
+    let mut printed = false;
+    while let Ok(left) = (unlock - aoc_now()).to_std() {
+        if live || !printed {
+            eprint!("\r{what} unlocks in {}  ", countdown(left));
+            if !live {
+                eprintln!();
+            }
+            let _ = std::io::stderr().flush();
+            printed = true;
+        }
+        thread::sleep(left.min(Duration::from_secs(1)));
+    }
+    if live && printed {
+        eprintln!();
+    }
+}
+
+fn countdown(d: Duration) -> String {
+    let secs = d.as_secs();
+    match secs / 86400 {
+        0 => format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60),
+        days => format!(
+            "{}d {:02}:{:02}:{:02}",
+            days,
+            secs / 3600 % 24,
+            secs / 60 % 60,
+            secs % 60
+        ),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::countdown;
+
+    #[test]
+    fn format_countdown() {
+        assert_eq!("00:00:09", countdown(Duration::from_secs(9)));
+        assert_eq!("01:02:03", countdown(Duration::from_secs(3723)));
+        assert_eq!("2d 00:00:01", countdown(Duration::from_secs(2 * 86400 + 1)));
+    }
+}
@@ -0,0 +1,93 @@
+use std::fmt::Display;
+
+use regex::Regex;
+
+use crate::{http::http, puzzle::unescape};
+
+#[derive(Debug, PartialEq)]
+pub struct User {
+    pub name: String,
+    pub supporter: bool,
+    pub stars: Option<u32>,
+}
+
+impl Display for User {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)?;
+        if self.supporter {
+            write!(f, " (AoC++)")?;
+        }
+        Ok(())
+    }
+}
+
+// Loads a page that shows who's logged in. An expired or made-up session
+// gets the page anyway, just without a user.
+pub fn whoami(base_url: &str, token: &str, year: i32) -> anyhow::Result<User> {
+    let url = format!("{}/{}", base_url, year);
+    let html = http().get(&url, token)?;
+    parse_user(&html).ok_or_else(|| {
+        anyhow::anyhow!(
+            "not logged in: the session token is invalid or expired, set a new one with --set-token"
+        )
+    })
+}
+
+fn parse_user(html: &str) -> Option<User> {
+    let user = Regex::new(r#"(?s)<div class="user">(.*?)</div>"#)
+        .unwrap()
+        .captures(html)?;
+    let user = user.get(1).unwrap().as_str();
+    let name = unescape(user.split('<').next().unwrap_or("").trim());
+    let stars = Regex::new(r#"<span class="star-count">(\d+)\*</span>"#)
+        .unwrap()
+        .captures(user)
+        .and_then(|c| c[1].parse().ok());
+    Some(User {
+        name,
+        supporter: user.contains(r#"class="supporter-badge""#),
+        stars,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_user, User};
+
+    fn page(header: &str) -> String {
+        format!(
+            r#"<html><body><header><div><h1 class="title-global"><a href="/">Advent of Code</a></h1><nav><ul><li><a href="/2023/about">[About]</a></li></ul></nav>{header}</div></header><main></main></body></html>"#
+        )
+    }
+
+    #[test]
+    fn supporter() {
+        let html = page(
+            r#"<div class="user">Jane &amp; Co <a href="/2023/support" class="supporter-badge" title="Advent of Code Supporter">(AoC++)</a> <span class="star-count">34*</span></div>"#,
+        );
+        assert_eq!(
+            Some(User {
+                name: "Jane & Co".to_string(),
+                supporter: true,
+                stars: Some(34),
+            }),
+            parse_user(&html)
+        );
+    }
+
+    #[test]
+    fn anonymous() {
+        let html = page(r#"<div class="user">(anonymous user #123456)</div>"#);
+        let user = parse_user(&html).unwrap();
+        assert_eq!("(anonymous user #123456)", user.name);
+        assert!(!user.supporter);
+        assert_eq!(None, user.stars);
+        assert_eq!("(anonymous user #123456)", user.to_string());
+    }
+
+    #[test]
+    fn logged_out() {
+        let html = page(r#"<div><a href="/2023/auth/login">[Log In]</a></div>"#);
+        assert_eq!(None, parse_user(&html));
+    }
+}
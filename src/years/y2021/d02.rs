@@ -1,23 +1,26 @@
-use std::fmt::Display;
+use crate::answer::Answer;
+use crate::vis::Visualizer;
 
-pub fn part1(input: String, _vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2021, day = 2, part = 1)]
+pub fn part1(input: String, _vis: &Visualizer) -> anyhow::Result<Answer> {
     let mut depth = 0;
     let mut pos = 0;
-    for c in parse(&input) {
+    for c in parse(&input)? {
         match c {
             Command::Forward(n) => pos += n,
             Command::Down(n) => depth += n,
             Command::Up(n) => depth -= n,
         };
     }
-    Box::new(depth * pos)
+    Ok((depth * pos).into())
 }
 
-pub fn part2(input: String, _vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2021, day = 2, part = 2)]
+pub fn part2(input: String, _vis: &Visualizer) -> anyhow::Result<Answer> {
     let mut depth = 0;
     let mut pos = 0;
     let mut aim = 0;
-    for c in parse(&input) {
+    for c in parse(&input)? {
         match c {
             Command::Forward(n) => {
                 pos += n;
@@ -27,23 +30,24 @@ pub fn part2(input: String, _vis: bool) -> Box<dyn Display> {
             Command::Up(n) => aim -= n,
         };
     }
-    Box::new(depth * pos)
+    Ok((depth * pos).into())
 }
 
-fn parse(s: &str) -> Vec<Command> {
+fn parse(s: &str) -> anyhow::Result<Vec<Command>> {
     s.lines().map(parse_line).collect()
 }
 
-fn parse_line(s: &str) -> Command {
-    let mut parts = s.split(' ');
-    let cmd = parts.next().unwrap();
-    let val = parts.next().unwrap().parse().unwrap();
-    match cmd {
+fn parse_line(s: &str) -> anyhow::Result<Command> {
+    let Some((cmd, val)) = s.split_once(' ') else {
+        anyhow::bail!("expected 'COMMAND N' but got '{s}'");
+    };
+    let val = val.parse()?;
+    Ok(match cmd {
         "forward" => Command::Forward(val),
         "up" => Command::Up(val),
         "down" => Command::Down(val),
-        _ => panic!("illegal command '{s}'"),
-    }
+        _ => anyhow::bail!("illegal command '{s}'"),
+    })
 }
 
 enum Command {
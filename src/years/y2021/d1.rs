@@ -1,13 +1,15 @@
 use std::fmt::Display;
 
+use crate::vis::Visualizer;
+
 #[aoc(year = 2021, day = 1, part = 1)]
-pub fn part1(input: String, _: bool) -> Box<dyn Display> {
+pub fn part1(input: String, _: &Visualizer) -> Box<dyn Display> {
     let values: Vec<u32> = input.lines().map(|l| l.trim().parse().unwrap()).collect();
     Box::new(values.windows(2).filter(|x| x[0] < x[1]).count())
 }
 
 #[aoc(year = 2021, day = 1, part = 2)]
-pub fn part2(input: String, _: bool) -> Box<dyn Display> {
+pub fn part2(input: String, _: &Visualizer) -> Box<dyn Display> {
     let values: Vec<u32> = input.lines().map(|l| l.trim().parse().unwrap()).collect();
     let sums: Vec<u32> = values.windows(3).map(|x| x[0] + x[1] + x[2]).collect();
     Box::new(sums.windows(2).filter(|x| x[0] < x[1]).count())
@@ -1,6 +1,9 @@
 use std::{collections::BinaryHeap, fmt::Display};
 
-pub fn part1(input: String, _: bool) -> Box<dyn Display> {
+use crate::vis::Visualizer;
+
+#[aoc(year = 2022, day = 1, part = 1)]
+pub fn part1(input: String, _: &Visualizer) -> Box<dyn Display> {
     Box::new(sum_first_n(groups(input), 1))
 }
 
@@ -23,7 +26,8 @@ fn sum_first_n(mut groups: BinaryHeap<u32>, n: usize) -> u32 {
     res
 }
 
-pub fn part2(input: String, _: bool) -> Box<dyn Display> {
+#[aoc(year = 2022, day = 1, part = 2)]
+pub fn part2(input: String, _: &Visualizer) -> Box<dyn Display> {
     Box::new(sum_first_n(groups(input), 3))
 }
 
@@ -1,6 +1,9 @@
 use std::fmt::Display;
 
-pub fn part1(input: String, _vis: bool) -> Box<dyn Display> {
+use crate::vis::Visualizer;
+
+#[aoc(year = 2022, day = 10, part = 1)]
+pub fn part1(input: String, _vis: &Visualizer) -> Box<dyn Display> {
     let mut cycles = 0;
     let mut x = 1;
     let mut signal = 0;
@@ -21,7 +24,8 @@ pub fn part1(input: String, _vis: bool) -> Box<dyn Display> {
     Box::new(signal)
 }
 
-pub fn part2(input: String, _vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2022, day = 10, part = 2)]
+pub fn part2(input: String, _vis: &Visualizer) -> Box<dyn Display> {
     let mut cycles = 0;
     let mut sprite_pos = 0;
     let mut res = "\n".to_string();
@@ -1,10 +1,14 @@
 use std::fmt::Display;
 
-pub fn part1(input: String, _vis: bool) -> Box<dyn Display> {
+use crate::vis::Visualizer;
+
+#[aoc(year = 2022, day = 11, part = 1)]
+pub fn part1(input: String, _vis: &Visualizer) -> Box<dyn Display> {
     Box::new(solve(input, 20, 3))
 }
 
-pub fn part2(input: String, _vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2022, day = 11, part = 2)]
+pub fn part2(input: String, _vis: &Visualizer) -> Box<dyn Display> {
     Box::new(solve(input, 10000, 1))
 }
 
@@ -20,7 +24,7 @@ fn solve(input: String, iterations: usize, backoff: Worry) -> usize {
     a * b
 }
 
-fn go_around(monkeys: &mut Vec<Monkey>, backoff: Worry) {
+fn go_around(monkeys: &mut [Monkey], backoff: Worry) {
     let common: Worry = monkeys.iter().map(|m| m.test).product();
     for i in 0..monkeys.len() {
         let nitems = monkeys[i].items.len();
@@ -55,7 +59,7 @@ impl Monkey {
     }
 
     fn do_test(&self, item: Worry) -> Action {
-        if item % self.test == 0 {
+        if item.is_multiple_of(self.test) {
             self.if_true
         } else {
             self.if_false
@@ -1,114 +1,67 @@
-use std::{cmp::Ordering, collections::BinaryHeap, fmt::Display};
+use std::{cmp::Ordering, fmt::Display};
 
-pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
-    Box::new(solve(input, vis, |map| vec![find_start(map)]))
+use crate::{
+    grid::{Grid, Pos},
+    search,
+    vis::Visualizer,
+};
+
+#[aoc(year = 2022, day = 12, part = 1)]
+pub fn part1(input: String, vis: &Visualizer) -> Box<dyn Display> {
+    Box::new(solve(input, vis, |map| {
+        vec![map.find(|c| *c == b'S').unwrap()]
+    }))
 }
 
-pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2022, day = 12, part = 2)]
+pub fn part2(input: String, vis: &Visualizer) -> Box<dyn Display> {
     Box::new(solve(input, vis, |map| {
-        let mut res = Vec::new();
-        for (i, row) in map.iter().enumerate() {
-            for (j, c) in row.iter().enumerate() {
-                if *c == b'S' || *c == b'a' {
-                    res.push((i, j));
-                }
-            }
-        }
-        res
+        map.iter()
+            .filter(|(_, c)| **c == b'S' || **c == b'a')
+            .map(|(pos, _)| pos)
+            .collect()
     }))
 }
 
-fn solve<F>(input: String, vis: bool, get_starts: F) -> usize
+fn solve<F>(input: String, vis: &Visualizer, get_starts: F) -> usize
 where
-    F: FnOnce(&[Vec<u8>]) -> Vec<(usize, usize)>,
+    F: FnOnce(&Grid<u8>) -> Vec<Pos>,
 {
-    let map: Vec<Vec<u8>> = input.lines().map(|s| s.as_bytes().to_vec()).collect();
+    let map = Grid::parse(&input, |c| c as u8);
 
-    let mut dist: Vec<Vec<usize>> = map
-        .iter()
-        .map(|row| row.iter().map(|_| usize::MAX).collect())
-        .collect();
-    let mut heap = BinaryHeap::new();
+    let starts = get_starts(&map);
+    let moves = |&pos: &Pos| {
+        let map = &map;
+        map.neighbors4(pos)
+            .filter(move |&next| can_move(map[pos], map[next]))
+    };
+    let found = search::bfs(starts.clone(), |pos| *pos, moves, |&pos| map[pos] == b'E').unwrap();
 
-    for (row, col) in get_starts(&map) {
-        dist[row][col] = 0;
-        heap.push(State { row, col, cost: 0 });
-    }
-
-    while let Some(State { cost, row, col }) = heap.pop() {
-        if vis {
-            println!("{:?}", dist);
-            println!("({},{}) @ {}", row, col, cost);
-        }
-        if map[row][col] == b'E' {
-            return cost;
-        }
-        if cost > dist[row][col] {
-            continue;
-        }
-        let next_cost = cost + 1;
-        let cur = map[row][col];
-        if row > 0 && can_move(cur, map[row - 1][col]) && next_cost < dist[row - 1][col] {
-            if vis {
-                println!("can move to ({},{})", row - 1, col);
-            }
-            dist[row - 1][col] = next_cost;
-            heap.push(State {
-                cost: next_cost,
-                row: row - 1,
-                col,
-            });
-        }
-        if col > 0 && can_move(cur, map[row][col - 1]) && next_cost < dist[row][col - 1] {
-            if vis {
-                println!("can move to ({},{})", row, col - 1);
-            }
-            dist[row][col - 1] = next_cost;
-            heap.push(State {
-                cost: next_cost,
-                row,
-                col: col - 1,
-            });
-        }
-        if row + 1 < map.len() && can_move(cur, map[row + 1][col]) && next_cost < dist[row + 1][col]
-        {
-            if vis {
-                println!("can move to ({},{})", row + 1, col);
-            }
-            dist[row + 1][col] = next_cost;
-            heap.push(State {
-                cost: next_cost,
-                row: row + 1,
-                col,
-            });
-        }
-        if let Some(c) = map[row].get(col + 1) {
-            if can_move(cur, *c) && next_cost < dist[row][col + 1] {
-                if vis {
-                    println!("can move to ({},{})", row, col + 1);
-                }
-                dist[row][col + 1] = next_cost;
-                heap.push(State {
-                    cost: next_cost,
-                    row,
-                    col: col + 1,
-                });
-            }
+    if vis.on() {
+        let (_, ways) = search::count_shortest_paths(
+            starts,
+            |pos| *pos,
+            |pos| moves(pos).map(|next| (next, 1)),
+            |&pos| map[pos] == b'E',
+        )
+        .unwrap();
+        vis.log(format!(
+            "{} steps, after looking at {} squares; {} ways to go that far",
+            found.cost, found.expanded, ways
+        ));
+        let mut shown = map.map(|&c| c as char);
+        for w in found.path.windows(2) {
+            let ((r0, c0), (r1, c1)) = (w[0], w[1]);
+            shown[w[0]] = match (r1.cmp(&r0), c1.cmp(&c0)) {
+                (Ordering::Less, _) => '^',
+                (Ordering::Greater, _) => 'v',
+                (_, Ordering::Less) => '<',
+                _ => '>',
+            };
         }
+        vis.frame(shown.frame("the way up"));
     }
-
-    unreachable!()
-}
-
-fn find_start(map: &[Vec<u8>]) -> (usize, usize) {
-    for (i, row) in map.iter().enumerate() {
-        for (j, c) in row.iter().enumerate() {
-            if *c == b'S' {
-                return (i, j);
-            }
-        }
-    }
-    unreachable!()
+    found.cost
 }
 
 fn can_move(mut from: u8, mut to: u8) -> bool {
@@ -121,29 +74,6 @@ fn can_move(mut from: u8, mut to: u8) -> bool {
     to <= (from + 1)
 }
 
-#[derive(Eq, PartialEq)]
-struct State {
-    row: usize,
-    col: usize,
-    cost: usize,
-}
-
-impl Ord for State {
-    fn cmp(&self, other: &Self) -> Ordering {
-        other
-            .cost
-            .cmp(&self.cost)
-            .then_with(|| other.row.cmp(&self.row))
-            .then_with(|| other.col.cmp(&self.col))
-    }
-}
-
-impl PartialOrd for State {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
 #[cfg(test)]
 mod test {
     use super::*;
@@ -1,41 +1,45 @@
 use std::fmt::Display;
 
-pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
+use crate::vis::Visualizer;
+
+#[aoc(year = 2022, day = 13, part = 1)]
+pub fn part1(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let mut sum = 0;
     for (i, pair) in input.split("\n\n").enumerate() {
         let (left, right) = pair.split_once('\n').unwrap();
         let (left, right) = (parse(left, vis), parse(right, vis));
         if left < right {
-            if vis {
-                println!(" => in order!");
+            if vis.on() {
+                vis.log(" => in order!");
             }
             sum += i + 1;
-        } else if vis {
-            println!(" => out of order!");
+        } else if vis.on() {
+            vis.log(" => out of order!");
         }
     }
     Box::new(sum)
 }
 
-pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2022, day = 13, part = 2)]
+pub fn part2(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let mut packets: Vec<Packet> = input
         .lines()
         .filter(|s| !s.is_empty())
-        .map(|s| parse(s, false))
+        .map(|s| parse(s, &Visualizer::off()))
         .collect();
-    let p1 = parse("[[2]]", false);
-    let p2 = parse("[[6]]", false);
+    let p1 = parse("[[2]]", &Visualizer::off());
+    let p2 = parse("[[6]]", &Visualizer::off());
     packets.push(p1.clone());
     packets.push(p2.clone());
     packets.sort();
     let mut res = 1;
     for (i, p) in packets.iter().enumerate() {
-        if vis {
-            println!("{:3}: {}", i, p);
+        if vis.on() {
+            vis.log(format!("{:3}: {}", i, p));
         }
         if *p == p1 || *p == p2 {
-            if vis {
-                println!("found {:?} at {}", p, i);
+            if vis.on() {
+                vis.log(format!("found {:?} at {}", p, i));
             }
             res *= i + 1;
         }
@@ -43,22 +47,23 @@ pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
     Box::new(res)
 }
 
-pub fn part2_no_sort(input: String, vis: bool) -> Box<dyn Display> {
-    let p1 = parse("[[2]]", false);
-    let p2 = parse("[[6]]", false);
+#[aoc(year = 2022, day = 13, part = 2, label = "no sort")]
+pub fn part2_no_sort(input: String, vis: &Visualizer) -> Box<dyn Display> {
+    let p1 = parse("[[2]]", &Visualizer::off());
+    let p2 = parse("[[6]]", &Visualizer::off());
     let mut small = 1;
     let mut mid = 2;
     for line in input.lines().filter(|s| !s.is_empty()) {
         let p = parse(line, vis);
         if p < p1 {
-            if vis {
-                println!(" ==> SMALL!");
+            if vis.on() {
+                vis.log(" ==> SMALL!");
             }
             small += 1;
             mid += 1;
         } else if p < p2 {
-            if vis {
-                println!(" ==> MID!");
+            if vis.on() {
+                vis.log(" ==> MID!");
             }
             mid += 1;
         }
@@ -66,7 +71,7 @@ pub fn part2_no_sort(input: String, vis: bool) -> Box<dyn Display> {
     Box::new(small * mid)
 }
 
-fn parse(s: &str, vis: bool) -> Packet {
+fn parse(s: &str, vis: &Visualizer) -> Packet {
     let s = s.trim();
     let mut parents = Vec::new();
     let mut cur = Vec::new();
@@ -85,13 +90,13 @@ fn parse(s: &str, vis: bool) -> Packet {
         };
     }
     let res = cur.into_iter().next().unwrap();
-    if vis {
-        println!("PARSED: {}", res);
+    if vis.on() {
+        vis.log(format!("PARSED: {}", res));
     }
     res
 }
 
-fn tokens(s: &str) -> Tokens {
+fn tokens(s: &str) -> Tokens<'_> {
     Tokens { s }
 }
 
@@ -233,12 +238,14 @@ mod test {
     #[test]
     fn parse() {
         use super::{parse, Packet};
-        assert_eq!(Packet::List(Vec::new()), parse("[]", true));
-        assert_eq!(Packet::Value(1), parse("1", true));
-        assert_eq!(Packet::Value(10), parse("10", true));
+        use crate::vis::{Stdout, Visualizer};
+        let vis = Visualizer::new(Stdout);
+        assert_eq!(Packet::List(Vec::new()), parse("[]", &vis));
+        assert_eq!(Packet::Value(1), parse("1", &vis));
+        assert_eq!(Packet::Value(10), parse("10", &vis));
         assert_eq!(
             Packet::List(vec![Packet::Value(1), Packet::List(vec![Packet::Value(2)])]),
-            parse("[1,[2]]", true)
+            parse("[1,[2]]", &vis)
         );
     }
 
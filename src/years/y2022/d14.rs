@@ -1,27 +1,38 @@
 use std::{fmt::Display, ops::RangeInclusive};
 
-pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
+use crate::{
+    grid::Grid,
+    vis::{Frame, Visualizer},
+};
+
+#[aoc(year = 2022, day = 14, part = 1)]
+pub fn part1(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let rocks = input.lines().map(parse_rock).collect();
     let (mut space, max_depth) = create_space(rocks);
+    let cols = drawn_cols(&space);
+    let cols = cols.start() - 1..=cols.end() + 1;
     for grains in 0.. {
+        if vis.on() && grains % GRAINS_PER_FRAME == 0 {
+            printspace(&space, cols.clone(), grains, vis);
+        }
         let mut sand_depth = 0;
         let mut sand_col = 500;
         'grain: loop {
             if sand_depth == max_depth {
-                if vis {
-                    printspace(space);
+                if vis.on() {
+                    printspace(&space, cols, grains, vis);
                 }
                 return Box::new(grains);
-            } else if space[sand_depth + 1][sand_col] == Space::Empty {
+            } else if space[(sand_depth + 1, sand_col)] == Space::Empty {
                 sand_depth += 1;
-            } else if space[sand_depth + 1][sand_col - 1] == Space::Empty {
+            } else if space[(sand_depth + 1, sand_col - 1)] == Space::Empty {
                 sand_depth += 1;
                 sand_col -= 1;
-            } else if space[sand_depth + 1][sand_col + 1] == Space::Empty {
+            } else if space[(sand_depth + 1, sand_col + 1)] == Space::Empty {
                 sand_depth += 1;
                 sand_col += 1;
             } else {
-                space[sand_depth][sand_col] = Space::Sand;
+                space[(sand_depth, sand_col)] = Space::Sand;
                 break 'grain;
             }
         }
@@ -29,31 +40,37 @@ pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
     unreachable!()
 }
 
-pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2022, day = 14, part = 2)]
+pub fn part2(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let rocks = input.lines().map(parse_rock).collect();
     let (mut space, _) = create_space(rocks);
-    space.push(vec![Space::Empty; 1000]);
-    space.push(vec![Space::Floor; 1000]);
+    space.push_row(vec![Space::Empty; 1000]);
+    space.push_row(vec![Space::Floor; 1000]);
+    // The sand ends up as a triangle on the floor.
+    let cols = 500 - space.rows()..=500 + space.rows();
     for grains in 0.. {
         let mut sand_depth = 0;
         let mut sand_col = 500;
-        if space[sand_depth][sand_col] != Space::Empty {
-            if vis {
-                printspace(space);
+        if space[(sand_depth, sand_col)] != Space::Empty {
+            if vis.on() {
+                printspace(&space, cols, grains, vis);
             }
             return Box::new(grains);
         }
+        if vis.on() && grains % GRAINS_PER_FRAME == 0 {
+            printspace(&space, cols.clone(), grains, vis);
+        }
         'grain: loop {
-            if space[sand_depth + 1][sand_col] == Space::Empty {
+            if space[(sand_depth + 1, sand_col)] == Space::Empty {
                 sand_depth += 1;
-            } else if space[sand_depth + 1][sand_col - 1] == Space::Empty {
+            } else if space[(sand_depth + 1, sand_col - 1)] == Space::Empty {
                 sand_depth += 1;
                 sand_col -= 1;
-            } else if space[sand_depth + 1][sand_col + 1] == Space::Empty {
+            } else if space[(sand_depth + 1, sand_col + 1)] == Space::Empty {
                 sand_depth += 1;
                 sand_col += 1;
             } else {
-                space[sand_depth][sand_col] = Space::Sand;
+                space[(sand_depth, sand_col)] = Space::Sand;
                 break 'grain;
             }
         }
@@ -61,44 +78,44 @@ pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
     unreachable!()
 }
 
-fn printspace(space: Vec<Vec<Space>>) {
+// While visualizing, how often to draw the sand.
+const GRAINS_PER_FRAME: usize = 25;
+
+// The columns with anything in them.
+fn drawn_cols(space: &Grid<Space>) -> RangeInclusive<usize> {
     let mut min_col = 999;
     let mut max_col = 0;
-    for row in &space {
-        for (col, x) in row.iter().enumerate() {
-            if *x != Space::Empty && *x != Space::Floor {
-                if col < min_col {
-                    min_col = col;
-                }
-                if col > max_col {
-                    max_col = col;
-                }
-            }
+    for ((_, col), x) in space.iter() {
+        if *x != Space::Empty && *x != Space::Floor {
+            min_col = min_col.min(col);
+            max_col = max_col.max(col);
         }
     }
-    for row in space {
-        for x in &row[min_col..=max_col] {
-            print!(
-                "{}",
-                match x {
-                    Space::Empty => '.',
-                    Space::Sand => 'o',
-                    Space::Rock => '#',
-                    Space::Floor => '-',
-                }
-            );
-        }
-        println!();
-    }
+    min_col..=max_col
+}
+
+fn printspace(space: &Grid<Space>, cols: RangeInclusive<usize>, grains: usize, vis: &Visualizer) {
+    let rows = space.iter_rows().map(|row| {
+        row[cols.clone()]
+            .iter()
+            .map(|x| match x {
+                Space::Empty => '.',
+                Space::Sand => 'o',
+                Space::Rock => '#',
+                Space::Floor => '-',
+            })
+            .collect()
+    });
+    vis.frame(Frame::new(format!("{grains} grains"), rows));
 }
 
-fn create_space(rocks: Vec<Vec<Coord>>) -> (Vec<Vec<Space>>, usize) {
+fn create_space(rocks: Vec<Vec<Coord>>) -> (Grid<Space>, usize) {
     let max_depth = rocks.iter().fold(0, |m, r| {
         r.iter()
             .fold(m, |m, Coord(_, d)| if m > *d { m } else { *d })
     });
 
-    let mut space = vec![vec![Space::Empty; 1000]; max_depth + 1];
+    let mut space = Grid::new(max_depth + 1, 1000, Space::Empty);
 
     fn fill_range(a: usize, b: usize) -> RangeInclusive<usize> {
         if a < b {
@@ -113,11 +130,11 @@ fn create_space(rocks: Vec<Vec<Coord>>) -> (Vec<Vec<Space>>, usize) {
             let Coord(b_col, b_depth) = pair[1];
             if a_col == b_col {
                 for depth in fill_range(a_depth, b_depth) {
-                    space[depth][a_col] = Space::Rock;
+                    space[(depth, a_col)] = Space::Rock;
                 }
             } else if a_depth == b_depth {
                 for col in fill_range(a_col, b_col) {
-                    space[a_depth][col] = Space::Rock;
+                    space[(a_depth, col)] = Space::Rock;
                 }
             } else {
                 unreachable!()
@@ -1,130 +1,72 @@
-use std::{collections::BTreeSet, fmt::Display, ops::RangeInclusive};
+use std::{collections::BTreeSet, fmt::Display, ops::Range};
 
-pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
+use crate::{interval::IntervalSet, vis::Visualizer};
+
+#[aoc(year = 2022, day = 15, part = 1)]
+pub fn part1(input: String, vis: &Visualizer) -> Box<dyn Display> {
     real_part1(input, vis, 2000000)
 }
 
-fn real_part1(input: String, vis: bool, y: Coord) -> Box<dyn Display> {
+fn real_part1(input: String, vis: &Visualizer, y: Coord) -> Box<dyn Display> {
     let sensors = input.lines().map(parse_sensor);
     let mut beacons = BTreeSet::new();
-    let mut covered = Vec::new();
+    let mut covered = IntervalSet::new();
     for s in sensors {
         let new = cover(&s, y);
-        if vis {
-            println!("{:?} => {:?}", s, new);
+        if vis.on() {
+            vis.log(format!("{:?} => {:?}", s, new));
         }
-        covered = merge(covered, new);
+        covered.insert(new);
         let (bx, by) = s.1;
         if by == y {
             beacons.insert(bx);
         }
     }
-    if vis {
-        println!("beacons: {:?}", beacons);
-        println!("coverage: {:?}", covered);
+    if vis.on() {
+        vis.log(format!("beacons: {:?}", beacons));
+        vis.log(format!("coverage: {:?}", covered));
     }
-    let covered: Coord = covered.into_iter().map(|r| 1 + r.end() - r.start()).sum();
+    let covered = covered.len();
     let beacons = beacons.len() as Coord;
     Box::new(covered - beacons)
 }
 
-pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2022, day = 15, part = 2)]
+pub fn part2(input: String, vis: &Visualizer) -> Box<dyn Display> {
     real_part2(input, vis, 4000000)
 }
 
-fn real_part2(input: String, vis: bool, max: Coord) -> Box<dyn Display> {
+fn real_part2(input: String, vis: &Visualizer, max: Coord) -> Box<dyn Display> {
     let (x, y) = real_part2_2(input, vis, max);
     Box::new(x * 4000000 + y)
 }
 
-fn real_part2_2(input: String, vis: bool, max: Coord) -> (Coord, Coord) {
+fn real_part2_2(input: String, vis: &Visualizer, max: Coord) -> (Coord, Coord) {
     let stepsize = max / 20;
     let sensors: Vec<SensorReading> = input.lines().map(parse_sensor).collect();
-    let mut a = Vec::new();
-    let mut b = Vec::new();
+    // Reused for every row, so the loop doesn't allocate.
+    let mut uncovered = IntervalSet::new();
     'y: for y in 0..=max {
-        if vis && y > 0 && y % stepsize == 0 {
-            println!("checking {} ...", y);
+        if vis.on() && y > 0 && y % stepsize == 0 {
+            vis.log(format!("checking {} ...", y));
         }
-        a.clear();
-        b.clear();
-        a.push(0..=max);
+        uncovered.clear();
+        uncovered.insert(0..max + 1);
         for s in &sensors {
-            if let Some(new) = cover(s, y) {
-                update(&a, &mut b, new);
-                if b.is_empty() {
-                    continue 'y;
-                }
-                (a, b) = (b, a);
+            uncovered.remove(cover(s, y));
+            // Most rows are covered after a few sensors.
+            if uncovered.is_empty() {
+                continue 'y;
             }
         }
-        if vis {
-            println!("at y={} found {:?}", y, a);
+        if vis.on() {
+            vis.log(format!("at y={} found {:?}", y, uncovered));
         }
-        return (*a[0].start(), y);
+        return (uncovered.first().unwrap(), y);
     }
     unreachable!()
 }
 
-fn update(
-    old: &Vec<RangeInclusive<Coord>>,
-    new: &mut Vec<RangeInclusive<Coord>>,
-    r: RangeInclusive<Coord>,
-) {
-    new.clear();
-    for pr in old {
-        if pr.start() > r.end() || pr.end() < r.start() {
-            new.push(pr.clone());
-            continue;
-        }
-        if pr.start() < r.start() {
-            new.push(*pr.start()..=(*r.start() - 1));
-        }
-        if pr.end() > r.end() {
-            new.push((*r.end() + 1)..=*pr.end());
-        }
-    }
-}
-
-fn merge(
-    ranges: Vec<RangeInclusive<Coord>>,
-    r: Option<RangeInclusive<Coord>>,
-) -> Vec<RangeInclusive<Coord>> {
-    fn m(a: RangeInclusive<Coord>, b: RangeInclusive<Coord>) -> RangeInclusive<Coord> {
-        let (a1, a2) = a.into_inner();
-        let (b1, b2) = b.into_inner();
-        (if a1 < b1 { a1 } else { b1 })..=(if a2 > b2 { a2 } else { b2 })
-    }
-    match (r, ranges.is_empty()) {
-        (None, _) => ranges,
-        (Some(r), true) => vec![r],
-        (Some(mut new), false) => {
-            let mut res = Vec::new();
-            let mut ranges = ranges.into_iter();
-            loop {
-                match ranges.next() {
-                    // Nothing left, add the new segment and return the result.
-                    None => {
-                        res.push(new);
-                        return res;
-                    }
-                    // We haven't found where 'new' slots in yet.
-                    Some(old) if old.end() < new.start() => res.push(old),
-                    // We passed 'new's slot, add new and the rest of the old things and return the
-                    // result.
-                    Some(old) if old.start() > new.end() => {
-                        res.push(new);
-                        res.push(old);
-                        res.extend(ranges);
-                        return res;
-                    }
-                    Some(old) => new = m(old, new),
-                };
-            }
-        }
-    }
-}
-
 type Coord = i64;
 type SensorReading = ((Coord, Coord), (Coord, Coord));
 
@@ -146,15 +88,11 @@ fn parse_sensor(line: &str) -> SensorReading {
     ((sensor_x, sensor_y), (beacon_x, beacon_y))
 }
 
-fn cover(s: &SensorReading, y: Coord) -> Option<RangeInclusive<Coord>> {
+// Where the sensor can see along row `y`. Empty if it can't reach it.
+fn cover(s: &SensorReading, y: Coord) -> Range<Coord> {
     let ((sx, sy), (bx, by)) = *s;
-    let dist = md((sx, sy), (bx, by));
-    if sy + dist < y || sy - dist > y {
-        None
-    } else {
-        let dx = dist - (y - sy).abs();
-        Some((sx - dx)..=(sx + dx))
-    }
+    let dx = md((sx, sy), (bx, by)) - (y - sy).abs();
+    (sx - dx)..(sx + dx + 1)
 }
 
 fn md(p1: (Coord, Coord), p2: (Coord, Coord)) -> Coord {
@@ -165,11 +103,11 @@ fn md(p1: (Coord, Coord), p2: (Coord, Coord)) -> Coord {
 mod test {
     use super::*;
 
-    fn part1_10(input: String, vis: bool) -> Box<dyn Display> {
+    fn part1_10(input: String, vis: &Visualizer) -> Box<dyn Display> {
         real_part1(input, vis, 10)
     }
 
-    fn part2_20(input: String, vis: bool) -> Box<dyn Display> {
+    fn part2_20(input: String, vis: &Visualizer) -> Box<dyn Display> {
         real_part2(input, vis, 20)
     }
 
@@ -191,12 +129,12 @@ Sensor at x=20, y=1: closest beacon is at x=15, y=3",
         part2_20 => 56000011);
 
     #[test]
-    fn merges() {
-        fn empty() -> Vec<RangeInclusive<Coord>> {
-            Vec::new()
-        }
-        assert_eq!(empty(), merge(empty(), None));
-        assert_eq!(vec![1..=2], merge(empty(), Some(1..=2)));
-        assert_eq!(vec![1..=5], merge(vec![1..=4], Some(2..=5)));
+    fn covers() {
+        let sensor = ((8, 7), (2, 10));
+        assert_eq!(8..9, cover(&sensor, -2));
+        assert_eq!(-1..18, cover(&sensor, 7));
+        assert!(cover(&sensor, 17).is_empty());
+        let covered: IntervalSet<_> = [7, 10, 16].map(|y| cover(&sensor, y)).into_iter().collect();
+        assert_eq!(vec![-1..18], covered.ranges().collect::<Vec<_>>());
     }
 }
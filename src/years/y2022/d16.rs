@@ -5,6 +5,8 @@ use std::{
     io::Write,
 };
 
+use crate::vis::Visualizer;
+
 #[allow(dead_code)]
 fn write_dot(valves: &[Valve]) -> std::io::Result<()> {
     let mut f = std::fs::File::create("d16.dot")?;
@@ -35,12 +37,14 @@ fn write_dot(valves: &[Valve]) -> std::io::Result<()> {
     writeln!(f, "}}")
 }
 
-pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2022, day = 16, part = 1)]
+pub fn part1(input: String, vis: &Visualizer) -> Box<dyn Display> {
     Box::new(solve(input, vis, 30, 1))
 }
 
 // 2105 is too low
-pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2022, day = 16, part = 2)]
+pub fn part2(input: String, vis: &Visualizer) -> Box<dyn Display> {
     Box::new(solve(input, vis, 26, 2))
 }
 
@@ -112,14 +116,17 @@ fn is_visited(visited: Visited, i: usize) -> bool {
     visited & (1 << i) != 0
 }
 
-fn solve(input: String, vis: bool, minutes: Flow, actors: usize) -> Flow {
-    if vis {
-        println!("~~~ 2022 day 16, minutes={} actors={} ~~~", minutes, actors);
+fn solve(input: String, vis: &Visualizer, minutes: Flow, actors: usize) -> Flow {
+    if vis.on() {
+        vis.log(format!(
+            "~~~ 2022 day 16, minutes={} actors={} ~~~",
+            minutes, actors
+        ));
     }
     let (valves, vindices) = parse_input(input);
     let dists = find_distances(&valves, &vindices, vis);
 
-    if vis {
+    if vis.on() {
         #[cfg(not(test))]
         write_dot(&valves).unwrap();
     }
@@ -159,7 +166,7 @@ fn solve(input: String, vis: bool, minutes: Flow, actors: usize) -> Flow {
             visited,
             paths,
         } = st;
-        if vis {
+        if vis.on() {
             let visited_names: Vec<&str> = game
                 .valves
                 .iter()
@@ -181,19 +188,19 @@ fn solve(input: String, vis: bool, minutes: Flow, actors: usize) -> Flow {
                     }
                 })
                 .collect();
-            println!(
+            vis.log(format!(
                 "@{} possible={} actual={} actors={} visited={}",
                 minutes_remaining,
                 possible,
                 actual,
                 actors.join(","),
                 visited_names.join(",")
-            );
+            ));
         }
         if possible == actual {
-            if vis {
-                println!("optimal path ({} steps):", steps);
-                println!("{}", paths);
+            if vis.on() {
+                vis.log(format!("optimal path ({} steps):", steps));
+                vis.log(&paths);
             }
             return possible;
         }
@@ -269,12 +276,14 @@ fn solve(input: String, vis: bool, minutes: Flow, actors: usize) -> Flow {
     unreachable!();
 }
 
-pub fn part1_new(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2022, day = 16, part = 1, label = "new")]
+pub fn part1_new(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let game = parse(input, vis);
     Box::new(solve1(&game, 30, 0, vis))
 }
 
-pub fn part2_new(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2022, day = 16, part = 2, label = "new", slow)]
+pub fn part2_new(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let game = parse(input, vis);
     let mut best = 0;
     let all_interesting = (1 << game.interesting.len()) - 1;
@@ -292,7 +301,7 @@ pub fn part2_new(input: String, vis: bool) -> Box<dyn Display> {
     Box::new(best)
 }
 
-fn parse(input: String, vis: bool) -> Game {
+fn parse(input: String, vis: &Visualizer) -> Game {
     let (valves, vindices) = parse_input(input);
     let dists = find_distances(&valves, &vindices, vis);
     let interesting: Vec<usize> = valves
@@ -315,7 +324,7 @@ fn parse(input: String, vis: bool) -> Game {
     }
 }
 
-fn solve1(game: &Game, minutes: Flow, visited: Visited, vis: bool) -> Flow {
+fn solve1(game: &Game, minutes: Flow, visited: Visited, vis: &Visualizer) -> Flow {
     // I'd like to make this faster. Some ideas:
     // - Scrap potential.
     // - Memoize reward from (valve, visited, minutes_remaining).
@@ -374,8 +383,8 @@ fn solve1(game: &Game, minutes: Flow, visited: Visited, vis: bool) -> Flow {
             }
             let i = *i;
             let v = &game.valves[i];
-            if vis {
-                println!("want to go from {:?} to {:?}", game.valves[loc], v);
+            if vis.on() {
+                vis.log(format!("want to go from {:?} to {:?}", game.valves[loc], v));
             }
             let dist = game.dists[loc][i].unwrap();
             if dist + 1 >= minutes_remaining {
@@ -500,7 +509,11 @@ impl Act {
 }
 
 // Figure out how far it is from every pair of valves to each other.
-fn find_distances(v: &[Valve], vi: &HashMap<String, usize>, vis: bool) -> Vec<Vec<Option<usize>>> {
+fn find_distances(
+    v: &[Valve],
+    vi: &HashMap<String, usize>,
+    vis: &Visualizer,
+) -> Vec<Vec<Option<usize>>> {
     let mut dists = vec![vec![None; v.len()]; v.len()];
     for (from, valve) in v.iter().enumerate() {
         if valve.name == "AA" || valve.rate > 0 {
@@ -517,7 +530,13 @@ fn find_distances(v: &[Valve], vi: &HashMap<String, usize>, vis: bool) -> Vec<Ve
     dists
 }
 
-fn get_dist(from: usize, to: usize, v: &[Valve], vi: &HashMap<String, usize>, _vis: bool) -> usize {
+fn get_dist(
+    from: usize,
+    to: usize,
+    v: &[Valve],
+    vi: &HashMap<String, usize>,
+    _vis: &Visualizer,
+) -> usize {
     let mut dists: Vec<usize> = vec![usize::MAX; v.len()];
     let mut heap = BinaryHeap::new();
 
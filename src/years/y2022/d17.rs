@@ -1,4 +1,9 @@
-use std::{collections::HashMap, fmt::Display};
+use std::fmt::Display;
+
+use crate::{
+    cycle::{self, Strategy},
+    vis::{Frame, Visualizer},
+};
 
 const ROCKS: &str = r"####
 
@@ -96,65 +101,76 @@ fn parse_puff(c: char) -> Puff {
     }
 }
 
+#[derive(Clone)]
 struct Cavern(Vec<[Space; CAVERN_WIDTH]>);
 
 const CAVERN_WIDTH: usize = 7;
 
-pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2022, day = 17, part = 1)]
+pub fn part1(input: String, vis: &Visualizer) -> Box<dyn Display> {
     go(input, vis, 2022)
 }
 
-pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2022, day = 17, part = 2)]
+pub fn part2(input: String, vis: &Visualizer) -> Box<dyn Display> {
+    // 1523167155410 is too high.
     go(input, vis, 1_000_000_000_000)
 }
 
-fn go(input: String, vis: bool, iterations: usize) -> Box<dyn Display> {
+// The cavern so far, and which jet and rock come next.
+#[derive(Clone)]
+struct Tower {
+    cavern: Cavern,
+    puffs: Forever<Puff>,
+    rocks: usize,
+}
+
+fn go(input: String, vis: &Visualizer, rocks: u64) -> Box<dyn Display> {
     let puffs: Vec<Puff> = input.trim().chars().map(parse_puff).collect();
-    let rocks: Vec<Rock> = ROCKS.split("\n\n").map(parse_rock).collect();
-    let mut cavern = Cavern(Vec::new());
-    let mut puffs = forever(puffs);
-    let mut seen = HashMap::new();
-    for i in 0..iterations {
-        rock_fall(&mut cavern, &rocks[i % rocks.len()], &mut puffs, vis);
-        if vis && i < 12 {
-            print_cavern(&cavern);
-        }
-        if puffs.i > puffs.len {
-            let score = (puffs.i % puffs.len, i % rocks.len(), top(&cavern));
-            let last = seen.get(&score);
-            if vis {
-                println!("i={} last={:?} score={:?}", i, last, score);
-            }
-            let height = tower_height(&cavern);
-            match last {
-                None => seen.insert(score, (i, height)),
-                Some((last_i, last_height)) => {
-                    let remaining = iterations - i;
-                    let span = i - last_i;
-                    let repeats = remaining / span;
-                    let add_height = repeats * (height - last_height);
-                    let resume = i + 1 + repeats * span;
-                    if vis {
-                        println!(
-                            "CYCLE! i={} last_i={} height={} last_height={}",
-                            i, last_i, height, last_height
-                        );
-                        println!(
-                            "  repeats={} resume={} remain={}",
-                            repeats, resume, remaining
-                        );
-                        println!("  span={} dheight={}", span, height - last_height);
-                    }
-                    for i in resume..iterations {
-                        rock_fall(&mut cavern, &rocks[i % rocks.len()], &mut puffs, vis);
-                    }
-                    // 1523167155410 is too high.
-                    return Box::new(add_height + tower_height(&cavern));
-                }
-            };
+    let shapes: Vec<Rock> = ROCKS.split("\n\n").map(parse_rock).collect();
+    let tower = Tower {
+        cavern: Cavern(Vec::new()),
+        puffs: forever(puffs),
+        rocks: 0,
+    };
+    if vis.on() {
+        let mut tower = tower.clone();
+        for _ in 0..12 {
+            drop_rock(&mut tower, &shapes, vis);
+            print_cavern(&tower.cavern, vis);
         }
     }
-    Box::new(tower_height(&cavern))
+
+    let step = |tower: &mut Tower| drop_rock(tower, &shapes, &Visualizer::off());
+    // Near the floor the top rows don't tell the whole story, so nothing
+    // counts as a repeat until all the jets have been used once.
+    let fingerprint = |tower: &Tower| {
+        let Forever { i, len, .. } = tower.puffs;
+        let first = (i <= len).then_some(tower.rocks);
+        (
+            first,
+            i % len,
+            tower.rocks % shapes.len(),
+            top(&tower.cavern),
+        )
+    };
+    let cycle = cycle::find(Strategy::Brent, &tower, step, fingerprint);
+    if vis.on() {
+        vis.log(format!(
+            "the tower repeats every {} rocks after the first {}",
+            cycle.period, cycle.start
+        ));
+    }
+    let height = cycle.value_after(rocks, &tower, step, |tower| {
+        tower_height(&tower.cavern) as u64
+    });
+    Box::new(height.unwrap())
+}
+
+fn drop_rock(tower: &mut Tower, shapes: &[Rock], vis: &Visualizer) {
+    let rock = &shapes[tower.rocks % shapes.len()];
+    rock_fall(&mut tower.cavern, rock, &mut tower.puffs, vis);
+    tower.rocks += 1;
 }
 
 type BF = u64;
@@ -174,10 +190,15 @@ fn top(cavern: &Cavern) -> BF {
         .fold(0, |bf, (i, cr)| bf | (asbf(cr) << (i * CAVERN_WIDTH)))
 }
 
-fn rock_fall<I: Iterator<Item = Puff>>(cavern: &mut Cavern, rock: &Rock, puffs: &mut I, vis: bool) {
+fn rock_fall<I: Iterator<Item = Puff>>(
+    cavern: &mut Cavern,
+    rock: &Rock,
+    puffs: &mut I,
+    vis: &Visualizer,
+) {
     let need = 4 - empty_space(cavern);
-    if vis {
-        println!("add {} rows to cavern", need);
+    if vis.on() {
+        vis.log(format!("add {} rows to cavern", need));
     }
     cavern.0.resize(cavern.0.len() + need, Default::default());
 
@@ -293,17 +314,18 @@ fn empty_space(cavern: &Cavern) -> usize {
     cavern.0.len()
 }
 
-fn print_cavern(cavern: &Cavern) {
-    for r in cavern.0.iter().rev() {
-        print!("|");
-        for c in r {
-            print!("{}", c.c('#'));
-        }
-        println!("|");
-    }
-    println!("+-------+");
+fn print_cavern(cavern: &Cavern, vis: &Visualizer) {
+    let mut rows: Vec<String> = cavern
+        .0
+        .iter()
+        .rev()
+        .map(|r| format!("|{}|", r.iter().map(|c| c.c('#')).collect::<String>()))
+        .collect();
+    rows.push("+-------+".to_string());
+    vis.frame(Frame::new("", rows));
 }
 
+#[derive(Clone)]
 struct Forever<T> {
     items: Vec<T>,
     len: usize,
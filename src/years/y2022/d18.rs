@@ -3,18 +3,21 @@ use std::{
     fmt::Display,
 };
 
-pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
+use crate::vis::Visualizer;
+
+#[aoc(year = 2022, day = 18, part = 1)]
+pub fn part1(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let mut exposed = HashMap::new();
     let mut covered = HashSet::new();
     for cube in input.lines() {
         let (x, y, z) = parse(cube);
-        if vis {
-            println!("! ({}, {}, {})", x, y, z);
+        if vis.on() {
+            vis.log(format!("! ({}, {}, {})", x, y, z));
         }
         covered.insert((x, y, z));
         if let Some(c) = exposed.remove(&(x, y, z)) {
-            if vis {
-                println!("  - cover {} ({}, {}, {})", c, x, y, z);
+            if vis.on() {
+                vis.log(format!("  - cover {} ({}, {}, {})", c, x, y, z));
             }
         }
         add_side(&mut exposed, &covered, vis, (x + 1, y, z));
@@ -23,19 +26,24 @@ pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
         add_side(&mut exposed, &covered, vis, (x, y - 1, z));
         add_side(&mut exposed, &covered, vis, (x, y, z + 1));
         add_side(&mut exposed, &covered, vis, (x, y, z - 1));
-        if vis {
-            println!("exposed={} covered={}", exposed.len(), covered.len());
+        if vis.on() {
+            vis.log(format!(
+                "exposed={} covered={}",
+                exposed.len(),
+                covered.len()
+            ));
         }
     }
-    if vis {
+    if vis.on() {
         for p in &exposed {
-            println!("** {:?}", p);
+            vis.log(format!("** {:?}", p));
         }
     }
     Box::new(exposed.values().sum::<usize>())
 }
 
-pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2022, day = 18, part = 2)]
+pub fn part2(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let mut exposed = HashMap::new();
     let mut covered = HashSet::new();
     let mut maxx = 0;
@@ -64,8 +72,8 @@ pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
     let mut connected = HashSet::new();
     let mut to_check = Vec::new();
     'exp: for (p, n) in exposed {
-        if vis {
-            println!("checking {:?} exposed={}...", p, n);
+        if vis.on() {
+            vis.log(format!("checking {:?} exposed={}...", p, n));
         }
         connected.clear();
         to_check.clear();
@@ -73,8 +81,8 @@ pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
         while let Some(p) = to_check.pop() {
             if p.0 < 0 || p.1 < 0 || p.2 < 0 || p.0 > maxx || p.1 > maxy || p.2 > maxz {
                 // escape!
-                if vis {
-                    println!("... escape via {:?}!", p);
+                if vis.on() {
+                    vis.log(format!("... escape via {:?}!", p));
                 }
                 for p in connected.drain() {
                     free.insert(p);
@@ -88,8 +96,8 @@ pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
             }
             if free.contains(&p) {
                 // woo hoo! we're connected to a free square, so we are free too!
-                if vis {
-                    println!("... escape, found a way to {:?}", p);
+                if vis.on() {
+                    vis.log(format!("... escape, found a way to {:?}", p));
                 }
                 for p in connected.drain() {
                     free.insert(p);
@@ -99,8 +107,8 @@ pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
             }
             if trapped.contains(&p) {
                 // womp we are trapped.
-                if vis {
-                    println!("... trapped :(");
+                if vis.on() {
+                    vis.log("... trapped :(");
                 }
                 assert!(
                     connected.is_empty(),
@@ -118,8 +126,11 @@ pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
             }
         }
         // Trapped!
-        if vis {
-            println!("... trapped, along with {} others", connected.len());
+        if vis.on() {
+            vis.log(format!(
+                "... trapped, along with {} others",
+                connected.len()
+            ));
         }
         for p in connected.drain() {
             trapped.insert(p);
@@ -143,10 +154,10 @@ fn add_neighbors(to_check: &mut Vec<P>, seen: &HashSet<P>, p: &P) {
     a(to_check, seen, (*x, *y, z + 1));
 }
 
-fn add_side(exposed: &mut HashMap<P, usize>, covered: &HashSet<P>, vis: bool, p: P) {
+fn add_side(exposed: &mut HashMap<P, usize>, covered: &HashSet<P>, vis: &Visualizer, p: P) {
     if !covered.contains(&p) {
-        if vis {
-            println!("  + expose {:?}", p);
+        if vis.on() {
+            vis.log(format!("  + expose {:?}", p));
         }
         let c = exposed.entry(p).or_insert(0);
         *c += 1;
@@ -3,7 +3,10 @@ use std::{
     fmt::Display,
 };
 
-pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
+use crate::vis::Visualizer;
+
+#[aoc(year = 2022, day = 19, part = 1, slow)]
+pub fn part1(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let total: usize = input
         .lines()
         .map(parse)
@@ -12,7 +15,8 @@ pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
     Box::new(total)
 }
 
-pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2022, day = 19, part = 2, slow)]
+pub fn part2(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let res: usize = input
         .lines()
         .take(3)
@@ -22,9 +26,9 @@ pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
     Box::new(res)
 }
 
-fn quality_level(bp: &Blueprint, minutes: usize, vis: bool) -> Quality {
-    if vis {
-        println!("{:?}", bp);
+fn quality_level(bp: &Blueprint, minutes: usize, vis: &Visualizer) -> Quality {
+    if vis.on() {
+        vis.log(format!("{:?}", bp));
     }
     let robots = [1, 0, 0, 0];
     let minerals = [0; 4];
@@ -42,8 +46,8 @@ fn quality_level(bp: &Blueprint, minutes: usize, vis: bool) -> Quality {
     while let Some(st) = to_try.pop() {
         if st.elapsed == minutes {
             let geodes = st.minerals[Mineral::Geode as usize];
-            if vis {
-                println!(" => {} {:?}", geodes, st);
+            if vis.on() {
+                vis.log(format!(" => {} {:?}", geodes, st));
             }
             return geodes;
         }
@@ -55,15 +59,15 @@ fn quality_level(bp: &Blueprint, minutes: usize, vis: bool) -> Quality {
 
         for rc in &bp.robot_costs {
             if let Some(minerals) = rc.buy(&st.minerals) {
-                if vis {
-                    println!(
+                if vis.on() {
+                    vis.log(format!(
                         "{:width$}buy {:?} for {:?} at {:?}",
                         ' ',
                         rc.produces,
                         rc.costs,
                         st,
                         width = st.elapsed
-                    );
+                    ));
                 }
                 let minerals = collect(minerals, &st.robots);
                 let mut robots = st.robots;
@@ -220,7 +224,7 @@ mod test {
 
     fn quality_level(s: &str) -> (usize, Quality) {
         let bp = parse(s);
-        (bp.n, super::quality_level(&bp, 24, false))
+        (bp.n, super::quality_level(&bp, 24, &Visualizer::off()))
     }
 
     crate::test::aoc_test!(example, r"Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. Each obsidian robot costs 3 ore and 14 clay. Each geode robot costs 2 ore and 7 obsidian.
@@ -1,6 +1,9 @@
 use std::fmt::Display;
 
-pub fn part1(input: String, _: bool) -> Box<dyn Display> {
+use crate::vis::Visualizer;
+
+#[aoc(year = 2022, day = 2, part = 1)]
+pub fn part1(input: String, _: &Visualizer) -> Box<dyn Display> {
     let mut score: u64 = 0;
     for line in input.lines() {
         let (opp, me) = line.split_once(' ').unwrap();
@@ -20,7 +23,8 @@ pub fn part1(input: String, _: bool) -> Box<dyn Display> {
     Box::new(score)
 }
 
-pub fn part1_nosplit(input: String, _: bool) -> Box<dyn Display> {
+#[aoc(year = 2022, day = 2, part = 1, label = "no split")]
+pub fn part1_nosplit(input: String, _: &Visualizer) -> Box<dyn Display> {
     let mut score: u64 = 0;
     for line in input.lines() {
         score += match line {
@@ -39,7 +43,8 @@ pub fn part1_nosplit(input: String, _: bool) -> Box<dyn Display> {
     Box::new(score)
 }
 
-pub fn part2(input: String, _: bool) -> Box<dyn Display> {
+#[aoc(year = 2022, day = 2, part = 2)]
+pub fn part2(input: String, _: &Visualizer) -> Box<dyn Display> {
     let mut score: u64 = 0;
     for line in input.lines() {
         let (opp, me) = line.split_once(' ').unwrap();
@@ -65,7 +70,8 @@ pub fn part2(input: String, _: bool) -> Box<dyn Display> {
     Box::new(score)
 }
 
-pub fn part1alt(input: String, _: bool) -> Box<dyn Display> {
+#[aoc(year = 2022, day = 2, part = 1, label = "mods")]
+pub fn part1alt(input: String, _: &Visualizer) -> Box<dyn Display> {
     Box::new(input.lines().map(score_line1).sum::<u32>())
 }
 
@@ -75,7 +81,8 @@ fn score_line1(line: &str) -> u32 {
     me + 1 + res * 3
 }
 
-pub fn part2alt(input: String, _: bool) -> Box<dyn Display> {
+#[aoc(year = 2022, day = 2, part = 2, label = "mods")]
+pub fn part2alt(input: String, _: &Visualizer) -> Box<dyn Display> {
     Box::new(input.lines().map(score_line2).sum::<u32>())
 }
 
@@ -1,8 +1,11 @@
 use std::fmt::Display;
 
+use crate::vis::Visualizer;
+
 // YES 16533
 // NOT -6697
-pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2022, day = 20, part = 1)]
+pub fn part1(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let mut values: Vec<(usize, i64)> = input
         .lines()
         .map(|s| s.parse().unwrap())
@@ -14,28 +17,29 @@ pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
     Box::new(score(values))
 }
 
-pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2022, day = 20, part = 2)]
+pub fn part2(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let mut values: Vec<(usize, i64)> = input
         .lines()
         .map(|s| s.parse::<i64>().unwrap() * 811589153)
         .enumerate()
         .collect();
 
-    if vis {
-        println!("Initial arrangement of {} items:", values.len());
-        print_values(&values);
-        println!();
+    if vis.on() {
+        vis.log(format!("Initial arrangement of {} items:", values.len()));
+        print_values(&values, vis);
+        vis.log("");
     }
 
     for i in 0..10 {
-        mix(&mut values, false);
+        mix(&mut values, &Visualizer::off());
 
-        if vis {
+        if vis.on() {
             let mut rv = values.clone();
             let zero = find_zero(&rv);
             rv.rotate_left(zero);
-            println!("After round {}", i + 1);
-            print_values(&rv);
+            vis.log(format!("After round {}", i + 1));
+            print_values(&rv, vis);
         }
     }
 
@@ -56,17 +60,17 @@ fn score(values: Vec<(usize, i64)>) -> i64 {
     a + b + c
 }
 
-fn mix(values: &mut Vec<(usize, i64)>, vis: bool) {
-    if vis {
-        println!("Initial arrangement of {} items:", values.len());
-        print_values(values);
+fn mix(values: &mut Vec<(usize, i64)>, vis: &Visualizer) {
+    if vis.on() {
+        vis.log(format!("Initial arrangement of {} items:", values.len()));
+        print_values(values, vis);
     }
     for order in 0..values.len() {
         mix1(values, order, vis);
     }
 }
 
-fn mix1(values: &mut Vec<(usize, i64)>, order: usize, vis: bool) {
+fn mix1(values: &mut Vec<(usize, i64)>, order: usize, vis: &Visualizer) {
     fn find(values: &[(usize, i64)], order: usize) -> (usize, i64) {
         let (pos, (_, val)) = values
             .iter()
@@ -101,26 +105,24 @@ fn mix1(values: &mut Vec<(usize, i64)>, order: usize, vis: bool) {
         values[pos..=newpos].rotate_left(1);
     }
     */
-    if vis {
-        println!("moved {} from [{}] to [{}]", val, pos, newpos);
-        print_values(values);
+    if vis.on() {
+        vis.log(format!("moved {} from [{}] to [{}]", val, pos, newpos));
+        print_values(values, vis);
     }
 }
 
-fn print_values(values: &[(usize, i64)]) {
-    let mut sep = "";
-    for (_, v) in values.iter() {
-        print!("{}{}", sep, v);
-        sep = ", ";
-    }
-    println!();
+fn print_values(values: &[(usize, i64)], vis: &Visualizer) {
+    let values: Vec<String> = values.iter().map(|(_, v)| v.to_string()).collect();
+    vis.log(values.join(", "));
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::vis::Stdout;
 
     #[test]
+    #[allow(clippy::identity_op)]
     fn negative_mod() {
         // This is not what I want, but good to know.
         assert_eq!(-32, -32 % 33);
@@ -152,7 +154,7 @@ mod test {
     fn test_mix() {
         fn mix(values: Vec<i64>) -> Vec<i64> {
             let mut values = values.into_iter().enumerate().collect();
-            super::mix(&mut values, false);
+            super::mix(&mut values, &Visualizer::off());
             values.into_iter().map(|(_, v)| v).collect()
         }
 
@@ -175,12 +177,14 @@ mod test {
     fn test_mix1() {
         fn mix1(values: Vec<i64>, pos: usize) -> Vec<i64> {
             let mut values: Vec<(usize, i64)> = values.into_iter().enumerate().collect();
-            if MIX1_VIS {
-                println!("------");
-                println!("mix1 test input");
-                print_values(&values);
-            }
-            super::mix1(&mut values, pos, MIX1_VIS);
+            let vis = match MIX1_VIS {
+                true => Visualizer::new(Stdout),
+                false => Visualizer::off(),
+            };
+            vis.log("------");
+            vis.log("mix1 test input");
+            print_values(&values, &vis);
+            super::mix1(&mut values, pos, &vis);
             values.into_iter().map(|(_, v)| v).collect()
         }
 
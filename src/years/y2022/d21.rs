@@ -1,24 +1,28 @@
 use std::{collections::HashMap, fmt::Display};
 
-pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
+use crate::vis::Visualizer;
+
+#[aoc(year = 2022, day = 21, part = 1)]
+pub fn part1(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let rules: HashMap<String, Rule> = input.lines().map(parse).collect();
     let mut memo = HashMap::new();
     Box::new(solve(&rules, &mut memo, "root", vis, None).unwrap())
 }
 
-pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2022, day = 21, part = 2)]
+pub fn part2(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let rules: HashMap<String, Rule> = input.lines().map(parse).collect();
     let mut memo = HashMap::new();
     solve(&rules, &mut memo, "root", vis, Some("humn"));
     let res = match rules.get("root") {
         Some(Rule::Op { arg1, arg2, .. }) => {
-            if vis {
-                println!("{}:", arg1);
-                println!("  {}", substitute(arg1, &rules));
-                println!("  => {:?}", memo.get(arg1));
-                println!("{}:", arg2);
-                println!("  {}", substitute(arg2, &rules));
-                println!("  => {:?}", memo.get(arg2));
+            if vis.on() {
+                vis.log(format!("{}:", arg1));
+                vis.log(format!("  {}", substitute(arg1, &rules)));
+                vis.log(format!("  => {:?}", memo.get(arg1)));
+                vis.log(format!("{}:", arg2));
+                vis.log(format!("  {}", substitute(arg2, &rules)));
+                vis.log(format!("  => {:?}", memo.get(arg2)));
             }
             match (memo.get(arg1), memo.get(arg2)) {
                 (Some(Some(answer)), Some(None)) => what_is_humn(*answer, &rules, &memo, arg2, vis),
@@ -36,17 +40,17 @@ fn what_is_humn(
     rules: &HashMap<String, Rule>,
     memo: &HashMap<String, Option<i64>>,
     cur: &str,
-    vis: bool,
+    vis: &Visualizer,
 ) -> i64 {
     match rules.get(cur) {
         Some(Rule::Op { arg1, arg2, op }) => {
             let m1 = memo.get(arg1);
             let m2 = memo.get(arg2);
-            if vis {
-                println!(
+            if vis.on() {
+                vis.log(format!(
                     "solving for {} = {}({:?}) {} {}({:?})",
                     answer, arg1, m1, op, arg2, m2
-                );
+                ));
             }
             match (m1, m2) {
                 // Still more to do!
@@ -87,7 +91,7 @@ fn solve(
     rules: &HashMap<String, Rule>,
     memo: &mut HashMap<String, Option<i64>>,
     target: &str,
-    vis: bool,
+    vis: &Visualizer,
     skip: Option<&str>,
 ) -> Option<i64> {
     if matches!(skip, Some(x) if x == target) {
@@ -96,8 +100,8 @@ fn solve(
     if let Some(val) = memo.get(target) {
         return *val;
     }
-    if vis {
-        println!("getting {}", target);
+    if vis.on() {
+        vis.log(format!("getting {}", target));
     }
     fn step(arg1: Option<i64>, arg2: Option<i64>, op: Op) -> Option<i64> {
         match (arg1, arg2) {
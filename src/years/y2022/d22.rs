@@ -1,7 +1,10 @@
 use std::collections::HashMap;
 use std::fmt::Display;
 
-pub fn part1(input: String, _vis: bool) -> Box<dyn Display> {
+use crate::vis::{Frame, Visualizer};
+
+#[aoc(year = 2022, day = 22, part = 1)]
+pub fn part1(input: String, _vis: &Visualizer) -> Box<dyn Display> {
     let (board, path) = input.split_once("\n\n").unwrap();
 
     let board = parse_board(board);
@@ -22,7 +25,8 @@ pub fn part1(input: String, _vis: bool) -> Box<dyn Display> {
     Box::new((row + 1) * 1000 + (col + 1) * 4 + dir as usize)
 }
 
-pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2022, day = 22, part = 2)]
+pub fn part2(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let (board, path) = input.split_once("\n\n").unwrap();
 
     let board = parse_board(board);
@@ -38,23 +42,28 @@ pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
 
     let edges = trace_edges(&board, pos, mode);
 
-    if vis {
-        for (row, tilerow) in board.tiles.iter().enumerate() {
-            for (col, tile) in tilerow.iter().enumerate() {
-                match (tile, edges.get(&(row, col))) {
-                    (None, None) => print!(" "),
-                    (Some(t), None) => print!("{}", t),
-                    (Some(_), Some(n)) => print!("{}", n),
+    if vis.on() {
+        let rows = board.tiles.iter().enumerate().map(|(row, tilerow)| {
+            tilerow
+                .iter()
+                .enumerate()
+                .map(|(col, tile)| match (tile, edges.get(&(row, col))) {
+                    (None, None) => " ".to_string(),
+                    (Some(t), None) => t.to_string(),
+                    (Some(_), Some(n)) => n.to_string(),
                     x => unreachable!("{:?}", x),
-                };
-            }
-            println!();
-        }
+                })
+                .collect()
+        });
+        vis.frame(Frame::new("edges", rows));
     }
 
     for m in path {
-        if vis {
-            println!("going {:?} from {:?}, next step is {:?}.", dir, pos, m);
+        if vis.on() {
+            vis.log(format!(
+                "going {:?} from {:?}, next step is {:?}.",
+                dir, pos, m
+            ));
         }
         match m {
             Move::L => dir = dir.l(),
@@ -203,7 +212,7 @@ fn walk2(
     mut dir: Dir,
     dist: usize,
     mode: WhichBoard,
-    vis: bool,
+    vis: &Visualizer,
 ) -> (Coord, Dir) {
     let (mut r, mut c) = (pos.0 as isize, pos.1 as isize);
     for _ in 0..dist {
@@ -211,8 +220,8 @@ fn walk2(
         let (newr, newc) = (r + dr, c + dc);
         match get(board, newr, newc) {
             Some(Tile::Wall) => {
-                if vis {
-                    println!("  ran into a wall at ({},{})", newr, newc);
+                if vis.on() {
+                    vis.log(format!("  ran into a wall at ({},{})", newr, newc));
                 }
                 break;
             }
@@ -225,16 +234,19 @@ fn walk2(
                     *colors.get(&(r as usize, c as usize)).unwrap(),
                     vis,
                 );
-                if vis {
-                    println!("  jump from ({},{}) to ({},{})", newr, newc, suckr, suckc);
+                if vis.on() {
+                    vis.log(format!(
+                        "  jump from ({},{}) to ({},{})",
+                        newr, newc, suckr, suckc
+                    ));
                 }
                 match get(board, suckr, suckc) {
                     Some(Tile::Wall) => {
-                        if vis {
-                            println!(
+                        if vis.on() {
+                            vis.log(format!(
                                 "  ran into a wall while trying to move {:?} through ({},{})",
                                 suckdir, suckr, suckc
-                            );
+                            ));
                         }
                         break;
                     }
@@ -252,8 +264,8 @@ fn walk2(
             }
         };
     }
-    if vis {
-        println!(" -> ({},{}) going {:?}", r, c, dir);
+    if vis.on() {
+        vis.log(format!(" -> ({},{}) going {:?}", r, c, dir));
     }
     ((r as usize, c as usize), dir)
 }
@@ -264,7 +276,7 @@ fn suck(
     pos: (isize, isize),
     dir: Dir,
     color: u8,
-    _vis: bool,
+    _vis: &Visualizer,
 ) -> (Dir, (isize, isize)) {
     let (r, c) = pos;
     match mode {
@@ -517,6 +529,7 @@ fn parse_path(s: &str) -> Path {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::vis::Stdout;
 
     crate::test::aoc_tests!(example, r"        ...#
         .#..
@@ -538,7 +551,13 @@ mod test {
     #[test]
     fn suck_example() {
         fn suck(pos: (isize, isize), dir: Dir, color: u8) -> (Dir, (isize, isize)) {
-            super::suck(WhichBoard::Example, pos, dir, color, true)
+            super::suck(
+                WhichBoard::Example,
+                pos,
+                dir,
+                color,
+                &Visualizer::new(Stdout),
+            )
         }
 
         assert_eq!(suck((4, 12), Dir::Right, 1), (Dir::Down, (8, 15)));
@@ -555,7 +574,13 @@ mod test {
     fn suck_real() {
         fn suck(pos: (isize, isize), dir: Dir, color: u8) -> (Dir, (isize, isize)) {
             println!("suck(Puzzle, {:?}, {:?}, color:{}, true)", pos, dir, color);
-            super::suck(WhichBoard::Puzzle, pos, dir, color, true)
+            super::suck(
+                WhichBoard::Puzzle,
+                pos,
+                dir,
+                color,
+                &Visualizer::new(Stdout),
+            )
         }
 
         assert_eq!(suck((0, 49), Dir::Left, 0), (Dir::Right, (149, 0)));
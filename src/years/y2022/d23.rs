@@ -3,9 +3,12 @@ use std::{
     fmt::Display,
 };
 
+use crate::vis::{Frame, Visualizer};
+
 type Coord = (isize, isize);
 
-pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2022, day = 23, part = 1)]
+pub fn part1(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let mut occupied: HashSet<Coord> = input
         .lines()
         .enumerate()
@@ -17,9 +20,8 @@ pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
         })
         .collect();
 
-    if vis {
-        println!("initial state");
-        print_field(&occupied);
+    if vis.on() {
+        print_field(&occupied, "initial state", vis);
     }
 
     let mut moves = ['n', 's', 'w', 'e'];
@@ -27,10 +29,8 @@ pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
     for round in 0..10 {
         occupied = play_round(occupied, &moves).0;
         moves.rotate_left(1);
-        if vis {
-            println!();
-            println!("after round {}", round + 1);
-            print_field(&occupied);
+        if vis.on() {
+            print_field(&occupied, format!("after round {}", round + 1), vis);
         }
     }
     let (r_min, r_max, c_min, c_max) = extents(&occupied);
@@ -38,7 +38,8 @@ pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
     Box::new(area as usize - occupied.len())
 }
 
-pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2022, day = 23, part = 2)]
+pub fn part2(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let mut occupied: HashSet<Coord> = input
         .lines()
         .enumerate()
@@ -53,14 +54,11 @@ pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
     let mut moves = ['n', 's', 'w', 'e'];
 
     for round in 0.. {
-        if vis && round % 100 == 0 {
-            print!(".");
+        if vis.on() && round % 100 == 0 {
+            vis.log(format!("round {round}"));
         }
         let (new_field, any_moved) = play_round(occupied, &moves);
         if !any_moved {
-            if vis {
-                println!();
-            }
             return Box::new(round + 1);
         }
         occupied = new_field;
@@ -143,18 +141,17 @@ fn extents(field: &HashSet<Coord>) -> (isize, isize, isize, isize) {
     (*r_min, *r_max, *c_min, *c_max)
 }
 
-fn print_field(field: &HashSet<Coord>) {
+fn print_field(field: &HashSet<Coord>, caption: impl Display, vis: &Visualizer) {
     let (r_min, r_max, c_min, c_max) = extents(field);
-    for r in r_min..=r_max {
-        for c in c_min..=c_max {
-            if field.contains(&(r, c)) {
-                print!("#");
-            } else {
-                print!(".");
-            }
-        }
-        println!();
-    }
+    vis.frame(Frame::draw(
+        caption,
+        (c_min as i64, r_min as i64),
+        (c_max as i64, r_max as i64),
+        |c, r| match field.contains(&(r as isize, c as isize)) {
+            true => '#',
+            false => '.',
+        },
+    ));
 }
 
 #[cfg(test)]
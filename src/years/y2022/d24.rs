@@ -1,134 +1,100 @@
-use std::{
-    cmp::Reverse,
-    collections::{BinaryHeap, HashSet},
-    fmt::Display,
+use std::fmt::Display;
+
+use crate::{
+    grid::Grid,
+    search::{self, Found},
+    vis::Visualizer,
 };
 
+// Waiting, or going to a neighbour.
 const MOVES: [(isize, isize); 5] = [(0, 0), (1, 0), (-1, 0), (0, 1), (0, -1)];
 
-pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2022, day = 24, part = 1)]
+pub fn part1(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let board = parse_input(&input);
-    let you_start = (0, board[0].iter().position(|sq| *sq == EMPTY).unwrap());
-    let bottom_row = board.len() - 1;
+    let you_start = board.find(|sq| *sq == EMPTY).unwrap();
+    let bottom_row = board.rows() - 1;
 
-    if vis {
-        println!("Initial state:");
-        show_state(&board, &you_start, 0);
+    if vis.on() {
+        show_state(&board, &you_start, 0, "Initial state:", vis);
 
-        println!();
-        println!("Minute 1 (after waiting):");
-        show_state(&board, &you_start, 1);
+        show_state(&board, &you_start, 1, "Minute 1 (after waiting):", vis);
     }
 
-    let mut tried = HashSet::new();
-    let mut todo = BinaryHeap::new();
-    todo.push(Reverse(State {
-        elapsed: 0,
-        pos: you_start,
-    }));
-
-    while let Some(Reverse(st)) = todo.pop() {
-        if vis {
-            println!("try {:?}", st);
-        }
-        let State { elapsed, pos } = st;
-        if tried.contains(&(pos, elapsed)) {
-            continue;
-        }
-        tried.insert((pos, elapsed));
-
-        if pos.0 == bottom_row {
-            return Box::new(elapsed);
-        }
+    let you_finish = (
+        bottom_row,
+        board
+            .row(bottom_row)
+            .iter()
+            .position(|sq| *sq == EMPTY)
+            .unwrap(),
+    );
 
-        let elapsed = elapsed + 1;
-        let (r, c) = (pos.0 as isize, pos.1 as isize);
-        for (dr, dc) in &MOVES {
-            let nr = r + dr;
-            let nc = c + dc;
-            if nr >= 0 && nc >= 0 {
-                let pos = (nr as usize, nc as usize);
-                if pos.0 < board.len() && pos.1 <= board[0].len() && is_empty(&board, elapsed, pos)
-                {
-                    let next = State { pos, elapsed };
-                    if vis {
-                        println!(" -> {:?}", next);
-                    }
-                    todo.push(Reverse(next));
-                }
-            }
+    let found = go(&board, 0, you_start, you_finish);
+    if vis.on() {
+        vis.log(format!("looked at {} states", found.expanded));
+        for st in &found.path {
+            vis.log(format!("{:?}", st));
         }
     }
-
-    unreachable!()
+    Box::new(found.cost)
 }
 
-pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2022, day = 24, part = 2)]
+pub fn part2(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let board = parse_input(&input);
-    let you_start = (0, board[0].iter().position(|sq| *sq == EMPTY).unwrap());
-    let bottom_row = board.len() - 1;
+    let you_start = board.find(|sq| *sq == EMPTY).unwrap();
+    let bottom_row = board.rows() - 1;
     let you_finish = (
         bottom_row,
-        board[bottom_row]
+        board
+            .row(bottom_row)
             .iter()
             .position(|sq| *sq == EMPTY)
             .unwrap(),
     );
 
-    let elapsed = go(&board, 0, you_start, you_finish);
-    if vis {
-        println!("got to the end after {} minutes", elapsed);
+    let elapsed = go(&board, 0, you_start, you_finish).cost;
+    if vis.on() {
+        vis.log(format!("got to the end after {} minutes", elapsed));
     }
-    let elapsed = go(&board, elapsed, you_finish, you_start);
-    if vis {
-        println!("got back to the start after {} minutes", elapsed);
+    let elapsed = go(&board, elapsed, you_finish, you_start).cost;
+    if vis.on() {
+        vis.log(format!("got back to the start after {} minutes", elapsed));
     }
-    let elapsed = go(&board, elapsed, you_start, you_finish);
-    if vis {
-        println!("got back to the end after {} minutes", elapsed);
+    let elapsed = go(&board, elapsed, you_start, you_finish).cost;
+    if vis.on() {
+        vis.log(format!("got back to the end after {} minutes", elapsed));
     }
 
     Box::new(elapsed)
 }
 
-fn go(board: &Board, elapsed: usize, start: Coord, finish: Coord) -> usize {
-    let mut tried = HashSet::new();
-    let mut todo = BinaryHeap::new();
-    todo.push(Reverse(State {
-        elapsed,
-        pos: start,
-    }));
-
-    while let Some(Reverse(st)) = todo.pop() {
-        let State { elapsed, pos } = st;
-        if tried.contains(&(pos, elapsed)) {
-            continue;
-        }
-        tried.insert((pos, elapsed));
-
-        if pos == finish {
-            return elapsed;
-        }
-
-        let elapsed = elapsed + 1;
-        let (r, c) = (pos.0 as isize, pos.1 as isize);
-        for (dr, dc) in &MOVES {
-            let nr = r + dr;
-            let nc = c + dc;
-            if nr >= 0 && nc >= 0 {
-                let pos = (nr as usize, nc as usize);
-                if pos.0 < board.len() && pos.1 <= board[0].len() && is_empty(board, elapsed, pos) {
-                    let next = State { pos, elapsed };
-                    todo.push(Reverse(next));
-                }
-            }
-        }
-    }
-
-    unreachable!()
+// The path through the blizzards, starting after `elapsed` minutes. Its cost
+// is the time when it gets to `finish`.
+fn go(board: &Board, elapsed: usize, start: Coord, finish: Coord) -> Found<State, usize> {
+    let mut found = search::bfs(
+        [State {
+            elapsed,
+            pos: start,
+        }],
+        |st| (st.pos, st.elapsed),
+        |st| {
+            let (pos, elapsed) = (st.pos, st.elapsed + 1);
+            MOVES
+                .iter()
+                .filter_map(move |&m| board.step(pos, m))
+                .filter(move |&pos| is_empty(board, elapsed, pos))
+                .map(move |pos| State { pos, elapsed })
+        },
+        |st| st.pos == finish,
+    )
+    .unwrap();
+    found.cost += elapsed;
+    found
 }
 
-#[derive(PartialOrd, Ord, PartialEq, Eq, Debug)]
+#[derive(Clone, Debug)]
 struct State {
     elapsed: usize,
     pos: Coord,
@@ -136,7 +102,7 @@ struct State {
 
 type Coord = (usize, usize);
 
-type Board = Vec<Vec<char>>;
+type Board = Grid<char>;
 
 const WALL: char = '#';
 const EMPTY: char = '.';
@@ -148,18 +114,15 @@ const RIGHT: char = '>';
 const EXPEDITION: char = 'E';
 
 fn parse_input(input: &str) -> Board {
-    input.lines().map(|line| line.chars().collect()).collect()
+    Grid::parse(input, |c| c)
 }
 
-fn show_state(board: &Board, you: &Coord, elapsed: usize) {
-    let rows = board.len();
-    let cols = board[0].len();
-    for r in 0..rows {
-        for c in 0..cols {
-            print!("{}", render(board, you, elapsed, (r, c)));
-        }
-        println!();
+fn show_state(board: &Board, you: &Coord, elapsed: usize, caption: &str, vis: &Visualizer) {
+    let mut state = board.clone();
+    for pos in board.positions() {
+        state[pos] = render(board, you, elapsed, pos);
     }
+    vis.frame(state.frame(caption));
 }
 
 fn is_empty(board: &Board, elapsed: usize, pos: Coord) -> bool {
@@ -169,24 +132,24 @@ fn is_empty(board: &Board, elapsed: usize, pos: Coord) -> bool {
 fn get(board: &Board, elapsed: usize, pos: Coord) -> char {
     let (r, c) = pos;
 
-    if board[r][c] == WALL {
+    if board[pos] == WALL {
         return WALL;
     }
 
-    if r == 0 || c == 0 || r + 1 == board.len() || c + 1 == board[0].len() {
-        assert!(board[r][c] == EMPTY);
+    if r == 0 || c == 0 || r + 1 == board.rows() || c + 1 == board.cols() {
+        assert!(board[pos] == EMPTY);
         return EMPTY;
     }
 
-    let brows = board.len() - 2;
-    let bcols = board[0].len() - 2;
+    let brows = board.rows() - 2;
+    let bcols = board.cols() - 2;
     let roff = elapsed % brows;
     let coff = elapsed % bcols;
 
-    let is_up = board[(r + roff - 1) % brows + 1][c] == UP;
-    let is_down = board[(r + brows - roff - 1) % brows + 1][c] == DOWN;
-    let is_left = board[r][(c + coff - 1) % bcols + 1] == LEFT;
-    let is_right = board[r][(c + bcols - coff - 1) % bcols + 1] == RIGHT;
+    let is_up = board[((r + roff - 1) % brows + 1, c)] == UP;
+    let is_down = board[((r + brows - roff - 1) % brows + 1, c)] == DOWN;
+    let is_left = board[(r, (c + coff - 1) % bcols + 1)] == LEFT;
+    let is_right = board[(r, (c + bcols - coff - 1) % bcols + 1)] == RIGHT;
 
     match (is_up, is_down, is_left, is_right) {
         (false, false, false, false) => EMPTY,
@@ -209,29 +172,29 @@ fn get(board: &Board, elapsed: usize, pos: Coord) -> char {
 fn render(board: &Board, you: &Coord, elapsed: usize, pos: Coord) -> char {
     let (r, c) = pos;
 
-    if board[r][c] == WALL {
+    if board[pos] == WALL {
         assert!(*you != pos);
         return WALL;
     }
 
-    if r == 0 || c == 0 || r + 1 == board.len() || c + 1 == board[0].len() {
-        assert!(board[r][c] == EMPTY);
+    if r == 0 || c == 0 || r + 1 == board.rows() || c + 1 == board.cols() {
+        assert!(board[pos] == EMPTY);
         if *you == pos {
             return EXPEDITION;
         } else {
-            return board[r][c];
+            return board[pos];
         }
     }
 
-    let brows = board.len() - 2;
-    let bcols = board[0].len() - 2;
+    let brows = board.rows() - 2;
+    let bcols = board.cols() - 2;
     let roff = elapsed % brows;
     let coff = elapsed % bcols;
     //println!("r={} roff={} brows={}", r, roff, brows);
-    let is_up = board[(r + roff - 1) % brows + 1][c] == UP;
-    let is_down = board[(r + brows - roff - 1) % brows + 1][c] == DOWN;
-    let is_left = board[r][(c + coff - 1) % bcols + 1] == LEFT;
-    let is_right = board[r][(c + bcols - coff - 1) % bcols + 1] == RIGHT;
+    let is_up = board[((r + roff - 1) % brows + 1, c)] == UP;
+    let is_down = board[((r + brows - roff - 1) % brows + 1, c)] == DOWN;
+    let is_left = board[(r, (c + coff - 1) % bcols + 1)] == LEFT;
+    let is_right = board[(r, (c + bcols - coff - 1) % bcols + 1)] == RIGHT;
 
     if *you == pos {
         assert!(!is_up);
@@ -1,11 +1,15 @@
 use std::fmt::Display;
 
-pub fn part1(input: String, _vis: bool) -> Box<dyn Display> {
+use crate::vis::Visualizer;
+
+#[aoc(year = 2022, day = 25, part = 1)]
+pub fn part1(input: String, _vis: &Visualizer) -> Box<dyn Display> {
     let total = input.lines().map(decode).sum();
     Box::new(encode(total))
 }
 
-pub fn part2(_input: String, _vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2022, day = 25, part = 2, unsolved)]
+pub fn part2(_input: String, _vis: &Visualizer) -> Box<dyn Display> {
     Box::new("todo")
 }
 
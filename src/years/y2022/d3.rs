@@ -3,7 +3,10 @@ use std::{
     fmt::Display,
 };
 
-pub fn part1(input: String, _: bool) -> Box<dyn Display> {
+use crate::vis::Visualizer;
+
+#[aoc(year = 2022, day = 3, part = 1)]
+pub fn part1(input: String, _: &Visualizer) -> Box<dyn Display> {
     let sacks = input.lines().map(compartmentalize);
     let common = sacks.map(compare_compartments);
     let priorities = common.map(priority);
@@ -11,7 +14,8 @@ pub fn part1(input: String, _: bool) -> Box<dyn Display> {
     Box::new(total_priority)
 }
 
-pub fn part2(input: String, _: bool) -> Box<dyn Display> {
+#[aoc(year = 2022, day = 3, part = 2)]
+pub fn part2(input: String, _: &Visualizer) -> Box<dyn Display> {
     let sacks = input.lines().collect::<Vec<&str>>();
     let groups = sacks.chunks(3);
     let common = groups.map(compare_groups);
@@ -20,14 +24,16 @@ pub fn part2(input: String, _: bool) -> Box<dyn Display> {
     Box::new(total_priority)
 }
 
-pub fn part1_set(input: String, _: bool) -> Box<dyn Display> {
+#[aoc(year = 2022, day = 3, part = 1, label = "with set")]
+pub fn part1_set(input: String, _: &Visualizer) -> Box<dyn Display> {
     let sacks = input.lines().map(compartmentalize);
     let common = sacks.map(compare_compartments_set);
     let total_priority: u32 = common.sum();
     Box::new(total_priority)
 }
 
-pub fn part2_set(input: String, _: bool) -> Box<dyn Display> {
+#[aoc(year = 2022, day = 3, part = 2, label = "with set")]
+pub fn part2_set(input: String, _: &Visualizer) -> Box<dyn Display> {
     let sacks = input.lines().collect::<Vec<&str>>();
     let groups = sacks.chunks(3);
     let priorities = groups.map(compare_groups_set);
@@ -35,7 +41,8 @@ pub fn part2_set(input: String, _: bool) -> Box<dyn Display> {
     Box::new(total_priority)
 }
 
-pub fn part2_set2(input: String, _: bool) -> Box<dyn Display> {
+#[aoc(year = 2022, day = 3, part = 2, label = "with fewer sets")]
+pub fn part2_set2(input: String, _: &Visualizer) -> Box<dyn Display> {
     let sacks = input.lines().collect::<Vec<&str>>();
     let groups = sacks.chunks(3);
     let common = groups.map(compare_groups_set2);
@@ -44,7 +51,8 @@ pub fn part2_set2(input: String, _: bool) -> Box<dyn Display> {
     Box::new(total_priority)
 }
 
-pub fn part2_bytes(input: String, _: bool) -> Box<dyn Display> {
+#[aoc(year = 2022, day = 3, part = 2, label = "with bytes")]
+pub fn part2_bytes(input: String, _: &Visualizer) -> Box<dyn Display> {
     //let mut priorities = vec![0; 256];
     //let little_a = b'a' as usize;
     //let big_a = b'A' as usize;
@@ -1,6 +1,9 @@
 use std::fmt::Display;
 
-pub fn part1(input: String, _: bool) -> Box<dyn Display> {
+use crate::vis::Visualizer;
+
+#[aoc(year = 2022, day = 4, part = 1)]
+pub fn part1(input: String, _: &Visualizer) -> Box<dyn Display> {
     fn completely_contains(x: &(u8, u8, u8, u8)) -> bool {
         let (a1, b1, a2, b2) = x;
         (a1 <= a2 && b1 >= b2) || (a2 <= a1 && b2 >= b1)
@@ -8,7 +11,8 @@ pub fn part1(input: String, _: bool) -> Box<dyn Display> {
     Box::new(input.lines().map(parse).filter(completely_contains).count())
 }
 
-pub fn part2(input: String, _: bool) -> Box<dyn Display> {
+#[aoc(year = 2022, day = 4, part = 2)]
+pub fn part2(input: String, _: &Visualizer) -> Box<dyn Display> {
     fn overlaps(x: &(u8, u8, u8, u8)) -> bool {
         let (a1, b1, a2, b2) = x;
         (a1 <= a2 && b1 >= a2) || (a2 <= a1 && b2 >= a1)
@@ -1,5 +1,7 @@
 use std::fmt::Display;
 
+use crate::vis::{Frame, Visualizer};
+
 type Stack = Vec<char>;
 struct Move {
     count: usize,
@@ -7,21 +9,22 @@ struct Move {
     to: usize,
 }
 
-pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2022, day = 5, part = 1)]
+pub fn part1(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let (mut stacks, moves) = parse(&input);
-    if vis {
-        show_stacks(&stacks);
+    if vis.on() {
+        show_stacks(&stacks, vis);
     }
     for m in moves {
-        if vis {
-            println!("{}", m);
+        if vis.on() {
+            vis.log(&m);
         }
         for _ in 0..m.count {
             let moved = stacks[m.from].pop().unwrap();
             stacks[m.to].push(moved);
         }
-        if vis {
-            show_stacks(&stacks);
+        if vis.on() {
+            show_stacks(&stacks, vis);
         }
     }
     Box::new(
@@ -32,21 +35,22 @@ pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
     )
 }
 
-pub fn part1_transpose(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2022, day = 5, part = 1, label = "transpose during parse")]
+pub fn part1_transpose(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let (mut stacks, moves) = parse_transpose(&input);
-    if vis {
-        show_stacks(&stacks);
+    if vis.on() {
+        show_stacks(&stacks, vis);
     }
     for m in moves {
-        if vis {
-            println!("{}", m);
+        if vis.on() {
+            vis.log(&m);
         }
         for _ in 0..m.count {
             let moved = stacks[m.from].pop().unwrap();
             stacks[m.to].push(moved);
         }
-        if vis {
-            show_stacks(&stacks);
+        if vis.on() {
+            show_stacks(&stacks, vis);
         }
     }
     Box::new(
@@ -57,20 +61,21 @@ pub fn part1_transpose(input: String, vis: bool) -> Box<dyn Display> {
     )
 }
 
-pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2022, day = 5, part = 2)]
+pub fn part2(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let (mut stacks, moves) = parse(&input);
-    if vis {
-        show_stacks(&stacks);
+    if vis.on() {
+        show_stacks(&stacks, vis);
     }
     for m in moves {
-        if vis {
-            println!("{}", m);
+        if vis.on() {
+            vis.log(&m);
         }
         let i = stacks[m.from].len() - m.count;
         let mut moved = stacks[m.from].split_off(i);
         stacks[m.to].append(&mut moved);
-        if vis {
-            show_stacks(&stacks);
+        if vis.on() {
+            show_stacks(&stacks, vis);
         }
     }
     Box::new(
@@ -81,14 +86,15 @@ pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
     )
 }
 
-pub fn part2_slices(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2022, day = 5, part = 2, label = "with fewer allocations")]
+pub fn part2_slices(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let (mut stacks, moves) = parse(&input);
-    if vis {
-        show_stacks(&stacks);
+    if vis.on() {
+        show_stacks(&stacks, vis);
     }
     for m in moves {
-        if vis {
-            println!("{}", m);
+        if vis.on() {
+            vis.log(&m);
         }
         let new_len = stacks[m.from].len() - m.count;
         for i in 0..m.count {
@@ -96,8 +102,8 @@ pub fn part2_slices(input: String, vis: bool) -> Box<dyn Display> {
             stacks[m.to].push(moved);
         }
         stacks[m.from].resize(new_len, Default::default());
-        if vis {
-            show_stacks(&stacks);
+        if vis.on() {
+            show_stacks(&stacks, vis);
         }
     }
     Box::new(
@@ -108,22 +114,22 @@ pub fn part2_slices(input: String, vis: bool) -> Box<dyn Display> {
     )
 }
 
-fn show_stacks(stacks: &Vec<Stack>) {
-    let mut i = stacks.iter().map(|s| s.len()).max().unwrap();
-    while i > 0 {
-        i -= 1;
-        for stack in stacks {
-            match stack.get(i) {
-                None => print!("    "),
-                Some(c) => print!("[{}] ", c),
-            };
-        }
-        println!();
-    }
-    for i in 0..stacks.len() {
-        print!(" {}  ", i + 1);
-    }
-    println!();
+fn show_stacks(stacks: &[Stack], vis: &Visualizer) {
+    let height = stacks.iter().map(|s| s.len()).max().unwrap();
+    let mut rows: Vec<String> = (0..height)
+        .rev()
+        .map(|i| {
+            stacks
+                .iter()
+                .map(|stack| match stack.get(i) {
+                    None => "    ".to_string(),
+                    Some(c) => format!("[{}] ", c),
+                })
+                .collect()
+        })
+        .collect();
+    rows.push((0..stacks.len()).map(|i| format!(" {}  ", i + 1)).collect());
+    vis.frame(Frame::new("", rows));
 }
 
 impl std::fmt::Display for Move {
@@ -1,26 +1,30 @@
 use std::fmt::Display;
 
-pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
+use crate::vis::Visualizer;
+
+#[aoc(year = 2022, day = 6, part = 1)]
+pub fn part1(input: String, vis: &Visualizer) -> Box<dyn Display> {
     find_tag(&input, 4, vis)
 }
 
-pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2022, day = 6, part = 2)]
+pub fn part2(input: String, vis: &Visualizer) -> Box<dyn Display> {
     find_tag(&input, 14, vis)
 }
 
-fn find_tag(input: &str, size: usize, vis: bool) -> Box<dyn Display> {
+fn find_tag(input: &str, size: usize, vis: &Visualizer) -> Box<dyn Display> {
     let input = input.as_bytes();
     for (i, vals) in input.windows(size).enumerate() {
         if none_match(vals) {
             let j = i + size;
-            if vis {
+            if vis.on() {
                 unsafe {
-                    println!(
+                    vis.log(format!(
                         "{} [ {} ] {}",
                         std::str::from_utf8_unchecked(&input[..i]),
                         std::str::from_utf8_unchecked(&input[i..j]),
                         std::str::from_utf8_unchecked(&input[j..])
-                    )
+                    ))
                 };
             }
             return Box::new(i + vals.len());
@@ -40,35 +44,37 @@ fn none_match(v: &[u8]) -> bool {
     true
 }
 
-pub fn part1_alt(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2022, day = 6, part = 1, label = "fewer comparisons")]
+pub fn part1_alt(input: String, vis: &Visualizer) -> Box<dyn Display> {
     find_tag2(&input, 4, vis)
 }
 
-pub fn part2_alt(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2022, day = 6, part = 2, label = "fewer comparisons")]
+pub fn part2_alt(input: String, vis: &Visualizer) -> Box<dyn Display> {
     find_tag2(&input, 14, vis)
 }
 
-fn find_tag2(input: &str, size: usize, vis: bool) -> Box<dyn Display> {
-    if vis {
-        println!("input: {}", input);
-        println!("size: {}", size);
+fn find_tag2(input: &str, size: usize, vis: &Visualizer) -> Box<dyn Display> {
+    if vis.on() {
+        vis.log(format!("input: {}", input));
+        vis.log(format!("size: {}", size));
     }
     let input = input.as_bytes();
     let mut min_good = size;
     'search: for i in 1..input.len() {
-        if vis {
-            println!("[{}] min = {}", i, min_good);
+        if vis.on() {
+            vis.log(format!("[{}] min = {}", i, min_good));
         }
         if i == min_good {
             let s = i - size;
-            if vis {
+            if vis.on() {
                 unsafe {
-                    println!(
+                    vis.log(format!(
                         "{} [ {} ] {}",
                         std::str::from_utf8_unchecked(&input[..s]),
                         std::str::from_utf8_unchecked(&input[s..i]),
                         std::str::from_utf8_unchecked(&input[i..])
-                    )
+                    ))
                 };
             }
             return Box::new(i);
@@ -76,10 +82,10 @@ fn find_tag2(input: &str, size: usize, vis: bool) -> Box<dyn Display> {
         let c = input[i];
         for off in 1..size {
             if off > i {
-                if vis {
-                    println!("{}: {}: too short", i, unsafe {
+                if vis.on() {
+                    vis.log(format!("{}: {}: too short", i, unsafe {
                         std::str::from_utf8_unchecked(&input[0..=i])
-                    });
+                    }));
                 }
                 continue 'search;
             }
@@ -89,29 +95,29 @@ fn find_tag2(input: &str, size: usize, vis: bool) -> Box<dyn Display> {
                 if maybe > min_good {
                     min_good = maybe;
                 }
-                if vis {
+                if vis.on() {
                     unsafe {
-                        println!(
+                        vis.log(format!(
                             "{}/{}: {}: match -> {} -> {}",
                             j,
                             i,
                             std::str::from_utf8_unchecked(&input[j..=i]),
                             maybe,
                             min_good,
-                        )
+                        ))
                     };
                 }
                 continue 'search;
             }
         }
-        if vis && i >= size {
+        if vis.on() && i >= size {
             unsafe {
-                println!(
+                vis.log(format!(
                     "{}: {}: no match for {}",
                     i,
                     std::str::from_utf8_unchecked(&input[i - size..=i]),
                     std::char::from_u32_unchecked(c as u32),
-                )
+                ))
             };
         }
     }
@@ -1,6 +1,9 @@
 use std::fmt::Display;
 
-pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
+use crate::vis::Visualizer;
+
+#[aoc(year = 2022, day = 7, part = 1)]
+pub fn part1(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let fs = parse_fs(&input, vis);
     let mut total = 0;
     for dir in fs.dirs() {
@@ -12,7 +15,8 @@ pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
     Box::new(total)
 }
 
-pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2022, day = 7, part = 2)]
+pub fn part2(input: String, vis: &Visualizer) -> Box<dyn Display> {
     const TOT_SPACE: usize = 70000000;
     const NEED: usize = 30000000;
     let fs = parse_fs(&input, vis);
@@ -27,7 +31,7 @@ pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
     Box::new(will_free)
 }
 
-fn parse_fs(input: &str, vis: bool) -> FS {
+fn parse_fs(input: &str, vis: &Visualizer) -> FS {
     fn collect(mut stack: Vec<(&str, Vec<(String, FS)>)>) -> FS {
         let mut res = None;
         while let Some((name, mut entries)) = stack.pop() {
@@ -65,8 +69,8 @@ fn parse_fs(input: &str, vis: bool) -> FS {
             "$ ls" => (),
             _ => {
                 let (size, name) = line.split_once(' ').unwrap();
-                if vis {
-                    println!("{} => ({}, {})", line, name, size);
+                if vis.on() {
+                    vis.log(format!("{} => ({}, {})", line, name, size));
                 }
                 if size != "dir" {
                     stack
@@ -94,7 +98,7 @@ impl FS {
         }
     }
 
-    fn dirs(&self) -> DirIter {
+    fn dirs(&self) -> DirIter<'_> {
         DirIter {
             remaining: vec![self],
         }
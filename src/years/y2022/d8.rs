@@ -1,31 +1,42 @@
 use std::{collections::HashSet, fmt::Display};
 
-pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
+use crate::{
+    grid::{Grid, Pos},
+    vis::Visualizer,
+};
+
+#[aoc(year = 2022, day = 8, part = 1)]
+pub fn part1(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let heights = parse(&input);
-    if vis {
-        for row in &heights {
-            println!("{:?}", row);
+    if vis.on() {
+        for row in heights.iter_rows() {
+            vis.log(format!("{:?}", row));
         }
     }
     let mut seen = HashSet::new();
-    let rows = heights.len();
-    let cols = heights[0].len();
-    for (row, heights_row) in heights.iter().enumerate() {
+    let rows = heights.rows();
+    let cols = heights.cols();
+    for (row, heights_row) in heights.iter_rows().enumerate() {
         let mut max_height_from_left = -1;
         let mut max_height_from_right = -1;
         for col in 0..cols {
             let h = heights_row[col];
             if h > max_height_from_left {
-                if vis {
-                    println!("can see > row={} col={} val={}", row, col, h);
+                if vis.on() {
+                    vis.log(format!("can see > row={} col={} val={}", row, col, h));
                 }
                 seen.insert((row, col));
                 max_height_from_left = h
             }
             let h = heights_row[cols - col - 1];
             if h > max_height_from_right {
-                if vis {
-                    println!("can see < row={} col={} val={}", row, cols - col - 1, h);
+                if vis.on() {
+                    vis.log(format!(
+                        "can see < row={} col={} val={}",
+                        row,
+                        cols - col - 1,
+                        h
+                    ));
                 }
                 seen.insert((row, cols - col - 1));
                 max_height_from_right = h
@@ -35,19 +46,23 @@ pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
     for col in 0..cols {
         let mut max_height_from_top = -1;
         let mut max_height_from_bottom = -1;
-        for row in 0..rows {
-            let h = heights[row][col];
+        for (row, &h) in heights.col(col).enumerate() {
             if h > max_height_from_top {
-                if vis {
-                    println!("can see v row={} col={} val={}", row, col, h);
+                if vis.on() {
+                    vis.log(format!("can see v row={} col={} val={}", row, col, h));
                 }
                 seen.insert((row, col));
                 max_height_from_top = h
             }
-            let h = heights[rows - row - 1][col];
+            let h = heights[(rows - row - 1, col)];
             if h > max_height_from_bottom {
-                if vis {
-                    println!("can see ^ row={} col={} val={}", rows - row - 1, col, h);
+                if vis.on() {
+                    vis.log(format!(
+                        "can see ^ row={} col={} val={}",
+                        rows - row - 1,
+                        col,
+                        h
+                    ));
                 }
                 seen.insert((rows - row - 1, col));
                 max_height_from_bottom = h
@@ -57,16 +72,23 @@ pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
     Box::new(seen.len())
 }
 
-pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2022, day = 8, part = 2)]
+pub fn part2(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let heights = parse(&input);
-    let rows = heights.len();
-    let cols = heights[0].len();
+    let rows = heights.rows();
+    let cols = heights.cols();
     let mut max_score = 0;
     for row in 1..rows - 1 {
         for col in 1..cols - 1 {
-            let score = scenic_score(&heights, row, col);
-            if vis {
-                println!("({},{})={} => score={}", row, col, heights[row][col], score);
+            let score = scenic_score(&heights, (row, col));
+            if vis.on() {
+                vis.log(format!(
+                    "({},{})={} => score={}",
+                    row,
+                    col,
+                    heights[(row, col)],
+                    score
+                ));
             }
             if score > max_score {
                 max_score = score;
@@ -76,41 +98,28 @@ pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
     Box::new(max_score)
 }
 
-fn scenic_score(heights: &[Vec<i8>], row: usize, col: usize) -> usize {
-    vd(heights, (row, col), (1, 0))
-        * vd(heights, (row, col), (-1, 0))
-        * vd(heights, (row, col), (0, 1))
-        * vd(heights, (row, col), (0, -1))
+fn scenic_score(heights: &Grid<i8>, pos: Pos) -> usize {
+    vd(heights, pos, (1, 0))
+        * vd(heights, pos, (-1, 0))
+        * vd(heights, pos, (0, 1))
+        * vd(heights, pos, (0, -1))
 }
-fn vd(heights: &[Vec<i8>], pos: (usize, usize), off: (isize, isize)) -> usize {
-    let (mut row, mut col) = pos;
-    let (roff, coff) = off;
-    let h = heights[row][col];
+fn vd(heights: &Grid<i8>, pos: Pos, off: (isize, isize)) -> usize {
+    let h = heights[pos];
+    let mut pos = pos;
     let mut dist = 0;
-    loop {
-        row = (row as isize + roff) as usize;
-        col = (col as isize + coff) as usize;
-        match heights.get(row) {
-            None => break,
-            Some(row) => match row.get(col) {
-                None => break,
-                Some(t) => {
-                    dist += 1;
-                    if *t >= h {
-                        break;
-                    }
-                }
-            },
-        };
+    while let Some(next) = heights.step(pos, off) {
+        dist += 1;
+        if heights[next] >= h {
+            break;
+        }
+        pos = next;
     }
     dist
 }
 
-fn parse(input: &str) -> Vec<Vec<i8>> {
-    fn parse_line(line: &str) -> Vec<i8> {
-        line.as_bytes().iter().map(|b| (b - b'0') as i8).collect()
-    }
-    input.lines().map(parse_line).collect()
+fn parse(input: &str) -> Grid<i8> {
+    Grid::parse(input, |c| c.to_digit(10).unwrap() as i8)
 }
 
 #[cfg(test)]
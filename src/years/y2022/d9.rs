@@ -1,14 +1,18 @@
 use std::{cmp::Ordering, collections::HashSet, fmt::Display};
 
-pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
+use crate::vis::Visualizer;
+
+#[aoc(year = 2022, day = 9, part = 1)]
+pub fn part1(input: String, vis: &Visualizer) -> Box<dyn Display> {
     doit(input, vis, 2)
 }
 
-pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2022, day = 9, part = 2)]
+pub fn part2(input: String, vis: &Visualizer) -> Box<dyn Display> {
     doit(input, vis, 10)
 }
 
-fn doit(input: String, vis: bool, size: usize) -> Box<dyn Display> {
+fn doit(input: String, vis: &Visualizer, size: usize) -> Box<dyn Display> {
     let mut positions = vec![(0, 0); size];
     let mut visited = HashSet::new();
     visited.insert((0, 0));
@@ -16,14 +20,14 @@ fn doit(input: String, vis: bool, size: usize) -> Box<dyn Display> {
         let ((dx, dy), steps) = parse(line);
         for _ in 0..steps {
             let (mut hx, mut hy) = positions[0];
-            if vis {
-                println!("head: ({},{}) + ({},{})", hx, hy, dx, dy);
+            if vis.on() {
+                vis.log(format!("head: ({},{}) + ({},{})", hx, hy, dx, dy));
             }
             hx += dx;
             hy += dy;
             positions[0] = (hx, hy);
-            if vis {
-                println!("  [{}] ({},{})", 0, hx, hy);
+            if vis.on() {
+                vis.log(format!("  [{}] ({},{})", 0, hx, hy));
             }
             for (i, pos) in positions.iter_mut().enumerate().skip(1) {
                 let (mut tx, mut ty) = pos;
@@ -58,8 +62,8 @@ fn doit(input: String, vis: bool, size: usize) -> Box<dyn Display> {
                 } else {
                     break;
                 }
-                if vis {
-                    println!("  [{}] ({},{})", i, tx, ty);
+                if vis.on() {
+                    vis.log(format!("  [{}] ({},{})", i, tx, ty));
                 }
                 *pos = (tx, ty);
                 hx = tx;
@@ -67,8 +71,8 @@ fn doit(input: String, vis: bool, size: usize) -> Box<dyn Display> {
             }
             visited.insert(*positions.last().unwrap());
         }
-        if vis {
-            println!("{} => {:?}", line, positions);
+        if vis.on() {
+            vis.log(format!("{} => {:?}", line, positions));
         }
     }
     Box::new(visited.len())
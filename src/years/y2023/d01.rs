@@ -1,6 +1,9 @@
 use std::fmt::Display;
 
-pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
+use crate::vis::Visualizer;
+
+#[aoc(year = 2023, day = 1, part = 1)]
+pub fn part1(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let key = [
         ("0", 0),
         ("1", 1),
@@ -21,7 +24,8 @@ pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
     )
 }
 
-pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2023, day = 1, part = 2)]
+pub fn part2(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let key = [
         ("0", 0),
         ("1", 1),
@@ -52,7 +56,7 @@ pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
     )
 }
 
-fn get_cal_new(line: &str, key: &[(&'static str, u32)], vis: bool) -> u32 {
+fn get_cal_new(line: &str, key: &[(&'static str, u32)], vis: &Visualizer) -> u32 {
     let mut first = None;
     let mut last = None;
     for k in key {
@@ -67,47 +71,49 @@ fn get_cal_new(line: &str, key: &[(&'static str, u32)], vis: bool) -> u32 {
             (_, x) => x,
         };
     }
-    if vis {
-        println!("{line} => {first:?} {last:?}");
+    if vis.on() {
+        vis.log(format!("{line} => {first:?} {last:?}"));
     }
     first.unwrap().1 * 10 + last.unwrap().1
 }
 
-pub fn orig_part1(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2023, day = 1, part = 1, label = "orig")]
+pub fn orig_part1(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let vals: Vec<u32> = input.lines().map(|line| get_cal(line, vis)).collect();
-    if vis {
-        println!("{:?}", vals);
+    if vis.on() {
+        vis.log(format!("{:?}", vals));
     }
     let sum: u32 = vals.into_iter().sum();
     Box::new(sum)
 }
 
-pub fn orig_part2(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2023, day = 1, part = 2, label = "orig")]
+pub fn orig_part2(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let vals: Vec<u32> = input.lines().map(|line| get_cal2(line, vis)).collect();
-    if vis {
-        println!("{:?}", vals);
+    if vis.on() {
+        vis.log(format!("{:?}", vals));
     }
     let sum: u32 = vals.into_iter().sum();
     Box::new(sum)
 }
 
-fn get_cal(line: &str, vis: bool) -> u32 {
+fn get_cal(line: &str, vis: &Visualizer) -> u32 {
     let mut numbers = Vec::new();
     for c in line.chars() {
-        if vis {
-            println!("{c}");
+        if vis.on() {
+            vis.log(c);
         }
         if let Some(d) = c.to_digit(10) {
             numbers.push(d);
         }
     }
-    if vis {
-        println!("=> {:?}", numbers);
+    if vis.on() {
+        vis.log(format!("=> {:?}", numbers));
     }
     numbers.first().unwrap() * 10 + numbers.last().unwrap()
 }
 
-fn get_cal2(mut line: &str, vis: bool) -> u32 {
+fn get_cal2(mut line: &str, vis: &Visualizer) -> u32 {
     let orig = line;
     let mut numbers = Vec::new();
     while !line.is_empty() {
@@ -134,8 +140,8 @@ fn get_cal2(mut line: &str, vis: bool) -> u32 {
         }
         line = &line[1..];
     }
-    if vis {
-        println!("{orig} => {:?}", numbers);
+    if vis.on() {
+        vis.log(format!("{orig} => {:?}", numbers));
     }
     numbers.first().unwrap() * 10 + numbers.last().unwrap()
 }
@@ -160,4 +166,8 @@ zoneight234
 7pqrstsixteen",
         part2 => 281,
         orig_part2 => 281);
+
+    crate::test::aoc_examples!(2023, 1,
+        1 => part1, 1 => orig_part1,
+        2 => part2, 2 => orig_part2);
 }
@@ -3,7 +3,10 @@ use std::fmt::Display;
 
 use itertools::Itertools;
 
-pub fn part1_iterate(input: String, vis: bool) -> Box<dyn Display> {
+use crate::vis::Visualizer;
+
+#[aoc(year = 2023, day = 2, part = 1, label = "itertools")]
+pub fn part1_iterate(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let mut res = 0;
 
     'lineloop: for line in input.lines() {
@@ -19,28 +22,29 @@ pub fn part1_iterate(input: String, vis: bool) -> Box<dyn Display> {
 
         for (n, l) in tokens.tuples() {
             let n: u32 = n.parse().unwrap();
-            if n > match l.trim_end_matches(&[',', ';']) {
+            if n > match l.trim_end_matches([',', ';']) {
                 "red" => 12,
                 "green" => 13,
                 "blue" => 14,
                 _ => panic!("illegal! {n} {l}"),
             } {
-                if vis {
-                    println!("{line}: too many {l}: {n}");
+                if vis.on() {
+                    vis.log(format!("{line}: too many {l}: {n}"));
                 }
                 continue 'lineloop;
             }
         }
 
-        if vis {
-            println!("{line}: ok!");
+        if vis.on() {
+            vis.log(format!("{line}: ok!"));
         }
         res += id;
     }
     Box::new(res)
 }
 
-pub fn part2_iterate(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2023, day = 2, part = 2, label = "itertools")]
+pub fn part2_iterate(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let mut res = 0;
 
     for line in input.lines() {
@@ -52,22 +56,23 @@ pub fn part2_iterate(input: String, vis: bool) -> Box<dyn Display> {
 
         for (n, l) in tokens.tuples() {
             let n: u32 = n.parse().unwrap();
-            match l.trim_end_matches(&[',', ';']) {
+            match l.trim_end_matches([',', ';']) {
                 "red" => red = max(red, n),
                 "green" => green = max(green, n),
                 "blue" => blue = max(blue, n),
                 _ => panic!("illegal! {n} {l}"),
             };
         }
-        if vis {
-            println!("{line} => red={red} green={green} blue={blue}");
+        if vis.on() {
+            vis.log(format!("{line} => red={red} green={green} blue={blue}"));
         }
         res += red * green * blue;
     }
     Box::new(res)
 }
 
-pub fn part1_regexp(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2023, day = 2, part = 1, label = "regexp")]
+pub fn part1_regexp(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let mut res = 0;
     for (id, moves) in parse_games(&input, vis) {
         if moves
@@ -80,11 +85,12 @@ pub fn part1_regexp(input: String, vis: bool) -> Box<dyn Display> {
     Box::new(res)
 }
 
-pub fn part2_regexp(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2023, day = 2, part = 2, label = "regexp")]
+pub fn part2_regexp(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let mut res = 0;
-    for (_, moves) in parse_games(&input, false) {
-        if vis {
-            println!("{moves:?}");
+    for (_, moves) in parse_games(&input, &Visualizer::off()) {
+        if vis.on() {
+            vis.log(format!("{moves:?}"));
         }
         let req = moves
             .into_iter()
@@ -94,8 +100,8 @@ pub fn part2_regexp(input: String, vis: bool) -> Box<dyn Display> {
                 blue: max(a.blue, b.blue),
             })
             .unwrap();
-        if vis {
-            println!(" ==> {req:?}");
+        if vis.on() {
+            vis.log(format!(" ==> {req:?}"));
         }
         res += req.red * req.green * req.blue;
     }
@@ -109,15 +115,15 @@ struct Move {
     blue: u32,
 }
 
-fn parse_games(input: &str, vis: bool) -> Vec<(u32, Vec<Move>)> {
+fn parse_games(input: &str, vis: &Visualizer) -> Vec<(u32, Vec<Move>)> {
     let game_re = regex::Regex::new(r"Game (?<id>\d+): (?<moves>.*)").unwrap();
     let move_re = regex::Regex::new(r"(?<count>\d+) (?<color>\w+)").unwrap();
 
     let mut res = Vec::new();
 
     for line in input.lines() {
-        if vis {
-            println!("parse_game({line:?})");
+        if vis.on() {
+            vis.log(format!("parse_game({line:?})"));
         }
         let vals = game_re.captures(line).unwrap();
         let id = vals.name("id").unwrap().as_str().parse().unwrap();
@@ -138,8 +144,8 @@ fn parse_games(input: &str, vis: bool) -> Vec<(u32, Vec<Move>)> {
             }
             moves.push(Move { red, green, blue });
         }
-        if vis {
-            println!("=> ({id}, {moves:?})");
+        if vis.on() {
+            vis.log(format!("=> ({id}, {moves:?})"));
         }
         res.push((id, moves));
     }
@@ -147,7 +153,8 @@ fn parse_games(input: &str, vis: bool) -> Vec<(u32, Vec<Move>)> {
     res
 }
 
-pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2023, day = 2, part = 1)]
+pub fn part1(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let games = input.lines().map(|l| parse_game(l, vis));
     let mut res = 0;
     for (id, moves) in games {
@@ -164,36 +171,36 @@ pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
     Box::new(res)
 }
 
-fn parse_game(line: &str, vis: bool) -> (u32, Vec<(u32, u32, u32)>) {
-    if vis {
-        println!("parse_game({line:?})");
+fn parse_game(line: &str, vis: &Visualizer) -> (u32, Vec<(u32, u32, u32)>) {
+    if vis.on() {
+        vis.log(format!("parse_game({line:?})"));
     }
     let (id, moves) = line.split_once(':').unwrap();
-    if vis {
-        println!(" id = {id}");
+    if vis.on() {
+        vis.log(format!(" id = {id}"));
     }
     let (_, id) = id.split_once(' ').unwrap();
-    if vis {
-        println!(" id = {id:?}");
+    if vis.on() {
+        vis.log(format!(" id = {id:?}"));
     }
     let id = id.parse().unwrap();
     let moves = moves.split(';').map(|m| parse_move(m, vis)).collect();
-    if vis {
-        println!(" => ({id}, {moves:?})");
+    if vis.on() {
+        vis.log(format!(" => ({id}, {moves:?})"));
     }
     (id, moves)
 }
 
-fn parse_move(m: &str, vis: bool) -> (u32, u32, u32) {
+fn parse_move(m: &str, vis: &Visualizer) -> (u32, u32, u32) {
     let mut res = (0, 0, 0);
     for group in m.split(',') {
-        if vis {
-            println!("  parse {group:?}");
+        if vis.on() {
+            vis.log(format!("  parse {group:?}"));
         }
         let (count, color) = group.trim().split_once(' ').unwrap();
         let count = count.parse().unwrap();
-        if vis {
-            println!("  -> count = {count}");
+        if vis.on() {
+            vis.log(format!("  -> count = {count}"));
         }
         match color.trim() {
             "red" => res.0 = count,
@@ -205,19 +212,20 @@ fn parse_move(m: &str, vis: bool) -> (u32, u32, u32) {
     res
 }
 
-pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
-    let games = input.lines().map(|l| parse_game(l, false));
+#[aoc(year = 2023, day = 2, part = 2)]
+pub fn part2(input: String, vis: &Visualizer) -> Box<dyn Display> {
+    let games = input.lines().map(|l| parse_game(l, &Visualizer::off()));
     let mut res = 0;
     for (_, moves) in games {
-        if vis {
-            println!("{moves:?}");
+        if vis.on() {
+            vis.log(format!("{moves:?}"));
         }
         let mut cubes = (0, 0, 0);
         for m in moves {
             cubes = (max(cubes.0, m.0), max(cubes.1, m.1), max(cubes.2, m.2));
         }
-        if vis {
-            println!(" ==> {cubes:?}");
+        if vis.on() {
+            vis.log(format!(" ==> {cubes:?}"));
         }
         res += cubes.0 * cubes.1 * cubes.2;
     }
@@ -243,7 +251,7 @@ Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green",
 
 #[cfg(test)]
 mod test_new {
-    const TEST_INPUT: &'static str = r"Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+    const TEST_INPUT: &str = r"Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
 Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
 Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
 Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
@@ -2,12 +2,15 @@ use std::cmp::{max, min};
 use std::collections::{HashMap, HashSet};
 use std::fmt::Display;
 
+use crate::vis::Visualizer;
+
 // Handy references:
 // - https://doc.rust-lang.org/std/iter/trait.Iterator.html
 // - https://docs.rs/itertools/0.8.2/itertools/trait.Itertools.html
 // - https://docs.rs/regex/latest/regex/struct.Regex.html
 
-pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2023, day = 3, part = 1, label = "d03::part1")]
+pub fn part1(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let things = parse(&input);
 
     let mut symbols = HashSet::new();
@@ -16,14 +19,14 @@ pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
             symbols.insert((t.row, t.col));
         }
     }
-    if vis {
-        println!("symbols at {symbols:?}");
+    if vis.on() {
+        vis.log(format!("symbols at {symbols:?}"));
     }
     let mut sum = 0;
     'things: for t in &things {
         if let TH::Number(n) = t.th {
-            if vis {
-                println!("is {n} ({}, {}) near a symbol?", t.row, t.col);
+            if vis.on() {
+                vis.log(format!("is {n} ({}, {}) near a symbol?", t.row, t.col));
             }
             let min_row = if t.row > 0 { t.row - 1 } else { t.row };
             let max_row = t.row + 1;
@@ -33,30 +36,31 @@ pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
                 for c in min_col..=max_col {
                     if symbols.contains(&(r, c)) {
                         sum += n;
-                        if vis {
-                            println!("yes! ({r}, {c}) sum is now {sum}");
+                        if vis.on() {
+                            vis.log(format!("yes! ({r}, {c}) sum is now {sum}"));
                         }
                         continue 'things;
                     }
                 }
             }
-            if vis {
-                println!("no!");
+            if vis.on() {
+                vis.log("no!");
             }
         }
     }
     Box::new(sum)
 }
 
-pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2023, day = 3, part = 2, label = "d03::part2")]
+pub fn part2(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let things = parse(&input);
 
     let mut sum = 0;
 
     for g in &things {
         if matches!(g.th, TH::Symbol('*')) {
-            if vis {
-                println!("GEAR! {}, {}", g.row, g.col);
+            if vis.on() {
+                vis.log(format!("GEAR! {}, {}", g.row, g.col));
             }
             let min_row = if g.row > 0 { g.row - 1 } else { g.row };
             let max_row = g.row + 1;
@@ -89,7 +93,8 @@ pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
     Box::new(sum)
 }
 
-pub fn part2_from_oliver(input: String, _vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2023, day = 3, part = 2, label = "d03::part2_from_oliver")]
+pub fn part2_from_oliver(input: String, _vis: &Visualizer) -> Box<dyn Display> {
     let things = parse(&input);
 
     let mut stars = HashSet::new();
@@ -168,7 +173,7 @@ enum TH {
 
 #[cfg(test)]
 mod test {
-    const TEST_INPUT: &'static str = r"467..114..
+    const TEST_INPUT: &str = r"467..114..
 ...*......
 ..35..633.
 ......#...
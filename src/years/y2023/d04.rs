@@ -3,18 +3,21 @@ use std::{
     fmt::Display,
 };
 
+use crate::vis::Visualizer;
+
 // Handy references:
 // - https://doc.rust-lang.org/std/iter/trait.Iterator.html
 // - https://docs.rs/itertools/0.8.2/itertools/trait.Itertools.html
 // - https://docs.rs/regex/latest/regex/struct.Regex.html
 
-pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2023, day = 4, part = 1, label = "d04::part1")]
+pub fn part1(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let mut score = 0;
     for line in input.lines() {
         let (_, vals) = line.split_once(':').unwrap();
         let (winning, mine) = vals.split_once('|').unwrap();
-        if vis {
-            println!("winning={winning:?} mine={mine:?}");
+        if vis.on() {
+            vis.log(format!("winning={winning:?} mine={mine:?}"));
         }
         let winning: HashSet<u32> = winning
             .trim()
@@ -36,13 +39,14 @@ pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
     Box::new(score)
 }
 
-pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2023, day = 4, part = 2, label = "d04::part2")]
+pub fn part2(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let mut score = 0;
     let mut wins = VecDeque::new();
     for line in input.lines() {
         let copies = 1 + wins.pop_front().unwrap_or(0);
-        if vis {
-            println!("{copies} copies of {line:?}");
+        if vis.on() {
+            vis.log(format!("{copies} copies of {line:?}"));
         }
         score += copies;
 
@@ -63,8 +67,8 @@ pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
 
         let n = winning.intersection(&mine).count();
         if n > 0 {
-            if vis {
-                println!("--> {copies} more copies of the next {n} cards!");
+            if vis.on() {
+                vis.log(format!("--> {copies} more copies of the next {n} cards!"));
             }
             for i in 0..n {
                 if wins.len() > i {
@@ -80,7 +84,7 @@ pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
 
 #[cfg(test)]
 mod test {
-    const TEST_INPUT: &'static str = r"Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+    const TEST_INPUT: &str = r"Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
 Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
 Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
 Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
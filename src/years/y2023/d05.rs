@@ -2,72 +2,54 @@ use std::{collections::HashMap, fmt::Display};
 
 use itertools::Itertools;
 
+use crate::{interval::IntervalSet, vis::Visualizer};
+
 // Handy references:
 // - https://doc.rust-lang.org/std/iter/trait.Iterator.html
 // - https://docs.rs/itertools/0.8.2/itertools/trait.Itertools.html
 // - https://docs.rs/regex/latest/regex/struct.Regex.html
 
-pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2023, day = 5, part = 1, label = "d05::part1")]
+pub fn part1(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let data = parse(&input);
-    if vis {
+    if vis.on() {
         let keys: Vec<&String> = data.maps.keys().collect();
-        println!("maps: {keys:?}");
+        vis.log(format!("maps: {keys:?}"));
     }
     let locations = data.seeds.iter().map(|s| map(*s, &data.maps, vis));
     Box::new(locations.min().unwrap())
 }
 
-fn map(seed: u128, maps: &HashMap<String, Map>, vis: bool) -> u128 {
-    if vis {
-        print!("seed {seed} -> ");
-    }
+fn map(seed: u128, maps: &HashMap<String, Map>, vis: &Visualizer) -> u128 {
     let soil = maps["seed-to-soil"].map(seed);
-    if vis {
-        print!("soil {soil} -> ");
-    }
     let fertilizer = maps["soil-to-fertilizer"].map(soil);
-    if vis {
-        print!("fertilizer {fertilizer} -> ");
-    }
     let water = maps["fertilizer-to-water"].map(fertilizer);
-    if vis {
-        print!("water {water} -> ");
-    }
     let light = maps["water-to-light"].map(water);
-    if vis {
-        print!("light {light} -> ");
-    }
     let temperature = maps["light-to-temperature"].map(light);
-    if vis {
-        print!("temperature {temperature} -> ");
-    }
     let humidity = maps["temperature-to-humidity"].map(temperature);
-    if vis {
-        print!("humidity {humidity} -> ");
-    }
     let location = maps["humidity-to-location"].map(humidity);
-    if vis {
-        println!("location {location}");
-    }
+    vis.log(format_args!(
+        "seed {seed} -> soil {soil} -> fertilizer {fertilizer} -> water {water} -> \
+         light {light} -> temperature {temperature} -> humidity {humidity} -> \
+         location {location}"
+    ));
     location
 }
 
-pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2023, day = 5, part = 2, label = "d05::part2")]
+pub fn part2(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let data = parse(&input);
-    let locations = data
+    let seeds = data
         .seeds
         .iter()
         .copied()
         .tuples()
-        .map(|s| map2(s, &data.maps, vis));
-    Box::new(locations.min().unwrap())
+        .map(|(start, len)| start..start + len)
+        .collect();
+    Box::new(map2(seeds, &data.maps, vis))
 }
 
-fn map2(
-    seed_range: (u128, u128), /*(start,len)*/
-    maps: &HashMap<String, Map>,
-    vis: bool,
-) -> u128 {
+fn map2(seeds: IntervalSet<u128>, maps: &HashMap<String, Map>, vis: &Visualizer) -> u128 {
     let stages = [
         "seed",
         "soil",
@@ -79,9 +61,9 @@ fn map2(
         "location",
     ];
 
-    let mut ranges = vec![seed_range];
-    if vis {
-        println!("seed {ranges:?}");
+    let mut ranges = seeds;
+    if vis.on() {
+        vis.log(format!("seed {ranges:?}"));
     }
 
     for fromto in stages.windows(2) {
@@ -89,17 +71,14 @@ fn map2(
         let to = fromto[1];
         let stage = format!("{}-to-{}", from, to);
         let map = &maps[&stage];
-        let mut new_ranges = Vec::new();
-        for r in ranges {
-            map.map_range(&mut new_ranges, r);
-        }
-        if vis {
-            println!("-> {to}: {new_ranges:?}");
+        let new_ranges = map.map_set(&ranges);
+        if vis.on() {
+            vis.log(format!("-> {to}: {new_ranges:?}"));
         }
         ranges = new_ranges;
     }
 
-    ranges.into_iter().map(|(a, _)| a).min().unwrap()
+    ranges.first().unwrap()
 }
 
 fn parse(input: &str) -> Data {
@@ -174,43 +153,21 @@ impl Map {
             .find(|range| range.src <= source && range.src + range.range > source)
     }
 
-    fn map_range(&self, new_ranges: &mut Vec<(u128, u128)>, range: (u128, u128)) {
-        let (mut start, mut len) = range;
+    // Moves the numbers in the map's ranges, and leaves the rest.
+    fn map_set(&self, set: &IntervalSet<u128>) -> IntervalSet<u128> {
+        let mut sources = IntervalSet::new();
+        let mut moved = Vec::new();
         for r in &self.ranges {
-            //println!("({start}, {len}) vs {r:?}");
-            if r.src > start {
-                // Map (start..?) to (start..?).
-                let diff = r.src - start;
-                if diff >= len {
-                    //println!("-> ({start},{len}) (done)");
-                    new_ranges.push((start, len));
-                    return;
-                } else {
-                    //println!("-> ({start},{diff})");
-                    new_ranges.push((start, diff));
-                    start = r.src;
-                    len -= diff;
-                }
-            }
-            if r.src + r.range > start {
-                // Map (start..?) to (r.dest+offset..?).
-                let rend = r.src + r.range;
-                let inputend = start + len;
-                let newstart = r.dest + (start - r.src);
-                if inputend <= rend {
-                    //println!("-> ({newstart},{len}) (done)");
-                    new_ranges.push((newstart, len));
-                    return;
-                } else {
-                    let newlen = rend - start;
-                    //println!("-> ({newstart},{newlen})");
-                    new_ranges.push((newstart, newlen));
-                    start = rend;
-                    len -= newlen;
-                }
-            }
+            let source = IntervalSet::from(r.src..r.src + r.range);
+            let to = |n| r.dest + (n - r.src);
+            moved.extend(
+                set.intersection(&source)
+                    .ranges()
+                    .map(|m| to(m.start)..to(m.end)),
+            );
+            sources = sources.union(&source);
         }
-        new_ranges.push((start, len));
+        set.difference(&sources).union(&moved.into_iter().collect())
     }
 }
 
@@ -243,7 +200,14 @@ struct Range {
 
 #[cfg(test)]
 mod test {
-    const TEST_INPUT: &'static str = r"seeds: 79 14 55 13
+    use std::collections::BTreeSet;
+
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::{Map, Range};
+    use crate::interval::IntervalSet;
+
+    const TEST_INPUT: &str = r"seeds: 79 14 55 13
 
 seed-to-soil map:
 50 98 2
@@ -279,4 +243,36 @@ humidity-to-location map:
 
     crate::test::aoc_test!(part1, TEST_INPUT, 35);
     crate::test::aoc_test!(part2, TEST_INPUT, 46);
+
+    #[test]
+    fn map_sets_match_brute_force() {
+        let mut rng = StdRng::seed_from_u64(5);
+        for _ in 0..500 {
+            // Every other piece between some cuts in 0..40 is moved somewhere.
+            let mut cuts: Vec<u128> = (0..6).map(|_| rng.gen_range(0..40)).collect();
+            cuts.sort();
+            let ranges = cuts
+                .chunks(2)
+                .map(|c| Range {
+                    dest: rng.gen_range(0..40),
+                    src: c[0],
+                    range: c[1] - c[0],
+                })
+                .collect();
+            let map = Map { ranges };
+            let set: IntervalSet<u128> = (0..3)
+                .map(|_| {
+                    let start = rng.gen_range(0..48);
+                    start..start + rng.gen_range(0..10)
+                })
+                .collect();
+            let expected: BTreeSet<u128> = set.ranges().flatten().map(|n| map.map(n)).collect();
+            let mapped = map.map_set(&set);
+            assert_eq!(
+                expected,
+                mapped.ranges().flatten().collect(),
+                "{set:?} through {map:?}"
+            );
+        }
+    }
 }
@@ -1,18 +1,22 @@
 use std::fmt::Display;
 use std::iter::zip;
 
+use crate::vis::Visualizer;
+
 // Handy references:
 // - https://doc.rust-lang.org/std/iter/trait.Iterator.html
 // - https://docs.rs/itertools/0.8.2/itertools/trait.Itertools.html
 // - https://docs.rs/regex/latest/regex/struct.Regex.html
 
-pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2023, day = 6, part = 1, label = "d06::part1")]
+pub fn part1(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let races = parse(&input);
     let res: u64 = races.iter().map(|r| score(r, vis)).product();
     Box::new(res)
 }
 
-pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2023, day = 6, part = 2, label = "d06::part2")]
+pub fn part2(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let sp = regex::Regex::new(" ").unwrap();
     let input = sp.replace_all(&input, "");
     let races = parse(&input);
@@ -20,7 +24,7 @@ pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
     Box::new(res)
 }
 
-fn score(race: &Race, vis: bool) -> u64 {
+fn score(race: &Race, vis: &Visualizer) -> u64 {
     // speed = x
     // dist = speed * (time - x)
     // break even when 0 = speed * (time - x) - best
@@ -33,13 +37,16 @@ fn score(race: &Race, vis: bool) -> u64 {
     // x = (-b +/- sqrt(b^2 - 4ac)) / 2a
     let a = -1.0;
     let b = race.time as f64;
-    let c = -1.0 * (race.best as f64);
+    let c = -(race.best as f64);
     let s = (b * b - 4.0 * a * c).sqrt();
     let x1 = (-b + s) / 2.0 * a;
     let x2 = (-b - s) / 2.0 * a;
-    if vis {
-        println!("0 = {a} * x^2 + {b} * x + {c}");
-        println!("time={} best={} => [ {x1}, {x2} ]", race.time, race.best);
+    if vis.on() {
+        vis.log(format!("0 = {a} * x^2 + {b} * x + {c}"));
+        vis.log(format!(
+            "time={} best={} => [ {x1}, {x2} ]",
+            race.time, race.best
+        ));
         //for i in 1..race.time {
         //    println!("  press={i} ===> {}", i * (race.time - i));
         //}
@@ -80,7 +87,7 @@ struct Race {
 
 #[cfg(test)]
 mod test {
-    const TEST_INPUT: &'static str = r"Time:      7  15   30
+    const TEST_INPUT: &str = r"Time:      7  15   30
 Distance:  9  40  200";
 
     crate::test::aoc_test!(part1, TEST_INPUT, 288);
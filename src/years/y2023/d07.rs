@@ -1,11 +1,14 @@
 use std::{collections::HashMap, fmt::Display};
 
+use crate::vis::Visualizer;
+
 // Handy references:
 // - https://doc.rust-lang.org/std/iter/trait.Iterator.html
 // - https://docs.rs/itertools/0.8.2/itertools/trait.Itertools.html
 // - https://docs.rs/regex/latest/regex/struct.Regex.html
 
-pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2023, day = 7, part = 1, label = "d07::part1")]
+pub fn part1(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let mut hands: Vec<Hand> = input
         .lines()
         .map(|line| parse_hand(Part1, line, vis))
@@ -19,7 +22,8 @@ pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
     Box::new(total)
 }
 
-pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2023, day = 7, part = 2, label = "d07::part2")]
+pub fn part2(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let mut hands: Vec<Hand> = input
         .lines()
         .map(|line| parse_hand(Part2, line, vis))
@@ -34,7 +38,7 @@ pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
 }
 
 trait Scorer {
-    fn hand_type(&self, hand: &str, vis: bool) -> u64;
+    fn hand_type(&self, hand: &str, vis: &Visualizer) -> u64;
     fn card_val(&self, card: char) -> u64;
 }
 
@@ -43,7 +47,7 @@ struct Hand {
     bid: u64,
 }
 
-fn parse_hand<S: Scorer>(s: S, line: &str, vis: bool) -> Hand {
+fn parse_hand<S: Scorer>(s: S, line: &str, vis: &Visualizer) -> Hand {
     let (hand, bid) = line.trim().split_once(' ').unwrap();
     let bid = bid.parse().unwrap();
     let mut score = 0x100000 * s.hand_type(hand, vis);
@@ -54,8 +58,8 @@ fn parse_hand<S: Scorer>(s: S, line: &str, vis: bool) -> Hand {
     score += 0x10 * s.card_val(cards.next().unwrap());
     score += s.card_val(cards.next().unwrap());
     assert!(cards.next().is_none());
-    if vis {
-        println!("{hand} => {score:#x}");
+    if vis.on() {
+        vis.log(format!("{hand} => {score:#x}"));
     }
     Hand { score, bid }
 }
@@ -63,7 +67,7 @@ fn parse_hand<S: Scorer>(s: S, line: &str, vis: bool) -> Hand {
 struct Part1;
 
 impl Scorer for Part1 {
-    fn hand_type(&self, hand: &str, vis: bool) -> u64 {
+    fn hand_type(&self, hand: &str, vis: &Visualizer) -> u64 {
         let mut seen = HashMap::new();
         let mut counts = Vec::new();
         for card in hand.chars() {
@@ -75,8 +79,8 @@ impl Scorer for Part1 {
             counts[*i] += 1;
         }
         counts.sort();
-        if vis {
-            println!("{hand} => {counts:?}");
+        if vis.on() {
+            vis.log(format!("{hand} => {counts:?}"));
         }
         match counts[..] {
             [5] => 7,             // five of a kind
@@ -113,7 +117,7 @@ impl Scorer for Part1 {
 struct Part2;
 
 impl Scorer for Part2 {
-    fn hand_type(&self, hand: &str, vis: bool) -> u64 {
+    fn hand_type(&self, hand: &str, vis: &Visualizer) -> u64 {
         let mut jokers = 0;
         let mut seen = HashMap::new();
         let mut counts = Vec::new();
@@ -136,8 +140,8 @@ impl Scorer for Part2 {
             let last_i = counts.len() - 1;
             counts[last_i] += jokers;
         }
-        if vis {
-            println!("{hand} => {counts:?}");
+        if vis.on() {
+            vis.log(format!("{hand} => {counts:?}"));
         }
         match counts[..] {
             [5] => 7,             // five of a kind
@@ -173,7 +177,7 @@ impl Scorer for Part2 {
 
 #[cfg(test)]
 mod test {
-    const TEST_INPUT: &'static str = r"32T3K 765
+    const TEST_INPUT: &str = r"32T3K 765
 T55J5 684
 KK677 28
 KTJJT 220
@@ -2,12 +2,15 @@ use std::{collections::HashMap, fmt::Display};
 
 use num::integer::lcm;
 
+use crate::vis::Visualizer;
+
 // Handy references:
 // - https://doc.rust-lang.org/std/iter/trait.Iterator.html
 // - https://docs.rs/itertools/0.8.2/itertools/trait.Itertools.html
 // - https://docs.rs/regex/latest/regex/struct.Regex.html
 
-pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2023, day = 8, part = 1, label = "d08::part1")]
+pub fn part1(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let (directions, map) = parse(input, vis);
 
     let z = regex::Regex::new("ZZZ").unwrap();
@@ -37,7 +40,8 @@ fn solve(
     }
 }
 
-pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2023, day = 8, part = 2, label = "d08::part2")]
+pub fn part2(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let (directions, map) = parse(input, vis);
     let fin = regex::Regex::new("Z$").unwrap();
     let solves: Vec<(&str, u128)> = map
@@ -45,14 +49,14 @@ pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
         .filter(|s| s.ends_with('A'))
         .map(|start| (start.as_str(), solve(&directions, &map, start, &fin)))
         .collect();
-    if vis {
-        println!("{solves:?}");
+    if vis.on() {
+        vis.log(format!("{solves:?}"));
     }
     let res: u128 = solves.iter().map(|(_, n)| *n).reduce(lcm).unwrap();
     Box::new(res)
 }
 
-fn parse(input: String, vis: bool) -> (Vec<Dir>, HashMap<String, (String, String)>) {
+fn parse(input: String, vis: &Visualizer) -> (Vec<Dir>, HashMap<String, (String, String)>) {
     let (directions, map_input) = input.split_once("\n\n").unwrap();
     let directions = directions.trim().chars().map(|d| d.into()).collect();
     let mut map = HashMap::new();
@@ -66,10 +70,10 @@ fn parse(input: String, vis: bool) -> (Vec<Dir>, HashMap<String, (String, String
         let (_, [from, l, r]) = line_re.captures(line).unwrap().extract();
         map.insert(from.to_owned(), (l.to_owned(), r.to_owned()));
     }
-    if vis {
-        println!("directions = {directions:?}");
-        println!("map = {map:?}");
-        println!("map_input = {map_input}");
+    if vis.on() {
+        vis.log(format!("directions = {directions:?}"));
+        vis.log(format!("map = {map:?}"));
+        vis.log(format!("map_input = {map_input}"));
     }
     (directions, map)
 }
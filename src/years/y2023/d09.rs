@@ -1,11 +1,14 @@
 use std::fmt::Display;
 
+use crate::vis::Visualizer;
+
 // Handy references:
 // - https://doc.rust-lang.org/std/iter/trait.Iterator.html
 // - https://docs.rs/itertools/0.8.2/itertools/trait.Itertools.html
 // - https://docs.rs/regex/latest/regex/struct.Regex.html
 
-pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2023, day = 9, part = 1, label = "d09::part1")]
+pub fn part1(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let mut sum = 0;
     for line in input.lines() {
         let numbers = nums(line);
@@ -14,7 +17,8 @@ pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
     Box::new(sum)
 }
 
-pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2023, day = 9, part = 2, label = "d09::part2")]
+pub fn part2(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let mut sum = 0;
     for line in input.lines() {
         let numbers = nums(line);
@@ -23,7 +27,7 @@ pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
     Box::new(sum)
 }
 
-fn next_number(numbers: &Vec<i64>, vis: bool, part: Part) -> i64 {
+fn next_number(numbers: &Vec<i64>, vis: &Visualizer, part: Part) -> i64 {
     let next_num = if numbers.iter().all(|a| *a == 0) {
         0
     } else {
@@ -32,8 +36,8 @@ fn next_number(numbers: &Vec<i64>, vis: bool, part: Part) -> i64 {
         part.go(numbers, next_number(&diffs, vis, part))
     };
 
-    if vis {
-        println!("{numbers:?} ==> {next_num}");
+    if vis.on() {
+        vis.log(format!("{numbers:?} ==> {next_num}"));
     }
 
     next_num
@@ -65,7 +69,7 @@ fn nums(line: &str) -> Vec<i64> {
 
 #[cfg(test)]
 mod test {
-    const TEST_INPUT: &'static str = r"0 3 6 9 12 15
+    const TEST_INPUT: &str = r"0 3 6 9 12 15
 1 3 6 10 15 21
 10 13 16 21 30 45";
 
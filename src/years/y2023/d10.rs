@@ -1,31 +1,37 @@
 use std::collections::{HashMap, VecDeque};
 use std::fmt::{Display, Write};
 
+use crate::{
+    grid::{Grid, Pos},
+    vis::{Frame, Visualizer},
+};
+
 // Handy references:
 // - https://doc.rust-lang.org/std/iter/trait.Iterator.html
 // - https://docs.rs/itertools/0.8.2/itertools/trait.Itertools.html
 // - https://docs.rs/regex/latest/regex/struct.Regex.html
 
-pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
-    if vis {
-        println!("{input}");
+#[aoc(year = 2023, day = 10, part = 1, label = "d10::part1")]
+pub fn part1(input: String, vis: &Visualizer) -> Box<dyn Display> {
+    if vis.on() {
+        vis.log(&input);
     }
     let tiles = parse(&input);
     let visited = trace(&tiles, vis);
     Box::new(*visited.values().max().unwrap())
 }
 
-pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
-    if vis {
-        println!("{input}");
+#[aoc(year = 2023, day = 10, part = 2, label = "d10::part2")]
+pub fn part2(input: String, vis: &Visualizer) -> Box<dyn Display> {
+    if vis.on() {
+        vis.log(&input);
     }
     let tiles = parse(&input);
-    let visited = trace(&tiles, false);
+    let visited = trace(&tiles, &Visualizer::off());
 
-    let mut marked: HashMap<(usize, usize), Fin> =
-        visited.keys().map(|pos| (*pos, Fin::Wall)).collect();
+    let mut marked: HashMap<Pos, Fin> = visited.keys().map(|pos| (*pos, Fin::Wall)).collect();
 
-    for (i, row) in tiles.iter().enumerate() {
+    for (i, row) in tiles.iter_rows().enumerate() {
         for (j, _) in row.iter().enumerate() {
             marked.entry((i, j)).or_insert_with(|| {
                 let mut last_t = None;
@@ -54,7 +60,7 @@ pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
                     }
                 } else {
                     // Check the tiles above.
-                    let above = tiles[0..i].iter().map(|row| row[j]);
+                    let above = tiles.col(j).take(i).copied();
                     for (ii, t) in above
                         .enumerate()
                         .filter(|(i, t)| visited.contains_key(&(*i, j)) && *t != Tile::Vertical)
@@ -70,8 +76,7 @@ pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
                             }
                             _ => {
                                 let mut details = String::new();
-                                for (i, row) in tiles[0..i].iter().enumerate() {
-                                    let t = row[j];
+                                for (i, &t) in tiles.col(j).take(i).enumerate() {
                                     let v = visited.get(&(i, j));
                                     writeln!(&mut details, "({i},{j}) {t:?} {v:?}").unwrap();
                                 }
@@ -101,19 +106,19 @@ pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
         }
     }
 
-    if vis {
-        println!("--- inner and outer ---");
-        for (i, row) in tiles.iter().enumerate() {
-            for (j, tile) in row.iter().enumerate() {
-                match marked.get(&(i, j)) {
-                    Some(Fin::Wall) => print!("{tile}"),
-                    Some(Fin::I) => print!("I"),
-                    Some(Fin::O) => print!("O"),
-                    None => print!("."),
-                }
-            }
-            println!();
-        }
+    if vis.on() {
+        let rows = tiles.iter_rows().enumerate().map(|(i, row)| {
+            row.iter()
+                .enumerate()
+                .map(|(j, tile)| match marked.get(&(i, j)) {
+                    Some(Fin::Wall) => tile.to_string(),
+                    Some(Fin::I) => "I".to_string(),
+                    Some(Fin::O) => "O".to_string(),
+                    None => ".".to_string(),
+                })
+                .collect()
+        });
+        vis.frame(Frame::new("--- inner and outer ---", rows));
     }
 
     let res: usize = marked.values().filter(|v| matches!(v, Fin::I)).count();
@@ -127,12 +132,12 @@ enum Fin {
     O,
 }
 
-fn trace(tiles: &[Vec<Tile>], vis: bool) -> HashMap<(usize, usize), u64> {
-    let start = find_start(tiles);
-    if vis {
-        println!("start = {start:?}");
+fn trace(tiles: &Grid<Tile>, vis: &Visualizer) -> HashMap<Pos, u64> {
+    let start = tiles.find(|t| matches!(t, Tile::Start)).unwrap();
+    if vis.on() {
+        vis.log(format!("start = {start:?}"));
     }
-    let mut visited: HashMap<(usize, usize), u64> = HashMap::new();
+    let mut visited: HashMap<Pos, u64> = HashMap::new();
     visited.insert(start, 0);
     let mut positions = VecDeque::new();
     positions.push_back(start);
@@ -142,8 +147,8 @@ fn trace(tiles: &[Vec<Tile>], vis: bool) -> HashMap<(usize, usize), u64> {
             Some(pos) => {
                 let n_dist = 1 + visited.get(&pos).unwrap();
                 let ns = neighbors(tiles, &pos);
-                if vis {
-                    println!("{pos:?} --> ({n_dist})  {ns:?}");
+                if vis.on() {
+                    vis.log(format!("{pos:?} --> ({n_dist})  {ns:?}"));
                 }
                 for n in ns {
                     let e = visited.entry(n).or_insert(n_dist);
@@ -158,20 +163,16 @@ fn trace(tiles: &[Vec<Tile>], vis: bool) -> HashMap<(usize, usize), u64> {
     visited
 }
 
-fn neighbors(tiles: &[Vec<Tile>], pos: &(usize, usize)) -> Vec<(usize, usize)> {
+fn neighbors(tiles: &Grid<Tile>, pos: &Pos) -> Vec<Pos> {
     //println!("find neighbors from {pos:?}");
-    fn st(
-        tiles: &[Vec<Tile>],
-        start: &(usize, usize),
-        choices: Vec<(usize, usize)>,
-    ) -> Vec<(usize, usize)> {
+    fn st(tiles: &Grid<Tile>, start: &Pos, choices: Vec<Pos>) -> Vec<Pos> {
         //println!("looking for start neighbors in {choices:?}");
         choices
             .into_iter()
             .filter(|p| neighbors(tiles, p).contains(start))
             .collect()
     }
-    let t = &tiles[pos.0][pos.1];
+    let t = &tiles[*pos];
     //println!(" (it's a {t:?})");
     match t {
         Tile::Vertical => {
@@ -224,35 +225,18 @@ fn neighbors(tiles: &[Vec<Tile>], pos: &(usize, usize)) -> Vec<(usize, usize)> {
     }
 }
 
-fn find_start(tiles: &[Vec<Tile>]) -> (usize, usize) {
-    for (i, row) in tiles.iter().enumerate() {
-        for (j, tile) in row.iter().enumerate() {
-            if matches!(tile, Tile::Start) {
-                return (i, j);
-            }
-        }
-    }
-    panic!("no start found in {tiles:?}");
-}
-
-fn parse(input: &str) -> Vec<Vec<Tile>> {
-    fn parse_line(line: &str) -> Vec<Tile> {
-        line.trim()
-            .chars()
-            .map(|c| match c {
-                '|' => Tile::Vertical,
-                '-' => Tile::Horizontal,
-                'L' => Tile::NE,
-                'J' => Tile::NW,
-                '7' => Tile::SW,
-                'F' => Tile::SE,
-                '.' => Tile::Ground,
-                'S' => Tile::Start,
-                _ => panic!("illegal tile {c:?}"),
-            })
-            .collect()
-    }
-    input.lines().map(parse_line).collect()
+fn parse(input: &str) -> Grid<Tile> {
+    Grid::parse_trimmed(input, |c| match c {
+        '|' => Tile::Vertical,
+        '-' => Tile::Horizontal,
+        'L' => Tile::NE,
+        'J' => Tile::NW,
+        '7' => Tile::SW,
+        'F' => Tile::SE,
+        '.' => Tile::Ground,
+        'S' => Tile::Start,
+        _ => panic!("illegal tile {c:?}"),
+    })
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -1,35 +1,39 @@
 use std::{collections::HashSet, fmt::Display};
 
+use crate::vis::Visualizer;
+
 // Handy references:
 // - https://doc.rust-lang.org/std/iter/trait.Iterator.html
 // - https://docs.rs/itertools/0.8.2/itertools/trait.Itertools.html
 // - https://docs.rs/regex/latest/regex/struct.Regex.html
 
-pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2023, day = 11, part = 1, label = "d11::part1")]
+pub fn part1(input: String, vis: &Visualizer) -> Box<dyn Display> {
     solve(input, vis, 2)
 }
 
-pub fn part2(input: String, _vis: bool) -> Box<dyn Display> {
-    solve(input, false, 1000000)
+#[aoc(year = 2023, day = 11, part = 2, label = "d11::part2")]
+pub fn part2(input: String, _vis: &Visualizer) -> Box<dyn Display> {
+    solve(input, &Visualizer::off(), 1000000)
 }
 
-fn solve(input: String, vis: bool, expansion: usize) -> Box<dyn Display> {
+fn solve(input: String, vis: &Visualizer, expansion: usize) -> Box<dyn Display> {
     let universe = parse(&input);
-    if vis {
-        println!("INPUT:");
-        println!("{universe}");
+    if vis.on() {
+        vis.log("INPUT:");
+        vis.log(&universe);
     }
     let universe = expand(universe, expansion);
-    if vis {
-        println!("EXPANDED:");
-        println!("{universe}");
+    if vis.on() {
+        vis.log("EXPANDED:");
+        vis.log(&universe);
     }
     let mut total_dist = 0;
     for (i, g1) in universe.galaxies.iter().enumerate() {
         for g2 in &universe.galaxies[i + 1..] {
             let dist = distance(g1, g2);
-            if vis {
-                println!("{g1:?} -> {g2:?} in {dist}");
+            if vis.on() {
+                vis.log(format!("{g1:?} -> {g2:?} in {dist}"));
             }
             total_dist += dist;
         }
@@ -41,11 +45,7 @@ fn distance(g1: &(usize, usize), g2: &(usize, usize)) -> usize {
     let (i1, j1) = g1;
     let (i2, j2) = g2;
     fn diff(a: &usize, b: &usize) -> usize {
-        if a > b {
-            a - b
-        } else {
-            b - a
-        }
+        a.abs_diff(*b)
     }
     diff(i1, i2) + diff(j1, j2)
 }
@@ -137,7 +137,7 @@ impl Display for Universe {
 
 #[cfg(test)]
 mod test {
-    const TEST_INPUT: &'static str = r"...#......
+    const TEST_INPUT: &str = r"...#......
 .......#..
 #.........
 ..........
@@ -2,24 +2,28 @@ use std::borrow::Cow;
 use std::collections::HashMap;
 use std::fmt::Display;
 
+use crate::vis::Visualizer;
+
 // Handy references:
 // - https://doc.rust-lang.org/std/iter/trait.Iterator.html
 // - https://docs.rs/itertools/0.8.2/itertools/trait.Itertools.html
 // - https://docs.rs/regex/latest/regex/struct.Regex.html
 
-pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2023, day = 12, part = 1, label = "d12::part1")]
+pub fn part1(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let res: usize = input.lines().map(|line| solve(line, vis, 1)).sum();
     Box::new(res)
 }
 
-pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2023, day = 12, part = 2, label = "d12::part2")]
+pub fn part2(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let res: usize = input.lines().map(|line| solve(line, vis, 5)).sum();
     Box::new(res)
 }
 
-fn solve(line: &str, vis: bool, mult: usize) -> usize {
-    if vis {
-        println!("{line}");
+fn solve(line: &str, vis: &Visualizer, mult: usize) -> usize {
+    if vis.on() {
+        vis.log(line);
     }
 
     let (conditions, counts) = line.trim().split_once(' ').unwrap();
@@ -41,9 +45,9 @@ fn solve(line: &str, vis: bool, mult: usize) -> usize {
     }
     let conditions = r(conditions, '?', mult);
     let counts = r(counts, ',', mult);
-    if vis && mult > 1 {
-        println!("++ {conditions}");
-        println!("## {counts}");
+    if vis.on() && mult > 1 {
+        vis.log(format!("++ {conditions}"));
+        vis.log(format!("## {counts}"));
     }
 
     let conditions: Vec<Cond> = conditions.chars().map(Cond::from).collect();
@@ -52,8 +56,8 @@ fn solve(line: &str, vis: bool, mult: usize) -> usize {
     let mut memo = HashMap::new();
 
     let res = s2(&conditions, &counts, false, &mut memo);
-    if vis {
-        println!("  >> {res}");
+    if vis.on() {
+        vis.log(format!("  >> {res}"));
     }
     res
 }
@@ -154,7 +158,7 @@ impl From<char> for Cond {
 
 #[cfg(test)]
 mod test {
-    const TEST_INPUT: &'static str = r"???.### 1,1,3
+    const TEST_INPUT: &str = r"???.### 1,1,3
 .??..??...?##. 1,1,3
 ?#?#?#?#?#?#?#? 1,3,1,6
 ????.#...#... 4,1,1
@@ -1,22 +1,25 @@
 use std::{fmt::Display, iter::zip};
 
+use crate::vis::Visualizer;
+
 // Handy references:
 // - https://doc.rust-lang.org/std/iter/trait.Iterator.html
 // - https://docs.rs/itertools/0.8.2/itertools/trait.Itertools.html
 // - https://docs.rs/regex/latest/regex/struct.Regex.html
 
-pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2023, day = 13, part = 1, label = "d13::part1")]
+pub fn part1(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let mut res = 0;
     for pattern in input.split("\n\n") {
-        if vis {
-            println!("{pattern}");
+        if vis.on() {
+            vis.log(pattern);
         }
         let pattern: Vec<&[u8]> = pattern.lines().map(|l| l.trim().as_bytes()).collect();
         for row in 0..pattern.len() - 1 {
             if is_v(&pattern, row) {
                 let row = row + 1;
-                if vis {
-                    println!(" v {row}");
+                if vis.on() {
+                    vis.log(format!(" v {row}"));
                 }
                 res += row * 100;
             }
@@ -24,8 +27,8 @@ pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
         for col in 0..pattern[0].len() - 1 {
             if is_h(&pattern, col) {
                 let col = col + 1;
-                if vis {
-                    println!(" > {col}");
+                if vis.on() {
+                    vis.log(format!(" > {col}"));
                 }
                 res += col;
             }
@@ -34,18 +37,19 @@ pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
     Box::new(res)
 }
 
-pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2023, day = 13, part = 2, label = "d13::part2")]
+pub fn part2(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let mut res = 0;
     for pattern in input.split("\n\n") {
-        if vis {
-            println!("{pattern}");
+        if vis.on() {
+            vis.log(pattern);
         }
         let pattern: Vec<&[u8]> = pattern.lines().map(|l| l.trim().as_bytes()).collect();
         for row in 0..pattern.len() - 1 {
             if is_smudged_v(&pattern, row) && !is_v(&pattern, row) {
                 let row = row + 1;
-                if vis {
-                    println!(" v {row}");
+                if vis.on() {
+                    vis.log(format!(" v {row}"));
                 }
                 res += row * 100;
             }
@@ -53,8 +57,8 @@ pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
         for col in 0..pattern[0].len() - 1 {
             if is_smudged_h(&pattern, col) && !is_h(&pattern, col) {
                 let col = col + 1;
-                if vis {
-                    println!(" > {col}");
+                if vis.on() {
+                    vis.log(format!(" > {col}"));
                 }
                 res += col;
             }
@@ -101,7 +105,7 @@ fn h_mismatches(pattern: &[&[u8]], col: usize) -> usize {
 
 #[cfg(test)]
 mod test {
-    const TEST_INPUT: &'static str = r"#.##..##.
+    const TEST_INPUT: &str = r"#.##..##.
 ..#.##.#.
 ##......#
 ##......#
@@ -1,194 +1,83 @@
-use std::collections::HashMap;
 use std::fmt::Display;
 
+use crate::{
+    cycle::{self, Strategy},
+    grid::Grid,
+    vis::Visualizer,
+};
+
 // Handy references:
 // - https://doc.rust-lang.org/std/iter/trait.Iterator.html
 // - https://docs.rs/itertools/0.8.2/itertools/trait.Itertools.html
 // - https://docs.rs/regex/latest/regex/struct.Regex.html
 
-pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
-    let g = parse(&input);
+#[aoc(year = 2023, day = 14, part = 1, label = "d14::part1")]
+pub fn part1(input: String, vis: &Visualizer) -> Box<dyn Display> {
+    let mut g = parse(&input);
 
-    let g = tilt_north(g);
+    tilt_north(&mut g);
 
-    if vis {
-        for line in &g.platform {
-            let line: String = line.iter().collect();
-            println!("{line}");
-        }
+    if vis.on() {
+        vis.frame(g.frame("tilted north"));
     }
 
-    Box::new(load(g))
+    Box::new(load(&g))
 }
 
-pub fn part2(input: String, _vis: bool) -> Box<dyn Display> {
-    let mut g = parse(&input);
+#[aoc(year = 2023, day = 14, part = 2, label = "d14::part2")]
+pub fn part2(input: String, vis: &Visualizer) -> Box<dyn Display> {
+    let g = parse(&input);
 
-    const CYCLES: usize = 1000000000;
-    let mut positions = HashMap::new();
-    let mut i = 0;
-    let mut found = false;
-    loop {
-        i += 1;
-        g = tilt_north(g);
-        g = tilt_west(g);
-        g = tilt_south(g);
-        g = tilt_east(g);
-        if !found {
-            match positions.get(&g.platform).cloned() {
-                None => {
-                    positions.insert(g.platform.clone(), i);
-                }
-                Some(j) => {
-                    let repeat_interval = i - j;
-                    i += repeat_interval * ((CYCLES - i) / repeat_interval);
-                    found = true;
-                }
-            };
-        }
-        match i {
-            i if i < CYCLES => continue,
-            CYCLES => return Box::new(load(g)),
-            _ => panic!("huh?!?! {i} vs {CYCLES}"),
-        };
+    const CYCLES: u64 = 1000000000;
+    // Only keeps two platforms, rather than one for every spin.
+    let cycle = cycle::find(Strategy::Brent, &g, spin, |g| g.clone());
+    if vis.on() {
+        vis.log(format!(
+            "the platform repeats every {} spins after the first {}",
+            cycle.period, cycle.start
+        ));
     }
+    Box::new(load(&cycle.state_after(CYCLES, &g, spin)))
 }
 
-fn load(g: Parsed) -> usize {
-    g.platform
-        .iter()
-        .enumerate()
-        .map(|(i, line)| (g.height - i) * (line.iter().filter(|c| matches!(c, 'O')).count()))
-        .sum()
-}
-
-fn tilt_north(g: Parsed) -> Parsed {
-    let Parsed {
-        mut platform,
-        height,
-        width,
-    } = g;
-    let mut moved = true;
-    while moved {
-        moved = false;
-        for i in 1..height {
-            for j in 0..width {
-                if matches!((platform[i - 1][j], platform[i][j]), ('.', 'O')) {
-                    moved = true;
-                    platform[i - 1][j] = 'O';
-                    platform[i][j] = '.';
-                }
-            }
-        }
-    }
-    Parsed {
-        platform,
-        height,
-        width,
+// North, then west, south and east, each one turned to the top.
+fn spin(g: &mut Grid<char>) {
+    for _ in 0..4 {
+        tilt_north(g);
+        *g = g.rotate_cw();
     }
 }
 
-fn tilt_south(g: Parsed) -> Parsed {
-    let Parsed {
-        mut platform,
-        height,
-        width,
-    } = g;
-    let mut moved = true;
-    while moved {
-        moved = false;
-        for i in (1..height).rev() {
-            for j in 0..width {
-                if matches!((platform[i - 1][j], platform[i][j]), ('O', '.')) {
-                    moved = true;
-                    platform[i - 1][j] = '.';
-                    platform[i][j] = 'O';
-                }
-            }
-        }
-    }
-    Parsed {
-        platform,
-        height,
-        width,
-    }
-}
-
-fn tilt_west(g: Parsed) -> Parsed {
-    let Parsed {
-        mut platform,
-        height,
-        width,
-    } = g;
-    let mut moved = true;
-    while moved {
-        moved = false;
-        for j in 1..width {
-            for row in platform.iter_mut() {
-                if matches!((row[j - 1], row[j]), ('.', 'O')) {
-                    moved = true;
-                    row[j - 1] = 'O';
-                    row[j] = '.';
-                }
-            }
-        }
-    }
-    Parsed {
-        platform,
-        height,
-        width,
-    }
+fn load(g: &Grid<char>) -> usize {
+    g.iter()
+        .filter(|(_, c)| matches!(c, 'O'))
+        .map(|((i, _), _)| g.rows() - i)
+        .sum()
 }
 
-fn tilt_east(g: Parsed) -> Parsed {
-    let Parsed {
-        mut platform,
-        height,
-        width,
-    } = g;
+fn tilt_north(platform: &mut Grid<char>) {
     let mut moved = true;
     while moved {
         moved = false;
-        for j in (1..width).rev() {
-            for row in platform.iter_mut() {
-                if matches!((row[j - 1], row[j]), ('O', '.')) {
+        for i in 1..platform.rows() {
+            for j in 0..platform.cols() {
+                if matches!((platform[(i - 1, j)], platform[(i, j)]), ('.', 'O')) {
                     moved = true;
-                    row[j - 1] = '.';
-                    row[j] = 'O';
+                    platform[(i - 1, j)] = 'O';
+                    platform[(i, j)] = '.';
                 }
             }
         }
     }
-    Parsed {
-        platform,
-        height,
-        width,
-    }
-}
-
-fn parse(input: &str) -> Parsed {
-    let platform: Vec<Vec<char>> = input
-        .lines()
-        .map(|line| line.trim().chars().collect())
-        .collect();
-    let height = platform.len();
-    let width = platform[0].len();
-    Parsed {
-        platform,
-        width,
-        height,
-    }
 }
 
-struct Parsed {
-    platform: Vec<Vec<char>>,
-    height: usize,
-    width: usize,
+fn parse(input: &str) -> Grid<char> {
+    Grid::parse_trimmed(input, |c| c)
 }
 
 #[cfg(test)]
 mod test {
-    const TEST_INPUT: &'static str = r"O....#....
+    const TEST_INPUT: &str = r"O....#....
 O.OO#....#
 .....##...
 OO.#O....O
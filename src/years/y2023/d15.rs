@@ -1,19 +1,22 @@
 use std::fmt::Display;
 
+use crate::vis::Visualizer;
+
 // Handy references:
 // - https://doc.rust-lang.org/std/iter/trait.Iterator.html
 // - https://docs.rs/itertools/0.8.2/itertools/trait.Itertools.html
 // - https://docs.rs/regex/latest/regex/struct.Regex.html
 
-pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2023, day = 15, part = 1, label = "d15::part1")]
+pub fn part1(input: String, vis: &Visualizer) -> Box<dyn Display> {
     Box::new(
         input
             .trim()
             .split(',')
             .map(|step| {
-                let h = hash(step, false);
-                if vis {
-                    println!("{step:?} -> {h}");
+                let h = hash(step, &Visualizer::off());
+                if vis.on() {
+                    vis.log(format!("{step:?} -> {h}"));
                 }
                 h as u64
             })
@@ -21,20 +24,21 @@ pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
     )
 }
 
-pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2023, day = 15, part = 2, label = "d15::part2")]
+pub fn part2(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let pattern = regex::Regex::new("\\A(.*)(-|=([0-9]+))\\z").unwrap();
     let mut boxes: Vec<Vec<(&str, usize)>> = vec![Vec::new(); 256];
-    fn show_boxes<T: std::fmt::Debug>(boxes: &[Vec<T>]) {
+    fn show_boxes<T: std::fmt::Debug>(boxes: &[Vec<T>], vis: &Visualizer) {
         for (i, b) in boxes.iter().enumerate() {
             if !b.is_empty() {
-                println!("Box {i}: {b:?}");
+                vis.log(format!("Box {i}: {b:?}"));
             }
         }
     }
     for step in input.trim().split(',') {
         let m = pattern.captures(step).expect("{step} should match!");
         let label = m.get(1).unwrap().as_str();
-        let box_num = hash(label, false);
+        let box_num = hash(label, &Visualizer::off());
         let b = boxes
             .get_mut(box_num as usize)
             .expect("should have box {box_num}");
@@ -54,9 +58,9 @@ pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
                 };
             }
         }
-        if vis {
-            println!("After {step:?}:");
-            show_boxes(&boxes);
+        if vis.on() {
+            vis.log(format!("After {step:?}:"));
+            show_boxes(&boxes, vis);
         }
     }
     let mut focusing_power: usize = 0;
@@ -64,8 +68,10 @@ pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
         for (j, (l, fl)) in b.into_iter().enumerate() {
             let j = j + 1;
             let fp = (i + 1) * j * fl;
-            if vis {
-                println!("- {l} (box {i}) * {j} (slot) * {fl} (focal length) = {fp}");
+            if vis.on() {
+                vis.log(format!(
+                    "- {l} (box {i}) * {j} (slot) * {fl} (focal length) = {fp}"
+                ));
             }
             focusing_power += fp;
         }
@@ -73,13 +79,13 @@ pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
     Box::new(focusing_power)
 }
 
-fn hash(input: &str, vis: bool) -> u8 {
+fn hash(input: &str, vis: &Visualizer) -> u8 {
     let input = input.as_bytes();
     let mut current_value: u8 = 0;
     for c in input {
         current_value = current_value.wrapping_add(*c).wrapping_mul(17);
-        if vis {
-            println!("--> {c} --> {current_value}");
+        if vis.on() {
+            vis.log(format!("--> {c} --> {current_value}"));
         }
     }
     current_value
@@ -87,13 +93,15 @@ fn hash(input: &str, vis: bool) -> u8 {
 
 #[cfg(test)]
 mod test {
-    const TEST_INPUT: &'static str = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7\n";
+    use crate::vis::{Stdout, Visualizer};
+
+    const TEST_INPUT: &str = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7\n";
 
     crate::test::aoc_test!(part1, TEST_INPUT, 1320);
     crate::test::aoc_test!(part2, TEST_INPUT, 145);
 
     #[test]
     fn test_hash() {
-        assert_eq!(super::hash("HASH", true), 52);
+        assert_eq!(super::hash("HASH", &Visualizer::new(Stdout)), 52);
     }
 }
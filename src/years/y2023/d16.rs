@@ -1,12 +1,15 @@
 use std::collections::HashMap;
 use std::fmt::Display;
 
+use crate::{grid::Grid, vis::Visualizer};
+
 // Handy references:
 // - https://doc.rust-lang.org/std/iter/trait.Iterator.html
 // - https://docs.rs/itertools/0.8.2/itertools/trait.Itertools.html
 // - https://docs.rs/regex/latest/regex/struct.Regex.html
 
-pub fn part1(input: String, _vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2023, day = 16, part = 1, label = "d16::part1")]
+pub fn part1(input: String, _vis: &Visualizer) -> Box<dyn Display> {
     let parsed = parse(&input);
     let mut visited: HashMap<(isize, isize), Vec<Dir>> = HashMap::new();
     let mut cur = vec![(Dir::Right, (0, 0))];
@@ -24,13 +27,14 @@ pub fn part1(input: String, _vis: bool) -> Box<dyn Display> {
     Box::new(visited.len())
 }
 
-pub fn part2(_input: String, _vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2023, day = 16, part = 2, label = "d16::part2", unsolved)]
+pub fn part2(_input: String, _vis: &Visualizer) -> Box<dyn Display> {
     Box::new("todo")
 }
 
 impl Parsed {
     fn step(&self, pos: (isize, isize), dir: Dir) -> Vec<(Dir, (isize, isize))> {
-        match (self.spaces.get(&pos), dir) {
+        match (self.spaces.get_signed(pos).unwrap(), dir) {
             (None, dir) => vec![go_from(pos, dir)],
 
             (Some(Device::VSplit), Dir::Right | Dir::Left) => {
@@ -56,8 +60,7 @@ impl Parsed {
     }
 
     fn is_valid(&self, pos: (isize, isize)) -> bool {
-        let (r, c) = pos;
-        r >= 0 && c >= 0 && r < self.rows && c < self.cols
+        self.spaces.get_signed(pos).is_some()
     }
 }
 
@@ -86,9 +89,8 @@ enum Dir {
 }
 
 struct Parsed {
-    rows: isize,
-    cols: isize,
-    spaces: HashMap<(isize, isize), Device>,
+    // Each space's device, if it has one.
+    spaces: Grid<Option<Device>>,
 }
 
 enum Device {
@@ -99,31 +101,20 @@ enum Device {
 }
 
 fn parse(input: &str) -> Parsed {
-    let mut rows = 0;
-    let mut cols = 0;
-    let mut spaces = HashMap::new();
-    for (row, line) in input.lines().enumerate() {
-        let row = row as isize;
-        rows = row + 1;
-        for (col, ch) in line.trim().chars().enumerate() {
-            let col = col as isize;
-            cols = col + 1;
-            match ch {
-                '|' => spaces.insert((row, col), Device::VSplit),
-                '-' => spaces.insert((row, col), Device::HSplit),
-                '\\' => spaces.insert((row, col), Device::NWMirror),
-                '/' => spaces.insert((row, col), Device::NEMirror),
-                '.' => None,
-                _ => unreachable!("illegal char {ch:?}"),
-            };
-        }
-    }
-    Parsed { rows, cols, spaces }
+    let spaces = Grid::parse_trimmed(input, |ch| match ch {
+        '|' => Some(Device::VSplit),
+        '-' => Some(Device::HSplit),
+        '\\' => Some(Device::NWMirror),
+        '/' => Some(Device::NEMirror),
+        '.' => None,
+        _ => unreachable!("illegal char {ch:?}"),
+    });
+    Parsed { spaces }
 }
 
 #[cfg(test)]
 mod test {
-    const TEST_INPUT: &'static str = r".|...\....
+    const TEST_INPUT: &str = r".|...\....
 |.-.\.....
 .....|-...
 ........|.
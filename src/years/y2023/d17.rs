@@ -1,7 +1,9 @@
-use std::{
-    cmp::Ordering,
-    collections::{BinaryHeap, HashMap},
-    fmt::Display,
+use std::fmt::Display;
+
+use crate::{
+    grid::{Grid, Pos},
+    search,
+    vis::Visualizer,
 };
 
 // Handy references:
@@ -9,75 +11,54 @@ use std::{
 // - https://docs.rs/itertools/0.8.2/itertools/trait.Itertools.html
 // - https://docs.rs/regex/latest/regex/struct.Regex.html
 
-pub fn part1(input: String, _vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2023, day = 17, part = 1, label = "d17::part1")]
+pub fn part1(input: String, vis: &Visualizer) -> Box<dyn Display> {
     Box::new(solve(
         &input,
         Config {
             min_run: 1,
             max_run: 3,
         },
+        vis,
     ))
 }
 
-pub fn part2(input: String, _vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2023, day = 17, part = 2, label = "d17::part2")]
+pub fn part2(input: String, vis: &Visualizer) -> Box<dyn Display> {
     Box::new(solve(
         &input,
         Config {
             min_run: 4,
             max_run: 10,
         },
+        vis,
     ))
 }
 
-fn solve(input: &str, cfg: Config) -> u64 {
+fn solve(input: &str, cfg: Config, vis: &Visualizer) -> u64 {
     let parsed = parse(input);
 
     // solve with Dijkstra's algorithm where nodes are the individual squares
     // and edges are sets of 1..3 blocks plus a left or right turn.
-
-    let mut dist = HashMap::new(); // assume infinity.
-    let mut to_visit = BinaryHeap::new();
-    dist.insert(((0, 0), Dir::Start), 0);
-    to_visit.push(State {
-        cost: 0,
-        pos: (0, 0),
-        next_dir: Dir::Right,
-    });
-    to_visit.push(State {
-        cost: 0,
-        pos: (0, 0),
-        next_dir: Dir::Down,
-    });
-
-    let dest = (parsed.height - 1, parsed.width - 1);
-
-    while let Some(State {
-        cost,
-        pos,
-        next_dir,
-    }) = to_visit.pop()
-    {
-        if pos == dest {
-            return cost;
-        }
-        match dist.get(&(pos, next_dir)) {
-            Some(d) if *d < cost => continue,
-            _ => (),
-        };
-
-        for st in &parsed.neighbors(pos, next_dir, cost, &cfg) {
-            let e = dist.entry((st.pos, st.next_dir)).or_insert_with(|| {
-                to_visit.push(*st);
-                st.cost
-            });
-            if *e > st.cost {
-                *e = st.cost;
-                to_visit.push(*st);
-            }
+    let dest = (parsed.costs.rows() - 1, parsed.costs.cols() - 1);
+    let found = search::dijkstra(
+        [((0, 0), Dir::Right), ((0, 0), Dir::Down)],
+        |&node| node,
+        |&(pos, next_dir)| parsed.neighbors(pos, next_dir, &cfg),
+        |&(pos, _)| pos == dest,
+    )
+    .expect("no path found!");
+    if vis.on() {
+        vis.log(format!("looked at {} nodes", found.expanded));
+        let mut shown = parsed
+            .costs
+            .map(|c| char::from_digit(*c as u32, 10).unwrap());
+        for (pos, _) in &found.path {
+            shown[*pos] = '#';
         }
+        vis.frame(shown.frame(format!("heat loss {}", found.cost)));
     }
-
-    panic!("no path found!")
+    found.cost
 }
 
 struct Config {
@@ -86,124 +67,74 @@ struct Config {
 }
 
 fn parse(input: &str) -> Parsed {
-    let costs: Vec<Vec<u64>> = input
-        .lines()
-        .map(|line| {
-            line.trim()
-                .as_bytes()
-                .iter()
-                .map(|c| (c - b'0') as u64)
-                .collect()
-        })
-        .collect();
-    let height = costs.len();
-    let width = costs.len();
     Parsed {
-        costs,
-        height,
-        width,
+        costs: Grid::parse_trimmed(input, |c| c.to_digit(10).unwrap() as u64),
     }
 }
 
 struct Parsed {
-    costs: Vec<Vec<u64>>,
-    height: usize,
-    width: usize,
+    costs: Grid<u64>,
 }
 
+// Where a run of blocks ends, and which way to turn there.
+type Node = (Pos, Dir);
+
 impl Parsed {
-    fn neighbors(
-        &self,
-        pos: (usize, usize),
-        dir: Dir,
-        start_cost: u64,
-        cfg: &Config,
-    ) -> Vec<State> {
+    // The nodes a run of blocks in `dir` can get to, with the heat lost on
+    // the way.
+    fn neighbors(&self, pos: Pos, dir: Dir, cfg: &Config) -> Vec<(Node, u64)> {
         let mut res = Vec::new();
-        self._neighbors(pos, dir, start_cost, cfg.min_run, cfg.max_run, &mut res);
+        self._neighbors(pos, dir, 0, cfg.min_run, cfg.max_run, &mut res);
         res
     }
 
     fn _neighbors(
         &self,
-        pos: (usize, usize),
+        pos: Pos,
         dir: Dir,
         start_cost: u64,
         min_run: usize,
         max_run: usize,
-        res: &mut Vec<State>,
+        res: &mut Vec<(Node, u64)>,
     ) {
         let nmr = if min_run > 0 { min_run - 1 } else { 0 };
         if max_run > 0 {
-            if let Some((i, j)) = self.step(pos, dir) {
-                let cost = self.costs[i][j];
-                self._neighbors((i, j), dir, start_cost + cost, nmr, max_run - 1, res);
+            if let Some(next) = self.costs.step(pos, dir.offset()) {
+                let cost = self.costs[next];
+                self._neighbors(next, dir, start_cost + cost, nmr, max_run - 1, res);
             }
         }
         if min_run == 0 {
-            res.push(State {
-                pos,
-                cost: start_cost,
-                next_dir: dir.turn_left(),
-            });
-            res.push(State {
-                pos,
-                cost: start_cost,
-                next_dir: dir.turn_right(),
-            });
-        }
-    }
-
-    fn step(&self, pos: (usize, usize), dir: Dir) -> Option<(usize, usize)> {
-        match (pos, dir) {
-            ((i, j), Dir::Up) if i > 0 => Some((i - 1, j)),
-            ((i, j), Dir::Down) if i + 1 < self.height => Some((i + 1, j)),
-            ((i, j), Dir::Left) if j > 0 => Some((i, j - 1)),
-            ((i, j), Dir::Right) if j + 1 < self.width => Some((i, j + 1)),
-            _ => None,
+            res.push(((pos, dir.turn_left()), start_cost));
+            res.push(((pos, dir.turn_right()), start_cost));
         }
     }
 }
 
-#[derive(Copy, Clone, Eq, PartialEq)]
-struct State {
-    cost: u64,
-    pos: (usize, usize),
-    next_dir: Dir,
-}
-
-impl Ord for State {
-    fn cmp(&self, other: &Self) -> Ordering {
-        // Note that the ordering is flipped.
-        // Cost is the most important, we compare the others because this needs to be != if the
-        // States aren't identical.
-        (other.cost, other.pos, other.next_dir).cmp(&(self.cost, self.pos, self.next_dir))
-    }
-}
-
-impl PartialOrd for State {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 enum Dir {
     Up,
     Down,
     Left,
     Right,
-    Start,
 }
 
 impl Dir {
+    fn offset(&self) -> (isize, isize) {
+        match self {
+            Dir::Up => (-1, 0),
+            Dir::Down => (1, 0),
+            Dir::Left => (0, -1),
+            Dir::Right => (0, 1),
+        }
+    }
+
     fn turn_left(&self) -> Dir {
         match self {
             Dir::Up => Dir::Left,
             Dir::Left => Dir::Down,
             Dir::Down => Dir::Right,
             Dir::Right => Dir::Up,
-            _ => unreachable!(),
         }
     }
 
@@ -213,14 +144,13 @@ impl Dir {
             Dir::Left => Dir::Up,
             Dir::Down => Dir::Left,
             Dir::Right => Dir::Down,
-            _ => unreachable!(),
         }
     }
 }
 
 #[cfg(test)]
 mod test {
-    const TEST_INPUT: &'static str = r"2413432311323
+    const TEST_INPUT: &str = r"2413432311323
 3215453535623
 3255245654254
 3446585845452
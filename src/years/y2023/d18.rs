@@ -1,19 +1,23 @@
 use std::cmp::{max, min};
 use std::fmt::Display;
 
+use crate::vis::Visualizer;
+
 // Handy references:
 // - https://doc.rust-lang.org/std/iter/trait.Iterator.html
 // - https://docs.rs/itertools/0.8.2/itertools/trait.Itertools.html
 // - https://docs.rs/regex/latest/regex/struct.Regex.html
 
-pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2023, day = 18, part = 1, label = "d18::part1")]
+pub fn part1(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let directions = parse(&input);
     let edges = dig(&directions, vis);
     let filled = fill(&edges, vis);
     Box::new(filled)
 }
 
-pub fn part2(input: String, _vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2023, day = 18, part = 2, label = "d18::part2")]
+pub fn part2(input: String, _vis: &Visualizer) -> Box<dyn Display> {
     fn flip(directions: Vec<Direction>) -> Vec<Direction> {
         directions
             .into_iter()
@@ -39,8 +43,8 @@ pub fn part2(input: String, _vis: bool) -> Box<dyn Display> {
 
     let directions = parse(&input);
     let directions = flip(directions);
-    let edges = dig(&directions, false);
-    let filled = fill(&edges, false);
+    let edges = dig(&directions, &Visualizer::off());
+    let filled = fill(&edges, &Visualizer::off());
     Box::new(filled)
 }
 
@@ -49,7 +53,7 @@ struct DigRes {
     perimeter: isize,
 }
 
-fn dig(directions: &[Direction], vis: bool) -> DigRes {
+fn dig(directions: &[Direction], vis: &Visualizer) -> DigRes {
     let mut pos = (0, 0);
     let mut vertices = Vec::with_capacity(directions.len() + 1);
     vertices.push(pos);
@@ -73,7 +77,7 @@ fn dig(directions: &[Direction], vis: bool) -> DigRes {
         min_col = min(min_col, pos.1);
         max_col = max(max_col, pos.1);
     }
-    if vis {
+    if vis.on() {
         fn walk(from: &(isize, isize), to: &(isize, isize)) -> Vec<(isize, isize)> {
             let (r1, r2) = if from.0 > to.0 {
                 (to.0, from.0)
@@ -93,8 +97,8 @@ fn dig(directions: &[Direction], vis: bool) -> DigRes {
             }
             res
         }
-        if vis {
-            println!("after digging:");
+        if vis.on() {
+            vis.log("after digging:");
         }
         let height = max_row - min_row + 1;
         let width = max_col - min_col + 1;
@@ -113,7 +117,7 @@ fn dig(directions: &[Direction], vis: bool) -> DigRes {
         }
         for row in trenches {
             let row: String = row.into_iter().collect();
-            println!("{row}");
+            vis.log(&row);
         }
     }
     DigRes {
@@ -122,9 +126,9 @@ fn dig(directions: &[Direction], vis: bool) -> DigRes {
     }
 }
 
-fn fill(edges: &DigRes, vis: bool) -> isize {
-    if vis {
-        println!("after filling:");
+fn fill(edges: &DigRes, vis: &Visualizer) -> isize {
+    if vis.on() {
+        vis.log("after filling:");
     }
     // from https://www.linkedin.com/advice/1/how-do-you-calculate-area-perimeter-irregular-polygon#:~:text=To%20calculate%20the%20area%20of,is%20the%20number%20of%20vertices.
     fn term(from: &(isize, isize), to: &(isize, isize)) -> isize {
@@ -173,7 +177,7 @@ enum Dir {
 
 #[cfg(test)]
 mod test {
-    const TEST_INPUT: &'static str = r"R 6 (#70c710)
+    const TEST_INPUT: &str = r"R 6 (#70c710)
 D 5 (#0dc571)
 L 2 (#5713f0)
 D 2 (#d2c081)
@@ -1,36 +1,40 @@
 use std::collections::HashMap;
 use std::fmt::Display;
-use std::ops::RangeInclusive;
+
+use crate::{interval, vis::Visualizer};
 
 // Handy references:
 // - https://doc.rust-lang.org/std/iter/trait.Iterator.html
 // - https://docs.rs/itertools/0.8.2/itertools/trait.Itertools.html
 // - https://docs.rs/regex/latest/regex/struct.Regex.html
 
-pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2023, day = 19, part = 1, label = "d19::part1")]
+pub fn part1(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let (workflows, parts) = parse(&input);
 
     let mut sum = 0;
     for p in parts {
-        if vis {
-            print!("{p}: ");
+        // Where the part went, when visualizing.
+        let mut trail = String::new();
+        if vis.on() {
+            trail = format!("{p}: ");
         }
         let mut wf = workflows.get("in").unwrap();
         loop {
-            if vis {
-                print!("{} -> ", wf.name);
+            if vis.on() {
+                trail.push_str(&format!("{} -> ", wf.name));
             }
             match wf.apply(&p) {
                 Destination::Reject => {
-                    if vis {
-                        println!("R");
+                    if vis.on() {
+                        vis.log(format!("{trail}R"));
                     }
                     break;
                 }
                 Destination::Accept => {
                     let psum = p.x + p.m + p.a + p.s;
-                    if vis {
-                        println!("A ({psum})");
+                    if vis.on() {
+                        vis.log(format!("{trail}A ({psum})"));
                     }
                     sum += psum;
                     break;
@@ -45,31 +49,22 @@ pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
     Box::new(sum)
 }
 
-pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2023, day = 19, part = 2, label = "d19::part2")]
+pub fn part2(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let (workflows, _) = parse(&input);
 
     let mut res = Vec::new();
-    try_all("in", SimulatedPart::new(), &workflows, &mut res, vis);
-    if vis {
-        res.sort_by_key(|(p, _)| {
-            (
-                *p.x.start(),
-                *p.x.end(),
-                *p.m.start(),
-                *p.m.end(),
-                *p.a.start(),
-                *p.a.end(),
-                *p.s.start(),
-                *p.s.end(),
-            )
-        });
+    let all = SimulatedPart::new([1..4001, 1..4001, 1..4001, 1..4001]);
+    try_all("in", all, &workflows, &mut res, vis);
+    if vis.on() {
+        res.sort_by_key(|(p, _)| p.sides().clone().map(|r| (r.start, r.end)));
         for (p, ok) in &res {
-            println!(" {} {p:?}", if *ok { "A" } else { "R" });
+            vis.log(format!(" {} {p:?}", if *ok { "A" } else { "R" }));
         }
     }
-    let sum: usize = res
+    let sum: Num = res
         .into_iter()
-        .filter_map(|(p, ok)| if ok { Some(p.size()) } else { None })
+        .filter_map(|(p, ok)| if ok { Some(p.volume()) } else { None })
         .sum();
     Box::new(sum)
 }
@@ -81,26 +76,26 @@ fn try_all(
     mut part: SimulatedPart,
     workflows: &Workflows,
     res: &mut Result,
-    vis: bool,
+    vis: &Visualizer,
 ) {
     fn recurse(
         dest: &Destination,
         part: SimulatedPart,
         workflows: &Workflows,
         res: &mut Result,
-        vis: bool,
+        vis: &Visualizer,
         name: &str,
     ) {
         match dest {
             Destination::Reject => {
-                if vis {
-                    println!("[{name}] REJECT {part:?}");
+                if vis.on() {
+                    vis.log(format!("[{name}] REJECT {part:?}"));
                 }
                 res.push((part, false));
             }
             Destination::Accept => {
-                if vis {
-                    println!("[{name}] ACCEPT {part:?}");
+                if vis.on() {
+                    vis.log(format!("[{name}] ACCEPT {part:?}"));
                 }
                 res.push((part, true));
             }
@@ -110,31 +105,31 @@ fn try_all(
 
     let vvv = name == "LKSJDFLKJSDLKFJ"; //name == "in" || name == "pv";
     if vvv {
-        println!("---WORKFLOW {name:?} with {part:?}---");
+        vis.log(format!("---WORKFLOW {name:?} with {part:?}---"));
     }
 
     let wf = workflows.get(name).unwrap();
     for rule in &wf.rules {
         if vvv {
-            println!("[{name}] Rule {rule}:");
+            vis.log(format!("[{name}] Rule {rule}:"));
         }
         let (cond_true, cond_false) = match &rule.cond {
-            Condition::Lt(f, n) => part.split_at(f, *n),
+            Condition::Lt(f, n) => part.split(f.dim(), *n),
             Condition::Gt(f, n) => {
-                let (cond_false, cond_true) = part.split_at(f, n + 1);
+                let (cond_false, cond_true) = part.split(f.dim(), n + 1);
                 (cond_true, cond_false)
             }
             Condition::Always => {
                 if vvv {
-                    println!("[{name}]  {part:?} -> {}", rule.dest);
+                    vis.log(format!("[{name}]  {part:?} -> {}", rule.dest));
                 }
                 recurse(&rule.dest, part, workflows, res, vis, name);
                 return;
             }
         };
         if vvv {
-            println!("[{name}]   true:  {cond_true:?} -> {}", rule.dest);
-            println!("[{name}]   false: {cond_false:?}");
+            vis.log(format!("[{name}]   true:  {cond_true:?} -> {}", rule.dest));
+            vis.log(format!("[{name}]   false: {cond_false:?}"));
         }
         if let Some(part) = cond_true {
             recurse(&rule.dest, part, workflows, res, vis, name);
@@ -147,72 +142,8 @@ fn try_all(
     unreachable!()
 }
 
-#[derive(Debug)]
-struct SimulatedPart {
-    x: RangeInclusive<Num>,
-    m: RangeInclusive<Num>,
-    a: RangeInclusive<Num>,
-    s: RangeInclusive<Num>,
-}
-
-impl SimulatedPart {
-    fn new() -> Self {
-        Self {
-            x: 1..=4000,
-            m: 1..=4000,
-            a: 1..=4000,
-            s: 1..=4000,
-        }
-    }
-
-    fn split_at(self, f: &Field, v: Num) -> (Option<Self>, Option<Self>) {
-        fn split<F>(
-            r: RangeInclusive<Num>,
-            v: Num,
-            f: F,
-        ) -> (Option<SimulatedPart>, Option<SimulatedPart>)
-        where
-            F: Fn(RangeInclusive<Num>) -> SimulatedPart,
-        {
-            if *r.start() >= v {
-                (None, Some(f(r)))
-            } else if *r.end() < v {
-                (Some(f(r.clone())), None)
-            } else {
-                (Some(f(*r.start()..=v - 1)), Some(f(v..=*r.end())))
-            }
-        }
-
-        fn mk(
-            x: &RangeInclusive<Num>,
-            m: &RangeInclusive<Num>,
-            a: &RangeInclusive<Num>,
-            s: &RangeInclusive<Num>,
-        ) -> SimulatedPart {
-            SimulatedPart {
-                x: x.clone(),
-                m: m.clone(),
-                a: a.clone(),
-                s: s.clone(),
-            }
-        }
-
-        let Self { x, m, a, s } = self;
-        match f {
-            Field::X => split(x, v, |nx| mk(&nx, &m, &a, &s)),
-            Field::M => split(m, v, |nm| mk(&x, &nm, &a, &s)),
-            Field::A => split(a, v, |na| mk(&x, &m, &na, &s)),
-            Field::S => split(s, v, |ns| mk(&x, &m, &a, &ns)),
-        }
-    }
-
-    fn size(self) -> usize {
-        fn span(r: RangeInclusive<Num>) -> usize {
-            (1 + r.end() - r.start()) as usize
-        }
-        span(self.x) * span(self.m) * span(self.a) * span(self.s)
-    }
-}
+// Every part with x, m, a and s in these ranges.
+type SimulatedPart = interval::Box<Num, 4>;
 
 type Workflows = HashMap<String, Workflow>;
 
@@ -301,7 +232,7 @@ fn parse_dest(dest: &str) -> Destination {
     }
 }
 
-type Num = u32;
+type Num = u64;
 
 struct Part {
     x: Num,
@@ -371,6 +302,16 @@ enum Field {
 }
 
 impl Field {
+    // Which side of a SimulatedPart it is.
+    fn dim(&self) -> usize {
+        match self {
+            Self::X => 0,
+            Self::M => 1,
+            Self::A => 2,
+            Self::S => 3,
+        }
+    }
+
     fn get(&self, part: &Part) -> Num {
         match self {
             Self::X => part.x,
@@ -414,7 +355,7 @@ impl Display for Destination {
 
 #[cfg(test)]
 mod test {
-    const TEST_INPUT: &'static str = r"px{a<2006:qkq,m>2090:A,rfg}
+    const TEST_INPUT: &str = r"px{a<2006:qkq,m>2090:A,rfg}
 pv{a>1716:R,A}
 lnx{m>1548:A,A}
 rfg{s<537:gd,x>2440:R,A}
@@ -1,60 +1,72 @@
 use std::collections::{HashMap, VecDeque};
 use std::fmt::Display;
 
+use crate::vis::Visualizer;
+
 // Handy references:
 // - https://doc.rust-lang.org/std/iter/trait.Iterator.html
 // - https://docs.rs/itertools/0.8.2/itertools/trait.Itertools.html
 // - https://docs.rs/regex/latest/regex/struct.Regex.html
 
-pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2023, day = 20, part = 1, label = "d20::part1")]
+pub fn part1(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let mut circuit = parse(&input);
     let mut low = 0;
     let mut high = 0;
     for i in 0..1000 {
-        if vis && i < 4 {
-            println!("---- CYCLE ----");
+        if vis.on() && i < 4 {
+            vis.log("---- CYCLE ----");
         }
-        let (l, h, _) = cycle(&mut circuit, vis && i < 4);
+        let (l, h, _) = cycle(
+            &mut circuit,
+            &match i < 4 {
+                true => vis.clone(),
+                false => Visualizer::off(),
+            },
+        );
         low += l;
         high += h;
     }
-    if vis {
-        println!("low pulses: {low}");
-        println!("high pulses: {high}");
+    if vis.on() {
+        vis.log(format!("low pulses: {low}"));
+        vis.log(format!("high pulses: {high}"));
     }
     Box::new(low * high)
 }
 
-pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2023, day = 20, part = 2, label = "d20::part2", unsolved)]
+pub fn part2(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let mut circuit = parse(&input);
     for i in 1..10 {
-        if vis {
-            println!("---- CYCLE ----");
+        if vis.on() {
+            vis.log("---- CYCLE ----");
         }
 
         let (_, _, rx) = cycle(&mut circuit, vis);
 
-        if vis {
-            println!(
+        if vis.on() {
+            vis.log(format!(
                 "after {i}: {rx:?} ff={} in={}",
                 circuit.flip_flop_states.len(),
                 circuit.input_states.len()
-            );
+            ));
             for m in circuit.modules.values() {
                 match m.mod_type {
                     ModuleType::Broadcaster => (),
-                    ModuleType::FlipFlop => {
-                        println!("%{}: {:?}", m.name, circuit.flip_flop_states.get(&m.name))
-                    }
+                    ModuleType::FlipFlop => vis.log(format!(
+                        "%{}: {:?}",
+                        m.name,
+                        circuit.flip_flop_states.get(&m.name)
+                    )),
                     ModuleType::Conjunction => {
-                        print!("&{}:", m.name);
+                        let mut line = format!("&{}:", m.name);
                         for input_name in &m.inputs {
                             let input = circuit
                                 .input_states
                                 .get(&(m.name.clone(), input_name.to_owned()));
-                            print!(" {input_name}:{input:?}");
+                            line.push_str(&format!(" {input_name}:{input:?}"));
                         }
-                        println!();
+                        vis.log(line);
                     }
                 };
             }
@@ -66,7 +78,7 @@ pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
 
 type RxCount = (usize, usize);
 
-fn cycle(circuit: &mut Circuit, vis: bool) -> (usize, usize, RxCount) {
+fn cycle(circuit: &mut Circuit, vis: &Visualizer) -> (usize, usize, RxCount) {
     let mut pending: VecDeque<(String, String, bool, Vec<String>)> = vec![(
         "button".to_owned(),
         "broadcaster".to_owned(),
@@ -80,11 +92,11 @@ fn cycle(circuit: &mut Circuit, vis: bool) -> (usize, usize, RxCount) {
     let mut rx_high = 0;
 
     while let Some((src, dest, pulse, mut trail)) = pending.pop_front() {
-        if vis {
-            println!(
+        if vis.on() {
+            vis.log(format!(
                 "{src} -{}-> {dest} (via {trail:?})",
                 if pulse { "high" } else { "low" }
-            );
+            ));
         }
 
         if pulse {
@@ -4,20 +4,24 @@ use std::fmt::Display;
 
 use num::integer::Roots;
 
+use crate::vis::{Frame, Visualizer};
+
 // Handy references:
 // - https://doc.rust-lang.org/std/iter/trait.Iterator.html
 // - https://docs.rs/itertools/0.8.2/itertools/trait.Itertools.html
 // - https://docs.rs/regex/latest/regex/struct.Regex.html
 
-pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2023, day = 21, part = 1, label = "d21::part1")]
+pub fn part1(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let parsed = parse(&input);
     let res = solve(Part1, parsed, 64, vis);
     Box::new(res)
 }
 
-pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2023, day = 21, part = 2, label = "d21::part2", slow)]
+pub fn part2(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let parsed = parse(&input);
-    if vis {
+    if vis.on() {
         // Usage: cargo run -- --year 2023 --day 21 --part 2 --visualize
         //
         // This shows how many types of each tile there are, the square root of the number of
@@ -40,9 +44,9 @@ pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
                 *counts.entry(*tile).or_insert(0) += 1;
                 counts
             });
-        println!("by type: {:?}", by_type);
+        vis.log(format!("by type: {:?}", by_type));
         let garden_tiles = by_type.get(&Tile::Garden).unwrap();
-        println!("  sqrt(garden) = {}", garden_tiles.sqrt());
+        vis.log(format!("  sqrt(garden) = {}", garden_tiles.sqrt()));
 
         let mut tracker = HashMap::new();
         let mut state = initial_state(&parsed);
@@ -56,12 +60,12 @@ pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
             let e = tracker.entry(possible).or_insert(i);
             if *e != i {
                 matches += 1;
-                println!("{possible} options at {e} and {i}");
+                vis.log(format!("{possible} options at {e} and {i}"));
             }
         }
         return Box::new("unexpected");
     }
-    let res = solve(Part2, parsed, 26501365, false);
+    let res = solve(Part2, parsed, 26501365, &Visualizer::off());
     Box::new(res)
 }
 
@@ -114,33 +118,33 @@ struct State {
     possible: HashSet<(isize, isize)>,
 }
 
-fn solve(p: impl Part + Copy, parsed: Parsed, steps: usize, vis: bool) -> usize {
+fn solve(p: impl Part + Copy, parsed: Parsed, steps: usize, vis: &Visualizer) -> usize {
     let mut state = initial_state(&parsed);
 
     for i in 1..=steps {
         state = step(state, p, &parsed);
-        if vis {
-            println!("---AFTER STEP {i}---");
-            for r in state.min_row..=state.max_row {
-                for c in state.min_col..=state.max_col {
-                    print!(
-                        "{}",
-                        match (
-                            state.possible.contains(&(r, c)),
-                            parsed
-                                .map
-                                .get(&(modwrap(r, parsed.rows), modwrap(c, parsed.cols)))
-                        ) {
-                            (true, _) => 'O',
-                            (false, Some(Tile::Garden)) => '.',
-                            (false, Some(Tile::Rock)) => '#',
-                            (false, Some(Tile::Start)) => 'S',
-                            _ => unreachable!(),
-                        }
-                    );
-                }
-                println!();
-            }
+        if vis.on() {
+            let frame = Frame::draw(
+                format!("---AFTER STEP {i}---"),
+                (state.min_col as i64, state.min_row as i64),
+                (state.max_col as i64, state.max_row as i64),
+                |c, r| {
+                    let (r, c) = (r as isize, c as isize);
+                    match (
+                        state.possible.contains(&(r, c)),
+                        parsed
+                            .map
+                            .get(&(modwrap(r, parsed.rows), modwrap(c, parsed.cols))),
+                    ) {
+                        (true, _) => 'O',
+                        (false, Some(Tile::Garden)) => '.',
+                        (false, Some(Tile::Rock)) => '#',
+                        (false, Some(Tile::Start)) => 'S',
+                        _ => unreachable!(),
+                    }
+                },
+            );
+            vis.frame(frame);
         }
     }
 
@@ -240,7 +244,9 @@ fn modwrap(n: isize, d: isize) -> isize {
 
 #[cfg(test)]
 mod test {
-    const TEST_INPUT: &'static str = r"...........
+    use crate::vis::{Stdout, Visualizer};
+
+    const TEST_INPUT: &str = r"...........
 .....###.#.
 .###.##..#.
 ..#.#...#..
@@ -265,7 +271,12 @@ mod test {
     fn test_part1() {
         assert_eq!(
             16,
-            super::solve(super::Part1, super::parse(TEST_INPUT), 6, true)
+            super::solve(
+                super::Part1,
+                super::parse(TEST_INPUT),
+                6,
+                &Visualizer::new(Stdout)
+            )
         );
     }
 
@@ -273,7 +284,12 @@ mod test {
     fn test_part2_6() {
         assert_eq!(
             16,
-            super::solve(super::Part2, super::parse(TEST_INPUT), 6, false)
+            super::solve(
+                super::Part2,
+                super::parse(TEST_INPUT),
+                6,
+                &Visualizer::off()
+            )
         );
     }
 
@@ -281,7 +297,12 @@ mod test {
     fn test_part2_10() {
         assert_eq!(
             50,
-            super::solve(super::Part2, super::parse(TEST_INPUT), 10, true)
+            super::solve(
+                super::Part2,
+                super::parse(TEST_INPUT),
+                10,
+                &Visualizer::new(Stdout)
+            )
         );
     }
 
@@ -289,7 +310,12 @@ mod test {
     fn test_part2_50() {
         assert_eq!(
             1594,
-            super::solve(super::Part2, super::parse(TEST_INPUT), 50, false)
+            super::solve(
+                super::Part2,
+                super::parse(TEST_INPUT),
+                50,
+                &Visualizer::off()
+            )
         );
     }
 
@@ -297,7 +323,12 @@ mod test {
     fn test_part2_100() {
         assert_eq!(
             6536,
-            super::solve(super::Part2, super::parse(TEST_INPUT), 100, false)
+            super::solve(
+                super::Part2,
+                super::parse(TEST_INPUT),
+                100,
+                &Visualizer::off()
+            )
         );
     }
 
@@ -305,7 +336,7 @@ mod test {
     //fn test_part2_500() {
     //    assert_eq!(
     //        167004,
-    //        super::solve(super::Part2, super::parse(TEST_INPUT), 500, false)
+    //        super::solve(super::Part2, super::parse(TEST_INPUT), 500, &Visualizer::off())
     //    );
     //}
 
@@ -313,7 +344,7 @@ mod test {
     //fn test_part2_1000() {
     //    assert_eq!(
     //        668697,
-    //        super::solve(super::Part2, super::parse(TEST_INPUT), 1000, false)
+    //        super::solve(super::Part2, super::parse(TEST_INPUT), 1000, &Visualizer::off())
     //    );
     //}
 
@@ -321,7 +352,7 @@ mod test {
     //fn test_part2_5000() {
     //    assert_eq!(
     //        16733044,
-    //        super::solve(super::Part2, super::parse(TEST_INPUT), 5000, false)
+    //        super::solve(super::Part2, super::parse(TEST_INPUT), 5000, &Visualizer::off())
     //    );
     //}
 }
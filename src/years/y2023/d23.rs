@@ -1,18 +1,22 @@
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::fmt::Display;
 
+use crate::vis::{Frame, Visualizer};
+
 // Handy references:
 // - https://doc.rust-lang.org/std/iter/trait.Iterator.html
 // - https://docs.rs/itertools/0.8.2/itertools/trait.Itertools.html
 // - https://docs.rs/regex/latest/regex/struct.Regex.html
 
-pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2023, day = 23, part = 1, label = "d23::part1")]
+pub fn part1(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let parsed = parse(&input);
     let longest_path = find_longest_path(&parsed, true, vis);
     Box::new(longest_path)
 }
 
-pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2023, day = 23, part = 2, label = "d23::part2", slow)]
+pub fn part2(input: String, vis: &Visualizer) -> Box<dyn Display> {
     // Hint: Maybe a simple Dijkstra would be faster?
     // https://www.reddit.com/r/adventofcode/comments/18ufl0o/algorithms_for_each_day/
     let parsed = parse(&input);
@@ -20,14 +24,14 @@ pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
     Box::new(longest_path)
 }
 
-fn find_longest_path(parsed: &Parsed, slippery: bool, vis: bool) -> usize {
+fn find_longest_path(parsed: &Parsed, slippery: bool, vis: &Visualizer) -> usize {
     let graph = trace(parsed, slippery);
-    if vis {
-        println!(
+    if vis.on() {
+        vis.log(format!(
             "{} edges, {} nodes",
             graph.count_edges(),
             graph.count_nodes()
-        );
+        ));
     }
 
     let mut to_visit: VecDeque<(Node, usize, Vec<Node>)> = VecDeque::new();
@@ -39,8 +43,10 @@ fn find_longest_path(parsed: &Parsed, slippery: bool, vis: bool) -> usize {
     let mut count = 0;
     while let Some((n, cost, visited)) = to_visit.pop_front() {
         count += 1;
-        if vis {
-            println!("[{count}] visiting {n:?} from a {cost}-long path ({visited:?})");
+        if vis.on() {
+            vis.log(format!(
+                "[{count}] visiting {n:?} from a {cost}-long path ({visited:?})"
+            ));
         }
 
         if n.0 == parsed.rows - 1 {
@@ -56,12 +62,12 @@ fn find_longest_path(parsed: &Parsed, slippery: bool, vis: bool) -> usize {
             .unwrap_or_else(|| panic!("expected to find edges from {n:?}"));
         for edge in edges {
             if visited.contains(&edge.to) {
-                if vis {
-                    println!(" - skip {:?}, it's already in this list", edge.to);
+                if vis.on() {
+                    vis.log(format!(" - skip {:?}, it's already in this list", edge.to));
                 }
             } else {
-                if vis {
-                    println!(" - consider {:?} (+{})", edge.to, edge.path.len());
+                if vis.on() {
+                    vis.log(format!(" - consider {:?} (+{})", edge.to, edge.path.len()));
                 }
                 let mut extended = visited.clone();
                 extended.push(edge.to);
@@ -72,8 +78,8 @@ fn find_longest_path(parsed: &Parsed, slippery: bool, vis: bool) -> usize {
 
     let (maxcost, path) = maxcost.unwrap();
 
-    if vis {
-        println!("looped {count} times");
+    if vis.on() {
+        vis.log(format!("looped {count} times"));
         let mut longest_path: HashSet<Pos> = HashSet::new();
         for w in path.windows(2) {
             let from = &w[0];
@@ -88,16 +94,16 @@ fn find_longest_path(parsed: &Parsed, slippery: bool, vis: bool) -> usize {
             longest_path.extend(edge.path.iter());
         }
 
-        for (r, row) in parsed.map.iter().enumerate() {
-            for (c, tile) in row.iter().enumerate() {
-                if longest_path.contains(&(r, c)) {
-                    print!("O");
-                } else {
-                    print!("{}", tile);
-                }
-            }
-            println!();
-        }
+        let rows = parsed.map.iter().enumerate().map(|(r, row)| {
+            row.iter()
+                .enumerate()
+                .map(|(c, tile)| match longest_path.contains(&(r, c)) {
+                    true => "O".to_string(),
+                    false => tile.to_string(),
+                })
+                .collect()
+        });
+        vis.frame(Frame::new("longest path", rows));
     }
 
     maxcost
@@ -331,7 +337,7 @@ fn parse(input: &str) -> Parsed {
 
 #[cfg(test)]
 mod test {
-    const TEST_INPUT: &'static str = r"#.#####################
+    const TEST_INPUT: &str = r"#.#####################
 #.......#########...###
 #######.#########.#.###
 ###.....#.>.>.###.#.###
@@ -2,6 +2,8 @@ use std::fmt::Display;
 
 use num::rational::Ratio;
 
+use crate::vis::Visualizer;
+
 // Hint: Gaussian elimination? Except I'm not sure how to make the equations into linear ones.
 // https://www.reddit.com/r/adventofcode/comments/18ufl0o/algorithms_for_each_day/
 
@@ -10,7 +12,8 @@ use num::rational::Ratio;
 // - https://docs.rs/itertools/0.8.2/itertools/trait.Itertools.html
 // - https://docs.rs/regex/latest/regex/struct.Regex.html
 
-pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2023, day = 24, part = 1, label = "d24::part1")]
+pub fn part1(input: String, vis: &Visualizer) -> Box<dyn Display> {
     Box::new(solve1(
         &input,
         (200000000000000, 200000000000000),
@@ -19,10 +22,11 @@ pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
     ))
 }
 
-pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2023, day = 24, part = 2, label = "d24::part2", unsolved)]
+pub fn part2(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let stones = parse(&input);
-    if vis {
-        println!("{stones:?}");
+    if vis.on() {
+        vis.log(format!("{stones:?}"));
     }
     // Looking for (xS,yS,zS) @ (dxS,dyS,dzS).
     // t0 where (xS+t0*dxS, yS+t0*dyS, zS+t0*dzS) == (x0+t0*dx0, y0+t0*dy0, z0+t0*dz0)
@@ -74,15 +78,15 @@ pub fn part2(input: String, vis: bool) -> Box<dyn Display> {
 
 type N = i128;
 
-fn solve1(input: &str, min: (N, N), max: (N, N), vis: bool) -> usize {
+fn solve1(input: &str, min: (N, N), max: (N, N), vis: &Visualizer) -> usize {
     let stones = parse(input);
     let mut res = 0;
     for (i, a) in stones.iter().enumerate() {
         for b in &stones[i + 1..] {
-            if vis {
-                println!();
-                println!("Hailstone A: {a}");
-                println!("Hailstone B: {b}");
+            if vis.on() {
+                vis.log("");
+                vis.log(format!("Hailstone A: {a}"));
+                vis.log(format!("Hailstone B: {b}"));
             }
             if intersects(a, b, min, max, vis) {
                 res += 1;
@@ -92,41 +96,49 @@ fn solve1(input: &str, min: (N, N), max: (N, N), vis: bool) -> usize {
     res
 }
 
-fn intersects(a: &Hailstone, b: &Hailstone, min: (N, N), max: (N, N), vis: bool) -> bool {
+fn intersects(a: &Hailstone, b: &Hailstone, min: (N, N), max: (N, N), vis: &Visualizer) -> bool {
     let a_line = slope_intercept(a);
     let b_line = slope_intercept(b);
     let x_range = min.0..=max.0;
     let y_range = min.1..=max.1;
     match intersection(a_line, b_line) {
         None => {
-            if vis {
-                println!("Hailstones' paths do not intersect");
+            if vis.on() {
+                vis.log("Hailstones' paths do not intersect");
             }
             false
         }
         Some((x, y))
             if !x_range.contains(&x.to_integer()) || !y_range.contains(&y.to_integer()) =>
         {
-            if vis {
-                println!("Hailstones' paths intersect outside the area (at x={x}, y={y})")
+            if vis.on() {
+                vis.log(format!(
+                    "Hailstones' paths intersect outside the area (at x={x}, y={y})"
+                ))
             }
             false
         }
         Some((x, y)) if is_past(a, (x, y)) => {
-            if vis {
-                println!("Hailstones' paths intersect in the past for A (at x={x}, y={y})");
+            if vis.on() {
+                vis.log(format!(
+                    "Hailstones' paths intersect in the past for A (at x={x}, y={y})"
+                ));
             }
             false
         }
         Some((x, y)) if is_past(b, (x, y)) => {
-            if vis {
-                println!("Hailstones' paths intersect in the past for B (at x={x}, y={y})");
+            if vis.on() {
+                vis.log(format!(
+                    "Hailstones' paths intersect in the past for B (at x={x}, y={y})"
+                ));
             }
             false
         }
         Some((x, y)) => {
-            if vis {
-                println!("Hailstones' paths intersect inside the area (at x={x}, y={y})");
+            if vis.on() {
+                vis.log(format!(
+                    "Hailstones' paths intersect inside the area (at x={x}, y={y})"
+                ));
             }
             true
         }
@@ -226,7 +238,9 @@ fn parse(input: &str) -> Vec<Hailstone> {
 
 #[cfg(test)]
 mod test {
-    const TEST_INPUT: &'static str = r"19, 13, 30 @ -2,  1, -2
+    use crate::vis::{Stdout, Visualizer};
+
+    const TEST_INPUT: &str = r"19, 13, 30 @ -2,  1, -2
 18, 19, 22 @ -1, -1, -2
 20, 25, 34 @ -2, -2, -4
 12, 31, 28 @ -1, -2, -1
@@ -234,7 +248,10 @@ mod test {
 
     #[test]
     fn part1() {
-        assert_eq!(2, super::solve1(TEST_INPUT, (7, 7), (27, 27), true));
+        assert_eq!(
+            2,
+            super::solve1(TEST_INPUT, (7, 7), (27, 27), &Visualizer::new(Stdout))
+        );
     }
 
     //crate::test::aoc_test!(part2, TEST_INPUT, 47);
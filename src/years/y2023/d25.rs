@@ -6,6 +6,8 @@ use rand::rngs::ThreadRng;
 use rand::seq::SliceRandom;
 use rand::thread_rng;
 
+use crate::vis::Visualizer;
+
 // Handy references:
 // - https://doc.rust-lang.org/std/iter/trait.Iterator.html
 // - https://docs.rs/itertools/0.8.2/itertools/trait.Itertools.html
@@ -13,20 +15,21 @@ use rand::thread_rng;
 
 const NODE_LABEL_LEN: usize = 3;
 
-pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2023, day = 25, part = 1, label = "d25::part1", slow)]
+pub fn part1(input: String, vis: &Visualizer) -> Box<dyn Display> {
     let g = parse(&input);
-    if vis {
-        println!("{} nodes", g.nodes.len());
-        println!("{} edges", g.edges.len());
+    if vis.on() {
+        vis.log(format!("{} nodes", g.nodes.len()));
+        vis.log(format!("{} edges", g.edges.len()));
     }
 
     let inv_sqrt_2 = Ratio::new(99, 70).recip();
     let mut rng = thread_rng();
 
     let g = fastmincut(g, &inv_sqrt_2, &mut rng);
-    if vis {
-        println!("final nodes = {:?}", g.nodes);
-        println!("final edges = {:?}", g.edges);
+    if vis.on() {
+        vis.log(format!("final nodes = {:?}", g.nodes));
+        vis.log(format!("final edges = {:?}", g.edges));
     }
     assert_eq!(2, g.nodes.len());
     assert_eq!(3, g.edges.len());
@@ -35,7 +38,8 @@ pub fn part1(input: String, vis: bool) -> Box<dyn Display> {
     Box::new(a * b)
 }
 
-pub fn part2(_input: String, _vis: bool) -> Box<dyn Display> {
+#[aoc(year = 2023, day = 25, part = 2, label = "d25::part2", unsolved)]
+pub fn part2(_input: String, _vis: &Visualizer) -> Box<dyn Display> {
     Box::new("todo")
 }
 
@@ -129,7 +133,7 @@ fn parse(input: &str) -> Graph {
 
 #[cfg(test)]
 mod test {
-    const TEST_INPUT: &'static str = r"jqt: rhn xhk nvd
+    const TEST_INPUT: &str = r"jqt: rhn xhk nvd
 rsh: frs pzl lsr
 xhk: hfx
 cmg: qnr nvd lhk bvb